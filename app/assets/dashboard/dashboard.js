@@ -0,0 +1,270 @@
+"use strict";
+
+const TOKEN_KEY = "api-gen-admin-token";
+const LIVE_INTERVAL_MS = 2000;
+
+const state = {
+  port: null,
+  editing: null,
+  live: null,
+};
+
+const $ = (id) => document.getElementById(id);
+
+async function api(method, uri, body) {
+  const headers = { "Content-Type": "application/json" };
+  const token = sessionStorage.getItem(TOKEN_KEY);
+  if (token) {
+    headers.Authorization = `Bearer ${token}`;
+  }
+
+  const response = await fetch(uri, {
+    method,
+    headers,
+    body: body === undefined ? undefined : JSON.stringify(body),
+  });
+  const json = await response.json().catch(() => null);
+
+  if (!response.ok) {
+    throw new Error(
+      json?.failureMessage ?? `${method} ${uri} failed with ${response.status}`,
+    );
+  }
+  return json;
+}
+
+function report(message, isError = false) {
+  const status = $("status");
+  status.textContent = message;
+  status.className = isError ? "error" : "";
+}
+
+async function attempt(action) {
+  try {
+    await action();
+  } catch (error) {
+    report(error.message, true);
+  }
+}
+
+function cell(row, content) {
+  const td = row.insertCell();
+  if (content instanceof Node) {
+    td.append(content);
+  } else {
+    td.textContent = content;
+  }
+  return td;
+}
+
+function code(value) {
+  const element = document.createElement("code");
+  element.textContent =
+    typeof value === "string" ? value : JSON.stringify(value);
+  return element;
+}
+
+async function loadServers() {
+  const page = await api("GET", "/servers?limit=1000");
+  const list = $("server-list");
+  list.replaceChildren();
+
+  for (const server of page.items) {
+    const row = list.insertRow();
+    row.className = "selectable";
+    if (server.port === state.port) {
+      row.classList.add("selected");
+    }
+
+    const features = ["socket", "oidc", "cors", "rateLimit"]
+      .filter((feature) => server[feature])
+      .join(", ");
+
+    cell(row, server.port);
+    cell(row, server.routes);
+    cell(row, server.grpc);
+    cell(row, features);
+    row.addEventListener("click", () => attempt(() => selectServer(server)));
+  }
+
+  if (state.port && !page.items.some(({ port }) => port === state.port)) {
+    closeServer();
+  }
+}
+
+async function selectServer(server) {
+  state.port = server.port;
+  $("server").hidden = false;
+  $("server-port").textContent = server.port;
+  $("captured").hidden = !server.socket;
+  $("rate-limits").hidden = !server.rateLimit;
+
+  await Promise.all([
+    loadServers(),
+    loadRoutes(),
+    server.socket ? loadCaptured() : null,
+    server.rateLimit ? loadRateLimits() : null,
+  ]);
+}
+
+function closeServer() {
+  state.port = null;
+  $("server").hidden = true;
+  setLive(false);
+}
+
+async function loadRoutes() {
+  const query = new URLSearchParams({ limit: "1000" });
+  if ($("filter-method").value) {
+    query.set("method", $("filter-method").value);
+  }
+  if ($("filter-path").value) {
+    query.set("path", $("filter-path").value);
+  }
+
+  const page = await api("GET", `/servers/${state.port}/routes?${query}`);
+  const list = $("route-list");
+  list.replaceChildren();
+
+  for (const route of page.items) {
+    const row = list.insertRow();
+    cell(row, route.method);
+    cell(row, route.path);
+    cell(row, code(route.response));
+
+    const edit = document.createElement("button");
+    edit.type = "button";
+    edit.textContent = "Edit";
+    edit.addEventListener("click", () => editRoute(route));
+    cell(row, edit);
+  }
+}
+
+async function loadCaptured() {
+  const messages = await api("GET", `/sockets/${state.port}/captured`);
+  const list = $("captured-list");
+  list.replaceChildren();
+
+  for (const message of messages) {
+    const row = list.insertRow();
+    cell(row, message.peer);
+    cell(row, code(message.data));
+  }
+}
+
+async function loadRateLimits() {
+  const counters = await api("GET", `/rate-limits/${state.port}`);
+  $("rate-limit-counters").textContent = JSON.stringify(counters, null, 2);
+}
+
+function setLive(enabled) {
+  $("live").checked = enabled;
+  clearInterval(state.live);
+  state.live = enabled
+    ? setInterval(() => attempt(loadCaptured), LIVE_INTERVAL_MS)
+    : null;
+}
+
+function editRoute(route) {
+  state.editing = route;
+  $("editor-title").textContent = `Edit ${route.method} ${route.path}`;
+  $("route-port").value = state.port;
+  $("route-method").value = route.method;
+  $("route-path").value = route.path;
+  $("route-response").value = JSON.stringify(route.response, null, 2);
+  $("route-response").focus();
+}
+
+function newRoute() {
+  state.editing = null;
+  $("editor-title").textContent = "New route";
+  $("route-form").reset();
+  $("route-port").value = state.port ?? "";
+}
+
+async function saveRoute() {
+  let response;
+  try {
+    response = JSON.parse($("route-response").value);
+  } catch (error) {
+    throw new Error(`Response is not valid JSON, ${error.message}`);
+  }
+
+  const { graphql, auth, rateLimit } = state.editing ?? {};
+  const registration = await api("POST", "/register", {
+    port: $("route-port").value,
+    method: $("route-method").value,
+    path: $("route-path").value,
+    graphql,
+    auth,
+    rateLimit,
+    response,
+  });
+
+  const { method, path } = registration.added;
+  report(`Saved ${method} ${path} on port ${registration.port}.`);
+  state.port = registration.port;
+  await refresh();
+}
+
+async function refresh() {
+  await loadServers();
+  if (state.port) {
+    const page = await api("GET", "/servers?limit=1000");
+    const server = page.items.find(({ port }) => port === state.port);
+    if (server) {
+      await selectServer(server);
+    }
+  }
+}
+
+$("credentials").addEventListener("submit", (event) => {
+  event.preventDefault();
+  sessionStorage.setItem(TOKEN_KEY, $("token").value);
+  attempt(refresh);
+});
+
+$("reset").addEventListener("click", () =>
+  attempt(async () => {
+    if (!confirm("Remove every stub except the pinned ones?")) {
+      return;
+    }
+    const { removed } = await api("POST", "/reset");
+    report(`Removed ${removed.length} server(s).`);
+    closeServer();
+    await loadServers();
+  }),
+);
+
+$("refresh").addEventListener("click", () => attempt(refresh));
+
+$("route-filter").addEventListener("submit", (event) => {
+  event.preventDefault();
+  attempt(loadRoutes);
+});
+
+$("route-form").addEventListener("submit", (event) => {
+  event.preventDefault();
+  attempt(saveRoute);
+});
+
+$("new-route").addEventListener("click", newRoute);
+
+$("live").addEventListener("change", (event) => setLive(event.target.checked));
+
+$("clear-captured").addEventListener("click", () =>
+  attempt(async () => {
+    await api("DELETE", `/sockets/${state.port}/captured`);
+    await loadCaptured();
+  }),
+);
+
+$("reset-rate-limits").addEventListener("click", () =>
+  attempt(async () => {
+    await api("DELETE", `/rate-limits/${state.port}`);
+    await loadRateLimits();
+  }),
+);
+
+$("token").value = sessionStorage.getItem(TOKEN_KEY) ?? "";
+attempt(loadServers);
@@ -0,0 +1,24 @@
+use std::{env, process::exit};
+
+use api_gen::ctl::Invocation;
+
+#[tokio::main]
+async fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let invocation = match Invocation::parse(&args, |name| env::var(name).ok())
+    {
+        Ok(invocation) => invocation,
+        Err(err) => {
+            eprintln!("{err}");
+            exit(2);
+        }
+    };
+
+    match invocation.run().await {
+        Ok(output) => println!("{output}"),
+        Err(err) => {
+            eprintln!("{err}");
+            exit(1);
+        }
+    }
+}
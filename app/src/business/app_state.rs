@@ -1,63 +1,445 @@
-use std::{collections::HashMap, sync::RwLock};
+use std::{collections::HashMap, sync::Arc};
 
+use tokio::{
+    sync::{Mutex, OwnedMutexGuard},
+    task::JoinSet,
+};
 use tracing::info;
 
 use crate::{
-    business::server::{
-        connection_establisher::ConnectionEstablisher, server::Server,
+    business::{
+        peer_sync::PeerSync,
+        server::{
+            connection_establisher::ConnectionEstablisher,
+            cors::CorsPolicy,
+            grpc::GrpcRegistry,
+            oidc::OidcProvider,
+            rate_limit::RateLimiter,
+            server::{Server, ServerSnapshot},
+            socket::SocketHandler,
+        },
+        storage::{RegistrationStorage, memory_storage::MemoryStorage},
+        store::{Store, store_error::StoreError},
+    },
+    logging::metrics::Metrics,
+    model::{
+        graphql_operation::GraphQlOperation,
+        http_method::HttpMethod,
+        internal::{
+            server_registration::{Registration, ServerRegistration},
+            stored_port::StoredPort,
+        },
+        port::Port,
+        request::registration_request::RegistrationRequest,
+        response::rate_limit_counter::RateLimitCounter,
+        route_path::RoutePath,
     },
-    model::internal::server_registration::ServerRegistration,
-    util::lock::{safe_read, safe_write},
+    security::admin_auth::AdminAuth,
 };
 
 pub struct AppState<T: ConnectionEstablisher> {
-    servers: RwLock<HashMap<String, Server>>,
+    servers: Store<HashMap<Port, Server>>,
+    port_locks: Store<HashMap<Port, Arc<Mutex<()>>>>,
+    storage: Arc<dyn RegistrationStorage>,
+    peer_sync: Option<PeerSync>,
     connection_establisher: T,
+    metrics: Arc<Metrics>,
+    admin_auth: Option<AdminAuth>,
+    pinned_registrations: Vec<RegistrationRequest>,
 }
 
 impl<T: ConnectionEstablisher> AppState<T> {
     pub fn new(connection_establisher: T) -> Self {
         Self {
-            servers: RwLock::new(HashMap::new()),
+            servers: Store::default(),
+            port_locks: Store::default(),
+            storage: Arc::new(MemoryStorage::default()),
+            peer_sync: None,
             connection_establisher,
+            metrics: Arc::default(),
+            admin_auth: None,
+            pinned_registrations: vec![],
         }
     }
 
+    pub fn with_server_store(
+        mut self,
+        servers: Store<HashMap<Port, Server>>,
+    ) -> Self {
+        self.servers = servers;
+        self
+    }
+
+    pub fn with_storage(
+        mut self,
+        storage: Arc<dyn RegistrationStorage>,
+    ) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    pub fn with_peer_sync(mut self, peer_sync: Option<PeerSync>) -> Self {
+        self.peer_sync = peer_sync;
+        self
+    }
+
+    pub fn with_admin_auth(mut self, admin_auth: Option<AdminAuth>) -> Self {
+        self.admin_auth = admin_auth;
+        self
+    }
+
+    pub fn with_pinned_registrations(
+        mut self,
+        pinned_registrations: Vec<RegistrationRequest>,
+    ) -> Self {
+        self.pinned_registrations = pinned_registrations;
+        self
+    }
+
     pub fn get_connection_establisher(&self) -> &T {
         &self.connection_establisher
     }
 
-    pub fn add_server(&self, port: &str, server: Server) {
+    pub fn get_metrics(&self) -> Arc<Metrics> {
+        self.metrics.clone()
+    }
+
+    pub fn get_admin_auth(&self) -> Option<AdminAuth> {
+        self.admin_auth.clone()
+    }
+
+    pub fn get_peer_sync(&self) -> Option<&PeerSync> {
+        self.peer_sync.as_ref()
+    }
+
+    pub fn get_pinned_registrations(&self) -> Vec<RegistrationRequest> {
+        self.pinned_registrations.clone()
+    }
+
+    /// Hold the guard from reading the server until it is added back.
+    pub async fn lock_port(
+        &self,
+        port: Port,
+    ) -> Result<OwnedMutexGuard<()>, StoreError> {
+        let lock = self
+            .port_locks
+            .write(|locks| {
+                locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+                locks.entry(port).or_default().clone()
+            })
+            .await?;
+
+        Ok(lock.lock_owned().await)
+    }
+
+    /// Locks every port in ascending order, so concurrent callers can't
+    /// deadlock on each other.
+    pub async fn lock_ports(
+        &self,
+        ports: impl IntoIterator<Item = Port>,
+    ) -> Result<Vec<OwnedMutexGuard<()>>, StoreError> {
+        let mut ports = ports.into_iter().collect::<Vec<_>>();
+        ports.sort();
+        ports.dedup();
+
+        let mut guards = vec![];
+        for port in ports {
+            guards.push(self.lock_port(port).await?);
+        }
+
+        Ok(guards)
+    }
+
+    /// Also locks the ports a change is being applied to before their server
+    /// is added.
+    pub async fn lock_all_ports(
+        &self,
+        ports: impl IntoIterator<Item = Port>,
+    ) -> Result<Vec<OwnedMutexGuard<()>>, StoreError> {
+        let locked = self
+            .port_locks
+            .read(|locks| locks.keys().copied().collect::<Vec<_>>())
+            .await?;
+        let served = self
+            .servers
+            .read(|servers| servers.keys().copied().collect::<Vec<_>>())
+            .await?;
+
+        self.lock_ports(
+            locked
+                .into_iter()
+                .chain(served)
+                .chain([Port::EPHEMERAL])
+                .chain(ports),
+        )
+        .await
+    }
+
+    /// The server is kept even if its routes can't be stored.
+    pub async fn add_server(&self, server: Server) -> Result<(), StoreError> {
+        let port = server.get_port();
         info!(%port, "Adding server at port {port}.");
 
-        safe_write(&self.servers, |mut guard| {
-            guard.insert(port.to_string(), server);
-        });
+        let stored_port = StoredPort::from(server.get_registrations());
+        self.put_back_server(server).await?;
+
+        self.storage.save_port(stored_port).await
     }
 
-    pub fn remove_server(&self, port: &str) -> Option<Server> {
+    /// Unlike [`Self::add_server`], leaves the storage as it is.
+    pub async fn put_back_server(
+        &self,
+        server: Server,
+    ) -> Result<(), StoreError> {
+        let port = server.get_port();
+
+        let mut server = Some(server);
+        let result = self
+            .servers
+            .write(|servers| {
+                if let Some(server) = server.take() {
+                    servers.insert(port, server);
+                }
+            })
+            .await;
+
+        if let Some(server) = server {
+            server.shutdown().await;
+        }
+
+        result
+    }
+
+    pub async fn take_server(
+        &self,
+        port: Port,
+    ) -> Result<Option<Server>, StoreError> {
+        self.servers.write(|servers| servers.remove(&port)).await
+    }
+
+    pub async fn remove_server(
+        &self,
+        port: Port,
+    ) -> Result<Option<Server>, StoreError> {
         info!(%port, "Removing server at port {port}.");
 
-        let server = safe_write(&self.servers, |mut guard| {
-            guard.remove(port).map(|server| {
-                server.stop();
-                server
+        self.storage.remove_port(port).await?;
+
+        self.servers
+            .write(|servers| {
+                servers.remove(&port).inspect(|server| {
+                    server.stop();
+                })
             })
-        });
+            .await
+    }
 
-        server.and_then(|server| server)
+    /// Unlike [`Self::shutdown_servers`], also forgets the stored routes.
+    pub async fn reset_servers(
+        &self,
+    ) -> Result<Vec<ServerRegistration>, StoreError> {
+        self.storage.clear().await?;
+
+        self.shutdown_servers().await
     }
 
-    pub fn get_registrations(&self) -> Vec<ServerRegistration> {
+    pub async fn shutdown_servers(
+        &self,
+    ) -> Result<Vec<ServerRegistration>, StoreError> {
+        info!("Shutting down all servers.");
+
+        let servers = self
+            .servers
+            .write(|servers| {
+                servers
+                    .drain()
+                    .map(|(_, server)| server)
+                    .collect::<Vec<_>>()
+            })
+            .await?;
+
+        let registrations =
+            servers.iter().map(Server::get_registrations).collect();
+
+        let mut shutdowns = JoinSet::new();
+        for server in servers {
+            shutdowns.spawn(server.shutdown());
+        }
+        shutdowns.join_all().await;
+
+        Ok(registrations)
+    }
+
+    pub fn has_persistent_storage(&self) -> bool {
+        self.storage.is_persistent()
+    }
+
+    pub async fn get_stored_ports(
+        &self,
+    ) -> Result<Vec<StoredPort>, StoreError> {
+        self.storage.load().await
+    }
+
+    pub async fn get_grpc_registry(
+        &self,
+        port: Port,
+    ) -> Result<GrpcRegistry, StoreError> {
+        self.servers
+            .read(|servers| {
+                servers
+                    .get(&port)
+                    .map(|server| server.get_grpc_registry())
+                    .unwrap_or_default()
+            })
+            .await
+    }
+
+    pub async fn get_socket_handler(
+        &self,
+        port: Port,
+    ) -> Result<Option<SocketHandler>, StoreError> {
+        self.servers
+            .read(|servers| {
+                servers
+                    .get(&port)
+                    .and_then(|server| server.get_socket_handler())
+            })
+            .await
+    }
+
+    pub async fn get_oidc_provider(
+        &self,
+        port: Port,
+    ) -> Result<Option<OidcProvider>, StoreError> {
+        self.servers
+            .read(|servers| {
+                servers
+                    .get(&port)
+                    .and_then(|server| server.get_oidc_provider())
+            })
+            .await
+    }
+
+    pub async fn get_cors_policy(
+        &self,
+        port: Port,
+    ) -> Result<Option<CorsPolicy>, StoreError> {
+        self.servers
+            .read(|servers| {
+                servers
+                    .get(&port)
+                    .and_then(|server| server.get_cors_policy())
+            })
+            .await
+    }
+
+    pub async fn get_rate_limiter(
+        &self,
+        port: Port,
+    ) -> Result<Option<RateLimiter>, StoreError> {
+        self.servers
+            .read(|servers| {
+                servers
+                    .get(&port)
+                    .and_then(|server| server.get_rate_limiter())
+            })
+            .await
+    }
+
+    /// Rate limiters keep their counters in stores of their own, which are
+    /// only read once the servers are released.
+    pub async fn get_rate_limit_counters(
+        &self,
+        port: Port,
+    ) -> Result<Option<Vec<RateLimitCounter>>, StoreError> {
+        match self.get_snapshot(port).await? {
+            Some(snapshot) => {
+                Ok(Some(snapshot.get_rate_limit_counters().await?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub async fn reset_rate_limit_counters(
+        &self,
+        port: Port,
+    ) -> Result<Option<Vec<RateLimitCounter>>, StoreError> {
+        match self.get_snapshot(port).await? {
+            Some(snapshot) => {
+                Ok(Some(snapshot.reset_rate_limit_counters().await?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn get_snapshot(
+        &self,
+        port: Port,
+    ) -> Result<Option<ServerSnapshot>, StoreError> {
+        self.servers
+            .read(|servers| servers.get(&port).map(Server::snapshot))
+            .await
+    }
+
+    pub async fn get_registrations(
+        &self,
+    ) -> Result<Vec<ServerRegistration>, StoreError> {
         info!("Collecting information about all registrations.");
 
-        let registrations = safe_read(&self.servers, |guard| {
-            guard
-                .iter()
-                .map(|(_, server)| server.get_registrations())
-                .collect::<Vec<_>>()
-        });
+        let mut registrations = self
+            .servers
+            .read(|servers| {
+                servers
+                    .values()
+                    .map(|server| server.get_registrations())
+                    .collect::<Vec<_>>()
+            })
+            .await?;
+
+        registrations.sort_by_key(|registration| registration.port);
+        Ok(registrations)
+    }
+
+    pub async fn get_registration(
+        &self,
+        port: Port,
+        path: RoutePath,
+        method: HttpMethod,
+        graphql: Option<GraphQlOperation>,
+    ) -> Result<Option<Registration>, StoreError> {
+        self.servers
+            .read(|servers| {
+                servers.get(&port).and_then(|server| {
+                    server.get_registration(path, method, graphql)
+                })
+            })
+            .await
+    }
 
-        registrations.unwrap_or(vec![])
+    pub async fn find_route_conflict(
+        &self,
+        port: Port,
+        path: &RoutePath,
+    ) -> Result<Option<RoutePath>, StoreError> {
+        let path_matching = self.connection_establisher.path_matching();
+
+        self.servers
+            .read(|servers| {
+                servers.get(&port).and_then(|server| {
+                    server.find_route_conflict(path, path_matching)
+                })
+            })
+            .await
+    }
+
+    pub async fn get_server_registration(
+        &self,
+        port: Port,
+    ) -> Result<Option<ServerRegistration>, StoreError> {
+        self.servers
+            .read(|servers| {
+                servers.get(&port).map(|server| server.get_registrations())
+            })
+            .await
     }
 }
@@ -0,0 +1,381 @@
+use tracing::warn;
+
+use crate::{
+    business::{
+        app_state::AppState,
+        server::{
+            connection_establisher::ConnectionEstablisher,
+            path_matching::find_conflict,
+            rate_limit::RateLimiter,
+            restartable::Restartable,
+            server::{RouteBatchUpdate, Server},
+        },
+        server_update::{add_restarted_server, update_server},
+    },
+    model::{
+        error::Error,
+        internal::stored_port::StoredPort,
+        port::Port,
+        request::registration_request::RegistrationRequest,
+        response::{
+            batch_registration_failure::BatchRegistrationFailure,
+            registration_response::RegistrationResponse,
+        },
+        route_path::RoutePath,
+    },
+    security::stub_auth::StubAuthenticator,
+};
+
+/// Applies all registrations, restarting every affected port once. If one
+/// port fails, the ports touched so far are rolled back.
+pub async fn register_batch<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+    registrations: Vec<RegistrationRequest>,
+) -> Result<Vec<RegistrationResponse>, Error> {
+    let _port_locks = app_state
+        .lock_ports(registrations.iter().map(|registration| registration.port))
+        .await?;
+
+    register_locked_batch(app_state, registrations).await
+}
+
+/// [`register_batch`] for callers already holding the lock of every port in
+/// `registrations`.
+pub async fn register_locked_batch<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+    registrations: Vec<RegistrationRequest>,
+) -> Result<Vec<RegistrationResponse>, Error> {
+    let total = registrations.len();
+    let registrations = registrations
+        .into_iter()
+        .map(|registration_request| normalize(app_state, registration_request))
+        .collect::<Vec<_>>();
+
+    let mut failures = vec![];
+    for (index, registration_request) in registrations.iter().enumerate() {
+        // Earlier entries for the same port are served alongside it.
+        let pending = registrations[..index]
+            .iter()
+            .filter(|other| other.port == registration_request.port)
+            .map(|other| &other.path);
+
+        let result = match validate(registration_request) {
+            Ok(()) => {
+                check_conflict(app_state, registration_request, pending).await
+            }
+            Err(err) => Err(err),
+        };
+
+        match result {
+            // The state itself is unreachable, which no entry can fix.
+            Err(err @ Error::Store(_)) => return Err(err),
+            Err(err) => failures.push(BatchRegistrationFailure::new(
+                index,
+                registration_request,
+                &err,
+            )),
+            Ok(()) => {}
+        }
+    }
+
+    if !failures.is_empty() {
+        return Err(batch_failure(failures, total, None));
+    }
+
+    let mut applied: Vec<(Port, Option<Server>)> = vec![];
+    let mut responses = vec![];
+
+    for (port, entries) in group_by_port(registrations) {
+        let previous = if port.is_ephemeral() {
+            None
+        } else {
+            match app_state.take_server(port).await {
+                Ok(previous) => previous,
+                Err(err) => {
+                    let rollback_failure = rollback(app_state, applied).await;
+                    return Err(with_rollback_failure(
+                        err.into(),
+                        rollback_failure,
+                    ));
+                }
+            }
+        };
+
+        let removed = entries
+            .iter()
+            .map(|(_, registration_request)| {
+                previous.as_ref().and_then(|previous| {
+                    previous.get_registration(
+                        registration_request.path.clone(),
+                        registration_request.method.clone(),
+                        registration_request.graphql.clone(),
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let update = RouteBatchUpdate::new(
+            port,
+            entries
+                .iter()
+                .map(|(_, registration_request)| registration_request.clone())
+                .collect(),
+        );
+
+        let server = previous.as_ref().restart(app_state, update).await;
+
+        let server = match server {
+            Ok(server) => add_restarted_server(app_state, server).await,
+            Err(err) => Err(err),
+        };
+
+        match server {
+            Ok(port) => {
+                applied.push((port, previous));
+
+                responses.extend(entries.into_iter().zip(removed).map(
+                    |((index, registration_request), removed)| {
+                        (
+                            index,
+                            RegistrationResponse::new(
+                                port,
+                                registration_request,
+                                removed,
+                            ),
+                        )
+                    },
+                ));
+            }
+            Err(err) => {
+                // The previous server's routes are still stored.
+                let put_back_failure = match previous {
+                    Some(previous) => app_state
+                        .put_back_server(previous)
+                        .await
+                        .err()
+                        .map(|err| {
+                            format!("port {port} could not be restored, {err}")
+                        }),
+                    None => None,
+                };
+                let rollback_failure = rollback(app_state, applied).await;
+                let rollback_failure = put_back_failure
+                    .into_iter()
+                    .chain(rollback_failure)
+                    .reduce(|failures, failure| {
+                        format!("{failures}; {failure}")
+                    });
+
+                if let Error::Store(_) = err {
+                    return Err(with_rollback_failure(err, rollback_failure));
+                }
+
+                let failures = entries
+                    .iter()
+                    .map(|(index, registration_request)| {
+                        BatchRegistrationFailure::new(
+                            *index,
+                            registration_request,
+                            &err,
+                        )
+                    })
+                    .collect();
+                return Err(batch_failure(failures, total, rollback_failure));
+            }
+        }
+    }
+
+    for (_, previous) in applied {
+        if let Some(previous) = previous {
+            previous.shutdown().await;
+        }
+    }
+
+    responses.sort_by_key(|(index, _)| *index);
+
+    Ok(responses
+        .into_iter()
+        .map(|(_, response)| response)
+        .collect())
+}
+
+/// Registers the ports the storage kept from an earlier run, each on its
+/// own so one that can't be bound doesn't hold back the others.
+pub async fn restore_stored_registrations<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+) -> Result<Vec<RegistrationResponse>, Error> {
+    let stored_ports = app_state.get_stored_ports().await?;
+    let mut restored = vec![];
+
+    for stored_port in stored_ports {
+        let port = stored_port.port;
+
+        match restore_port(app_state, stored_port).await {
+            Ok(responses) => restored.extend(responses),
+            Err(err) => {
+                let message = err.failure_message();
+                warn!(%port, "Failed to restore the routes on port {port}, {message}.");
+            }
+        }
+    }
+
+    Ok(restored)
+}
+
+async fn restore_port<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+    mut stored_port: StoredPort,
+) -> Result<Vec<RegistrationResponse>, Error> {
+    let port = stored_port.port;
+    let _port_lock = app_state.lock_port(port).await?;
+
+    stored_port.registrations = stored_port
+        .registrations
+        .into_iter()
+        .map(|registration_request| normalize(app_state, registration_request))
+        .collect();
+    for registration_request in &stored_port.registrations {
+        validate(registration_request)?;
+    }
+
+    let registrations = stored_port.registrations.clone();
+    let port = update_server(app_state, port, stored_port).await?;
+
+    Ok(registrations
+        .into_iter()
+        .map(|registration_request| {
+            RegistrationResponse::new(port, registration_request, None)
+        })
+        .collect())
+}
+
+pub fn validate(
+    registration_request: &RegistrationRequest,
+) -> Result<(), Error> {
+    if let Some(auth) = &registration_request.auth {
+        StubAuthenticator::validate(auth)?;
+    }
+
+    if let Some(rate_limit) = &registration_request.rate_limit {
+        RateLimiter::validate(rate_limit)?;
+    }
+
+    Ok(())
+}
+
+/// Puts the route in the form the stub servers match requests against.
+pub fn normalize<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+    mut registration_request: RegistrationRequest,
+) -> RegistrationRequest {
+    let path_matching = app_state.get_connection_establisher().path_matching();
+    registration_request.path = path_matching.route(registration_request.path);
+
+    registration_request
+}
+
+/// Fails if the route can't be served next to the routes already on its
+/// port and the `pending` ones about to be added.
+pub async fn check_conflict<'a, T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+    registration_request: &RegistrationRequest,
+    pending: impl IntoIterator<Item = &'a RoutePath>,
+) -> Result<(), Error> {
+    let path = &registration_request.path;
+
+    let conflict = match find_conflict(pending, path) {
+        Some(route) => Some(route.clone()),
+        None => {
+            app_state
+                .find_route_conflict(registration_request.port, path)
+                .await?
+        }
+    };
+
+    match conflict {
+        Some(route) => Err(Error::Conflict(format!(
+            "`{path}` conflicts with the registered route `{route}`"
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn group_by_port(
+    registrations: Vec<RegistrationRequest>,
+) -> Vec<(Port, Vec<(usize, RegistrationRequest)>)> {
+    let mut groups: Vec<(Port, Vec<(usize, RegistrationRequest)>)> = vec![];
+
+    for (index, registration_request) in registrations.into_iter().enumerate() {
+        // Entries without a port share one freshly allocated port.
+        let port = registration_request.port;
+
+        match groups
+            .iter_mut()
+            .find(|(group_port, _)| *group_port == port)
+        {
+            Some((_, entries)) => entries.push((index, registration_request)),
+            None => groups.push((port, vec![(index, registration_request)])),
+        }
+    }
+
+    groups
+}
+
+/// Undoes the `applied` ports, newest first, and describes the ones that
+/// couldn't be restored.
+async fn rollback<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+    applied: Vec<(Port, Option<Server>)>,
+) -> Option<String> {
+    let mut failures = vec![];
+
+    for (port, previous) in applied.into_iter().rev() {
+        // A port that can't be rolled back doesn't stop the remaining ones.
+        match app_state.take_server(port).await {
+            Ok(Some(server)) => server.shutdown().await,
+            Ok(None) => {}
+            Err(err) => failures
+                .push(format!("port {port} could not be released, {err}")),
+        }
+
+        let restored = match previous {
+            Some(previous) => app_state.add_server(previous).await,
+            None => app_state.remove_server(port).await.map(|_| ()),
+        };
+        if let Err(err) = restored {
+            failures.push(format!("port {port} could not be restored, {err}"));
+        }
+    }
+
+    (!failures.is_empty()).then(|| failures.join("; "))
+}
+
+fn with_rollback_failure(
+    err: Error,
+    rollback_failure: Option<String>,
+) -> Error {
+    match rollback_failure {
+        Some(rollback_failure) => err.with_context(&format!(
+            "rolling back failed too, {rollback_failure}"
+        )),
+        None => err,
+    }
+}
+
+fn batch_failure(
+    failures: Vec<BatchRegistrationFailure>,
+    total: usize,
+    rollback_failure: Option<String>,
+) -> Error {
+    let failed = failures.len();
+    let message = match rollback_failure {
+        Some(rollback_failure) => format!(
+            "{failed} of {total} registrations failed, rolling back failed too, {rollback_failure}"
+        ),
+        None => format!(
+            "{failed} of {total} registrations failed, no changes were applied"
+        ),
+    };
+
+    Error::Batch(message, failures)
+}
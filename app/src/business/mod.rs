@@ -1,2 +1,8 @@
 pub mod app_state;
+pub mod batch_registration;
+pub mod peer_sync;
 pub mod server;
+pub mod server_query;
+pub mod server_update;
+pub mod storage;
+pub mod store;
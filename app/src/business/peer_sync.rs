@@ -0,0 +1,346 @@
+use std::{collections::HashMap, process, time::Duration};
+
+use ring::rand::{SecureRandom, SystemRandom};
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+use crate::{
+    business::{
+        app_state::AppState,
+        batch_registration::{normalize, validate},
+        server::{
+            connection_establisher::ConnectionEstablisher,
+            server::RouteReplacement,
+        },
+        server_update::update_server,
+        store::{Store, store_error::StoreError},
+    },
+    client::{admin_client::AdminClient, client_error::ClientError},
+    model::{
+        error::Error,
+        internal::server_registration::ServerRegistration,
+        port::Port,
+        port_version::PortVersion,
+        request::peer_update_request::PeerUpdateRequest,
+        response::{
+            field_error::FieldError, peer_state_response::PeerStateResponse,
+        },
+    },
+};
+
+/// How often a change is sent to a peer that can't be reached or fails.
+const SYNC_ATTEMPTS: u32 = 5;
+/// Doubled after every failed attempt.
+const FIRST_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// Replicates the HTTP routes of every changed port to the other api-gen
+/// instances. A peer only applies a change newer than the version it has
+/// seen for the port, so the order changes arrive in doesn't matter.
+#[derive(Clone)]
+pub struct PeerSync {
+    instance_id: String,
+    peers: Vec<AdminClient>,
+    versions: Store<HashMap<Port, PortVersion>>,
+}
+
+impl PeerSync {
+    pub fn new(instance_id: &str, peers: Vec<AdminClient>) -> Self {
+        Self {
+            instance_id: instance_id.to_string(),
+            peers,
+            versions: Store::default(),
+        }
+    }
+
+    /// An identifier unlikely to be shared with any peer.
+    pub fn random_instance_id() -> String {
+        let mut bytes = [0; 8];
+
+        match SystemRandom::new().fill(&mut bytes) {
+            Ok(()) => bytes.iter().map(|byte| format!("{byte:02x}")).collect(),
+            Err(_) => format!("api-gen-{}", process::id()),
+        }
+    }
+
+    pub fn get_instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    pub async fn get_version(
+        &self,
+        port: Port,
+    ) -> Result<Option<PortVersion>, StoreError> {
+        self.versions
+            .read(|versions| versions.get(&port).cloned())
+            .await
+    }
+
+    async fn next_version(
+        &self,
+        port: Port,
+    ) -> Result<PortVersion, StoreError> {
+        self.versions
+            .write(|versions| {
+                let version =
+                    PortVersion::next(versions.get(&port), &self.instance_id);
+                versions.insert(port, version.clone());
+                version
+            })
+            .await
+    }
+
+    /// Keeps `version` unless a newer one was seen in the meantime.
+    async fn accept(
+        &self,
+        port: Port,
+        version: PortVersion,
+    ) -> Result<(), StoreError> {
+        self.versions
+            .write(|versions| {
+                if versions.get(&port).is_none_or(|current| *current < version)
+                {
+                    versions.insert(port, version);
+                }
+            })
+            .await
+    }
+
+    /// Sends `update` to every peer in the background. A peer that stays
+    /// down catches up when it starts again, see [`pull_peer_state`].
+    fn broadcast(&self, port: Port, update: PeerUpdateRequest) {
+        for peer in &self.peers {
+            let peer = peer.clone();
+            let update = update.clone();
+
+            tokio::spawn(async move {
+                let url = peer.base_url();
+                let version = &update.version;
+                let mut delay = FIRST_RETRY_DELAY;
+
+                for attempt in 1..=SYNC_ATTEMPTS {
+                    match peer.sync(port, &update).await {
+                        Ok(_) => {
+                            info!(%port, %version, %url, "Sent version {version} of port {port} to {url}.");
+                        }
+                        Err(ClientError::Api { status: 409, .. }) => {
+                            info!(%port, %version, %url, "{url} already has a newer version of port {port} than {version}.");
+                        }
+                        Err(err)
+                            if attempt < SYNC_ATTEMPTS
+                                && is_retryable(&err) =>
+                        {
+                            let seconds = delay.as_secs_f64();
+                            warn!(%port, %version, %url, "Failed to send version {version} of port {port} to {url}, retrying in {seconds}s, {err}.");
+                            sleep(delay).await;
+                            delay *= 2;
+                            continue;
+                        }
+                        Err(err) => {
+                            warn!(%port, %version, %url, "Failed to send version {version} of port {port} to {url}, {err}.");
+                        }
+                    }
+
+                    break;
+                }
+            });
+        }
+    }
+}
+
+/// A peer that is unreachable or fails on its side may accept the change
+/// later, one that rejects it won't.
+fn is_retryable(err: &ClientError) -> bool {
+    match err {
+        ClientError::Transport(_) => true,
+        ClientError::Api { status, .. } => *status >= 500,
+    }
+}
+
+/// The latest change to every port this instance made or applied, for a
+/// peer that starts after the changes were broadcast.
+pub async fn peer_state<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+) -> Result<Vec<PeerStateResponse>, Error> {
+    let peer_sync = enabled_peer_sync(app_state)?;
+
+    let mut ports = peer_sync
+        .versions
+        .read(|versions| versions.keys().copied().collect::<Vec<_>>())
+        .await?;
+    ports.sort();
+
+    let mut state = vec![];
+    for port in ports {
+        // The routes must belong to the version they are sent with.
+        let _port_lock = app_state.lock_port(port).await?;
+
+        let Some(version) = peer_sync.get_version(port).await? else {
+            continue;
+        };
+        let registrations = app_state
+            .get_server_registration(port)
+            .await?
+            .map(ServerRegistration::into_registration_requests);
+
+        state.push(PeerStateResponse {
+            port,
+            update: PeerUpdateRequest {
+                version,
+                registrations,
+            },
+        });
+    }
+
+    Ok(state)
+}
+
+/// Applies the changes the peers made before this instance started and
+/// returns how many ports were updated.
+pub async fn pull_peer_state<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+) -> usize {
+    let Some(peer_sync) = app_state.get_peer_sync() else {
+        return 0;
+    };
+
+    let mut applied = 0;
+    for peer in &peer_sync.peers {
+        let url = peer.base_url();
+
+        let state = match peer.peer_state().await {
+            Ok(state) => state,
+            Err(err) => {
+                warn!(%url, "Failed to pull the routes of {url}, {err}.");
+                continue;
+            }
+        };
+
+        for PeerStateResponse { port, update } in state {
+            match apply_peer_update(app_state, port, update).await {
+                Ok(_) => applied += 1,
+                // Another peer already sent the same or a newer change.
+                Err(Error::Conflict(_)) => {}
+                Err(err) => {
+                    let message = err.failure_message();
+                    warn!(%port, %url, "Failed to apply the routes of port {port} from {url}, {message}.");
+                }
+            }
+        }
+    }
+
+    applied
+}
+
+fn enabled_peer_sync<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+) -> Result<&PeerSync, Error> {
+    app_state.get_peer_sync().ok_or(Error::NotFound(
+        "Peer sync is not enabled on this instance".to_string(),
+    ))
+}
+
+/// Sends the current HTTP routes of `ports` to every peer. Callers hold
+/// the locks of `ports`, so the routes sent match their version.
+pub async fn publish_ports<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+    ports: impl IntoIterator<Item = Port>,
+) {
+    let Some(peer_sync) = app_state.get_peer_sync() else {
+        return;
+    };
+
+    let mut ports = ports.into_iter().collect::<Vec<_>>();
+    ports.sort();
+    ports.dedup();
+
+    for port in ports {
+        if let Err(err) = publish_port(app_state, peer_sync, port).await {
+            warn!(%port, "Failed to publish the routes on port {port}, {err}.");
+        }
+    }
+}
+
+async fn publish_port<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+    peer_sync: &PeerSync,
+    port: Port,
+) -> Result<(), StoreError> {
+    let registrations = app_state
+        .get_server_registration(port)
+        .await?
+        .map(ServerRegistration::into_registration_requests);
+    let version = peer_sync.next_version(port).await?;
+
+    peer_sync.broadcast(
+        port,
+        PeerUpdateRequest {
+            version,
+            registrations,
+        },
+    );
+
+    Ok(())
+}
+
+/// Applies a change a peer made to `port`, unless a newer one is known. It
+/// isn't published again, every peer hears of it from its origin.
+pub async fn apply_peer_update<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+    port: Port,
+    PeerUpdateRequest {
+        version,
+        registrations,
+    }: PeerUpdateRequest,
+) -> Result<PortVersion, Error> {
+    let peer_sync = enabled_peer_sync(app_state)?;
+
+    if port.is_ephemeral() {
+        return Err(Error::validation(vec![FieldError::new(
+            "port",
+            "Only a fixed port can be synced between peers",
+        )]));
+    }
+
+    let _port_lock = app_state.lock_port(port).await?;
+
+    if let Some(current) = peer_sync.get_version(port).await?
+        && current >= version
+    {
+        return Err(Error::Conflict(format!(
+            "Version {version} of port {port} is not newer than {current}"
+        )));
+    }
+
+    match registrations {
+        Some(registrations) => {
+            let registrations = registrations
+                .into_iter()
+                .map(|mut registration_request| {
+                    registration_request.port = port;
+                    normalize(app_state, registration_request)
+                })
+                .collect::<Vec<_>>();
+
+            for registration_request in &registrations {
+                validate(registration_request)?;
+            }
+
+            update_server(
+                app_state,
+                port,
+                RouteReplacement::new(port, registrations),
+            )
+            .await?;
+        }
+        None => {
+            if let Some(server) = app_state.remove_server(port).await? {
+                server.shutdown().await;
+            }
+        }
+    }
+
+    peer_sync.accept(port, version.clone()).await?;
+    info!(%port, %version, "Applied version {version} of port {port} from a peer.");
+
+    Ok(version)
+}
@@ -1,54 +1,380 @@
-use std::sync::Arc;
+use std::{
+    future::pending,
+    io::{self, ErrorKind},
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    ops::RangeInclusive,
+    sync::Arc,
+    time::Duration,
+};
 
-use axum::{Router, serve};
-use tokio::{net::TcpListener, task::JoinHandle};
-use tracing::info;
+use axum::{
+    Router,
+    serve::{Listener, serve},
+};
+use tokio::{
+    net::{TcpListener, TcpStream, UdpSocket},
+    select,
+    task::{JoinHandle, JoinSet},
+    time::sleep,
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 
-use crate::{model::error::Error, util::notifier::Notifier};
+use crate::{
+    business::server::{path_matching::PathMatching, socket::SocketHandler},
+    logging::{http_trace::HttpTracingMiddleware, metrics::Metrics},
+    model::{
+        error::Error, port::Port,
+        request::socket_registration_request::SocketProtocol,
+    },
+};
+
+pub enum ConnectionHandler {
+    /// Stub routes, HTTP tracing and request metrics are layered on once the
+    /// port is bound.
+    Http(Router, Arc<Metrics>),
+    Socket(SocketHandler),
+}
+
+impl ConnectionHandler {
+    fn protocol(&self) -> SocketProtocol {
+        match self {
+            Self::Http(..) => SocketProtocol::Tcp,
+            Self::Socket(handler) => handler.protocol(),
+        }
+    }
+}
+
+/// A bound port, shared by the servers that take turns on it so that a
+/// replacement accepts connections before its predecessor stops.
+#[derive(Clone)]
+pub enum BoundSocket {
+    Tcp(Arc<std::net::TcpListener>),
+    Udp(Arc<std::net::UdpSocket>),
+}
+
+impl BoundSocket {
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        match self {
+            Self::Tcp(listener) => listener.local_addr(),
+            Self::Udp(socket) => socket.local_addr(),
+        }
+    }
+}
+
+pub struct Connection {
+    pub handle: JoinHandle<()>,
+    pub port: Port,
+    /// The socket bound for this connection, `None` when the establisher
+    /// doesn't bind real ports.
+    pub socket: Option<BoundSocket>,
+}
+
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long connections accepted right before a shutdown have to send their
+/// request, hyper closes them as idle afterwards.
+const ACCEPTED_GRACE: Duration = Duration::from_millis(100);
 
 pub trait ConnectionEstablisher: Send + Sync {
+    /// Starts serving `handler` on `port` until `shutdown` is cancelled.
+    ///
+    /// An ephemeral `port` is resolved to a free one, the returned
+    /// [`Connection`] carries the port actually bound. `inherited` is the
+    /// socket of the server being replaced on `port`, it's taken over
+    /// instead of binding the port a second time.
     fn connect(
         &self,
-        port: String,
-        router: Router,
-    ) -> impl Future<Output = Result<JoinHandle<()>, Error>> + Send + Sync;
+        port: Port,
+        handler: ConnectionHandler,
+        inherited: Option<BoundSocket>,
+        shutdown: CancellationToken,
+    ) -> impl Future<Output = Result<Connection, Error>> + Send + Sync;
+
+    /// How the HTTP stubs served by this establisher match request paths.
+    fn path_matching(&self) -> PathMatching {
+        PathMatching::default()
+    }
+}
+
+pub struct TcpConnectionEstablisher {
+    drain_timeout: Duration,
+    bind_address: IpAddr,
+    port_range: Option<RangeInclusive<u16>>,
+    path_matching: PathMatching,
+}
+
+impl TcpConnectionEstablisher {
+    pub fn new(drain_timeout: Duration) -> Self {
+        Self {
+            drain_timeout,
+            bind_address: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            port_range: None,
+            path_matching: PathMatching::default(),
+        }
+    }
+
+    pub fn with_bind_address(mut self, bind_address: IpAddr) -> Self {
+        self.bind_address = bind_address;
+        self
+    }
+
+    pub fn with_port_range(
+        mut self,
+        port_range: Option<RangeInclusive<u16>>,
+    ) -> Self {
+        self.port_range = port_range;
+        self
+    }
+
+    pub fn with_path_matching(mut self, path_matching: PathMatching) -> Self {
+        self.path_matching = path_matching;
+        self
+    }
 }
 
-#[derive(Default)]
-pub struct TcpConnectionEstablisher;
+impl Default for TcpConnectionEstablisher {
+    fn default() -> Self {
+        Self::new(DEFAULT_DRAIN_TIMEOUT)
+    }
+}
 
 impl ConnectionEstablisher for TcpConnectionEstablisher {
     async fn connect(
         &self,
-        port: String,
-        router: Router,
-    ) -> Result<JoinHandle<()>, Error> {
-        info!(port = port, "Establishing connection on port {port}.");
-
-        let notifier = Arc::new(Notifier::new());
-
-        let notifier_clone = notifier.clone();
-        let join_handle = tokio::spawn(async move {
-            match TcpListener::bind(format!("0.0.0.0:{}", port)).await {
-                Ok(listener) => {
-                    let _ = notifier_clone.notify(Ok(()));
-                    serve(listener, router).await.unwrap();
-                }
-                Err(err) => {
-                    let _ = notifier_clone.notify(Err(err));
-                }
-            };
-        });
-
-        match notifier.await_notification().await {
-            Ok(Ok(_)) => Ok(join_handle),
-            Ok(Err(err)) => Err(Error::Connection(format!(
+        port: Port,
+        handler: ConnectionHandler,
+        inherited: Option<BoundSocket>,
+        shutdown: CancellationToken,
+    ) -> Result<Connection, Error> {
+        info!(%port, "Establishing connection on port {port}.");
+
+        let connection = async {
+            let socket = self.socket(port, &handler, inherited).await?;
+            let address = socket.local_addr()?;
+            let handle =
+                Self::serve(handler, &socket, shutdown, self.drain_timeout)?;
+
+            Ok::<_, io::Error>(Connection {
+                handle,
+                port: Port::new(address.port()),
+                socket: Some(socket),
+            })
+        };
+
+        connection.await.map_err(|err| {
+            Error::Connection(format!(
                 "Failed to establish connection, {}",
                 err
-            ))),
-            Err(_notification_error) => {
-                Err(Error::Connection("Something went wrong!".to_string()))
+            ))
+        })
+    }
+
+    fn path_matching(&self) -> PathMatching {
+        self.path_matching
+    }
+}
+
+impl TcpConnectionEstablisher {
+    fn addresses(&self, port: Port) -> Vec<String> {
+        let address =
+            |port: u16| SocketAddr::new(self.bind_address, port).to_string();
+
+        match &self.port_range {
+            Some(port_range) if port.is_ephemeral() => {
+                port_range.clone().map(address).collect()
             }
+            _ => vec![address(port.number())],
         }
     }
+
+    /// Takes over `inherited` when it speaks the protocol `handler` needs,
+    /// otherwise binds `port` anew.
+    async fn socket(
+        &self,
+        port: Port,
+        handler: &ConnectionHandler,
+        inherited: Option<BoundSocket>,
+    ) -> io::Result<BoundSocket> {
+        match (handler.protocol(), inherited) {
+            (SocketProtocol::Tcp, Some(socket @ BoundSocket::Tcp(_)))
+            | (SocketProtocol::Udp, Some(socket @ BoundSocket::Udp(_))) => {
+                Ok(socket)
+            }
+            (SocketProtocol::Tcp, _) => {
+                let listener =
+                    Self::bind(self.addresses(port), TcpListener::bind).await?;
+                Ok(BoundSocket::Tcp(Arc::new(listener.into_std()?)))
+            }
+            (SocketProtocol::Udp, _) => {
+                let socket =
+                    Self::bind(self.addresses(port), UdpSocket::bind).await?;
+                Ok(BoundSocket::Udp(Arc::new(socket.into_std()?)))
+            }
+        }
+    }
+
+    /// Serves `handler` on a handle of its own to `socket`, so it stops
+    /// accepting on `shutdown` without releasing the port.
+    fn serve(
+        handler: ConnectionHandler,
+        socket: &BoundSocket,
+        shutdown: CancellationToken,
+        drain_timeout: Duration,
+    ) -> io::Result<JoinHandle<()>> {
+        match (handler, socket) {
+            (
+                ConnectionHandler::Http(router, metrics),
+                BoundSocket::Tcp(listener),
+            ) => {
+                let listener = TcpListener::from_std(listener.try_clone()?)?;
+                let port = listener.local_addr()?.port().to_string();
+
+                Ok(tokio::spawn(serve_with_drain(
+                    listener,
+                    router.with_http_tracing(port, metrics),
+                    shutdown,
+                    drain_timeout,
+                )))
+            }
+            (
+                ConnectionHandler::Socket(handler),
+                BoundSocket::Tcp(listener),
+            ) => {
+                let listener = TcpListener::from_std(listener.try_clone()?)?;
+
+                Ok(tokio::spawn(Self::accept(listener, handler, shutdown)))
+            }
+            (ConnectionHandler::Socket(handler), BoundSocket::Udp(socket)) => {
+                let socket = UdpSocket::from_std(socket.try_clone()?)?;
+
+                Ok(tokio::spawn(async move {
+                    select! {
+                        _ = handler.serve_udp(socket) => {}
+                        _ = shutdown.cancelled() => {}
+                    }
+                }))
+            }
+            (ConnectionHandler::Http(..), BoundSocket::Udp(_)) => {
+                Err(io::Error::new(
+                    ErrorKind::InvalidInput,
+                    "HTTP needs a TCP port",
+                ))
+            }
+        }
+    }
+
+    /// Binds the first of `addresses` that is free.
+    async fn bind<T, F, B>(addresses: Vec<String>, bind: B) -> io::Result<T>
+    where
+        B: Fn(String) -> F,
+        F: Future<Output = io::Result<T>>,
+    {
+        let mut last_error = None;
+
+        for address in addresses {
+            match bind(address).await {
+                Ok(bound) => return Ok(bound),
+                Err(err) => last_error = Some(err),
+            }
+        }
+
+        Err(last_error.unwrap_or(io::Error::new(
+            ErrorKind::AddrNotAvailable,
+            "no port left to allocate",
+        )))
+    }
+
+    /// Serves every accepted connection until `shutdown`, then aborts the
+    /// connections that are still open.
+    async fn accept(
+        listener: TcpListener,
+        handler: SocketHandler,
+        shutdown: CancellationToken,
+    ) {
+        let mut connections = JoinSet::new();
+
+        loop {
+            select! {
+                _ = shutdown.cancelled() => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, peer)) => {
+                        connections.spawn(
+                            handler.clone().serve_tcp(stream, peer.to_string()),
+                        );
+                    }
+                    Err(err) => {
+                        warn!(%err, "Failed to accept socket connection, {err}.");
+                    }
+                },
+                Some(_) = connections.join_next() => {}
+            }
+        }
+
+        connections.shutdown().await;
+    }
+}
+
+/// Serves `router` until `shutdown` is cancelled, then lets in-flight
+/// requests finish for at most `drain_timeout`.
+pub async fn serve_with_drain(
+    listener: TcpListener,
+    router: Router,
+    shutdown: CancellationToken,
+    drain_timeout: Duration,
+) {
+    let address = listener
+        .local_addr()
+        .map(|address| address.to_string())
+        .unwrap_or_default();
+    let listener = DrainingListener {
+        listener,
+        shutdown: shutdown.clone(),
+    };
+    let graceful_shutdown = shutdown.clone().cancelled_owned();
+    let server = serve(listener, router).with_graceful_shutdown(async {
+        graceful_shutdown.await;
+        sleep(ACCEPTED_GRACE).await;
+    });
+
+    let drain_deadline = async {
+        shutdown.cancelled().await;
+        sleep(drain_timeout).await;
+    };
+
+    select! {
+        served = server => {
+            if let Err(err) = served {
+                warn!(%err, "Server on {address} failed, {err}.");
+            }
+        }
+        _ = drain_deadline => {
+            let drain_timeout = drain_timeout.as_secs_f64();
+            warn!(%drain_timeout, "Dropping in-flight requests on {address} after {drain_timeout}s.");
+        }
+    }
+}
+
+/// Stops accepting as soon as `shutdown` is cancelled, leaving the pending
+/// connections of a shared socket to the server that took it over.
+struct DrainingListener {
+    listener: TcpListener,
+    shutdown: CancellationToken,
+}
+
+impl Listener for DrainingListener {
+    type Io = TcpStream;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        select! {
+            biased;
+            _ = self.shutdown.cancelled() => pending().await,
+            accepted = Listener::accept(&mut self.listener) => accepted,
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        self.listener.local_addr()
+    }
 }
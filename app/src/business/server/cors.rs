@@ -0,0 +1,154 @@
+use std::{str::FromStr, time::Duration};
+
+use axum::Router;
+use http::{HeaderName, HeaderValue, Method};
+use tower_http::cors::{
+    AllowHeaders, AllowMethods, AllowOrigin, Any, CorsLayer, ExposeHeaders,
+};
+
+use crate::model::{
+    error::Error, internal::server_registration::CorsRegistration, port::Port,
+    request::cors_registration_request::CorsRegistrationRequest,
+};
+
+const WILDCARD: &str = "*";
+
+#[derive(Clone)]
+pub struct CorsPolicy {
+    registration: CorsRegistration,
+    layer: CorsLayer,
+}
+
+pub struct CorsPolicyUpdate {
+    pub port: Port,
+    pub policy: CorsPolicy,
+}
+
+impl CorsPolicyUpdate {
+    pub fn new(port: Port, policy: CorsPolicy) -> Self {
+        Self { port, policy }
+    }
+}
+
+impl CorsPolicy {
+    pub fn new(
+        registration_request: CorsRegistrationRequest,
+    ) -> Result<Self, Error> {
+        let registration = CorsRegistration::from(registration_request);
+        let credentials = registration.allow_credentials;
+
+        let allow_origin = if registration.allowed_origins.is_empty() {
+            AllowOrigin::mirror_request()
+        } else if is_wildcard(&registration.allowed_origins) {
+            reject_wildcard_with_credentials(credentials, "allowedOrigins")?;
+            AllowOrigin::from(Any)
+        } else {
+            AllowOrigin::list(parse_all(
+                &registration.allowed_origins,
+                "origin",
+                HeaderValue::from_str,
+            )?)
+        };
+
+        let allow_methods = if registration.allowed_methods.is_empty() {
+            AllowMethods::mirror_request()
+        } else {
+            AllowMethods::list(
+                registration.allowed_methods.iter().map(Method::from),
+            )
+        };
+
+        let allow_headers = if registration.allowed_headers.is_empty() {
+            AllowHeaders::mirror_request()
+        } else if is_wildcard(&registration.allowed_headers) {
+            reject_wildcard_with_credentials(credentials, "allowedHeaders")?;
+            AllowHeaders::from(Any)
+        } else {
+            AllowHeaders::list(parse_all(
+                &registration.allowed_headers,
+                "header",
+                HeaderName::from_str,
+            )?)
+        };
+
+        let expose_headers = if is_wildcard(&registration.exposed_headers) {
+            reject_wildcard_with_credentials(credentials, "exposedHeaders")?;
+            ExposeHeaders::from(Any)
+        } else {
+            ExposeHeaders::list(parse_all(
+                &registration.exposed_headers,
+                "header",
+                HeaderName::from_str,
+            )?)
+        };
+
+        let mut layer = CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(allow_methods)
+            .allow_headers(allow_headers)
+            .expose_headers(expose_headers)
+            .allow_credentials(credentials);
+
+        if let Some(max_age) = registration.max_age {
+            layer = layer.max_age(Duration::from_secs(max_age));
+        }
+
+        Ok(Self {
+            registration,
+            layer,
+        })
+    }
+
+    pub fn get_registration(&self) -> CorsRegistration {
+        self.registration.clone()
+    }
+}
+
+pub trait CorsMiddleware {
+    fn with_cors(self, cors: Option<&CorsPolicy>) -> Router<()>;
+}
+
+impl CorsMiddleware for Router<()> {
+    fn with_cors(self, cors: Option<&CorsPolicy>) -> Router<()> {
+        match cors {
+            Some(cors) => self.layer(cors.layer.clone()),
+            None => self,
+        }
+    }
+}
+
+fn is_wildcard(values: &[String]) -> bool {
+    values.iter().any(|value| value == WILDCARD)
+}
+
+fn reject_wildcard_with_credentials(
+    credentials: bool,
+    field: &str,
+) -> Result<(), Error> {
+    if credentials {
+        return Err(Error::Cors(format!(
+            "`{WILDCARD}` in `{field}` cannot be combined with `allowCredentials`"
+        )));
+    }
+
+    Ok(())
+}
+
+fn parse_all<T, E, F>(
+    values: &[String],
+    kind: &str,
+    parse: F,
+) -> Result<Vec<T>, Error>
+where
+    E: std::fmt::Display,
+    F: Fn(&str) -> Result<T, E>,
+{
+    values
+        .iter()
+        .map(|value| {
+            parse(value).map_err(|err| {
+                Error::Cors(format!("Invalid {kind} `{value}`, {err}"))
+            })
+        })
+        .collect()
+}
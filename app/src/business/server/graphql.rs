@@ -0,0 +1,96 @@
+use std::cmp::Reverse;
+
+use axum::{
+    Json,
+    response::{IntoResponse, Response},
+};
+use http::HeaderMap;
+use http::StatusCode;
+use serde_json::{Value, json};
+use tracing::{info, warn};
+
+use crate::{
+    business::server::RouteStub, model::graphql_operation::GraphQlOperation,
+};
+
+pub(super) struct GraphQlResolver {
+    operations: Vec<(GraphQlOperation, RouteStub)>,
+    fallback: Option<RouteStub>,
+}
+
+impl GraphQlResolver {
+    pub(super) fn new(
+        mut operations: Vec<(GraphQlOperation, RouteStub)>,
+        fallback: Option<RouteStub>,
+    ) -> Self {
+        operations.sort_by_cached_key(|(operation, _)| {
+            (
+                Reverse(operation.specificity()),
+                serde_json::to_string(operation).unwrap_or_default(),
+            )
+        });
+
+        Self {
+            operations,
+            fallback,
+        }
+    }
+
+    pub(super) async fn resolve(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Response {
+        let incoming = match serde_json::from_slice::<GraphQlOperation>(body) {
+            Ok(incoming) => incoming,
+            Err(err) => {
+                warn!(%err, "Received a malformed GraphQL request, {err}.");
+
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(GraphQlResolver::error(
+                        &format!("Malformed GraphQL request, {err}"),
+                        "BAD_REQUEST",
+                    )),
+                )
+                    .into_response();
+            }
+        };
+
+        let operation = incoming.describe();
+
+        let stub = self
+            .operations
+            .iter()
+            .find(|(registered, _)| registered.matches(&incoming))
+            .map(|(_, stub)| stub)
+            .or(self.fallback.as_ref());
+
+        match stub {
+            Some(stub) => {
+                info!(%operation, "Resolved GraphQL operation {operation}.");
+                stub.respond(headers).await
+            }
+            None => {
+                warn!(%operation, "No stub registered for GraphQL operation {operation}.");
+                Json(GraphQlResolver::error(
+                    &format!("No stub registered for operation {operation}."),
+                    "OPERATION_NOT_STUBBED",
+                ))
+                .into_response()
+            }
+        }
+    }
+
+    fn error(message: &str, code: &str) -> Value {
+        json!({
+            "data": null,
+            "errors": [
+                {
+                    "message": message,
+                    "extensions": { "code": code },
+                }
+            ],
+        })
+    }
+}
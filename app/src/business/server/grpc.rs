@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+use axum::{Router, body::Body, response::Response, routing::post};
+use base64::{Engine, prelude::BASE64_STANDARD};
+use http::{HeaderMap, HeaderName, HeaderValue, header::CONTENT_TYPE};
+use http_body_util::{BodyExt, Full};
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, MethodDescriptor,
+    prost::{
+        Message,
+        bytes::{BufMut, Bytes, BytesMut},
+    },
+};
+use tracing::info;
+
+use crate::model::{
+    error::Error,
+    internal::server_registration::GrpcRegistration,
+    port::Port,
+    request::grpc_registration_request::GrpcRegistrationRequest,
+    response::grpc_descriptor_response::{GrpcMethod, GrpcService},
+    route_path::RoutePath,
+};
+
+const GRPC_CONTENT_TYPE: &str = "application/grpc";
+const GRPC_STATUS_HEADER: &str = "grpc-status";
+const GRPC_MESSAGE_HEADER: &str = "grpc-message";
+const GRPC_STATUS_OK: u32 = 0;
+const GRPC_STATUS_UNIMPLEMENTED: u32 = 12;
+const GRPC_MAX_STATUS: u32 = 16;
+const GRPC_RESERVED_PREFIX: &str = "grpc-";
+
+#[derive(Clone, Default)]
+pub struct GrpcRegistry {
+    pool: DescriptorPool,
+    stubs: HashMap<String, GrpcStub>,
+}
+
+pub struct GrpcRegistryUpdate {
+    pub port: Port,
+    pub registry: GrpcRegistry,
+}
+
+impl GrpcRegistryUpdate {
+    pub fn new(port: Port, registry: GrpcRegistry) -> Self {
+        Self { port, registry }
+    }
+}
+
+#[derive(Clone)]
+struct GrpcStub {
+    registration: GrpcRegistration,
+    message: Bytes,
+    trailers: HeaderMap,
+}
+
+impl GrpcRegistry {
+    pub fn add_descriptor_set(
+        &mut self,
+        encoded_descriptor_set: &str,
+    ) -> Result<Vec<GrpcService>, Error> {
+        let descriptor_set = BASE64_STANDARD
+            .decode(encoded_descriptor_set)
+            .map_err(|err| {
+                Error::Grpc(format!(
+                    "Descriptor set is not valid base64, {err}"
+                ))
+            })?;
+
+        self.pool
+            .decode_file_descriptor_set(descriptor_set.as_slice())
+            .map_err(|err| {
+                Error::Grpc(format!("Failed to load descriptor set, {err}"))
+            })?;
+
+        Ok(self.describe())
+    }
+
+    pub fn add_stub(
+        &mut self,
+        registration_request: GrpcRegistrationRequest,
+    ) -> Result<Option<GrpcRegistration>, Error> {
+        let registration = GrpcRegistration::from(registration_request);
+        let method = self.find_method(&registration)?;
+
+        if method.is_client_streaming() || method.is_server_streaming() {
+            return Err(Error::Grpc(format!(
+                "Method `{}` is streaming, only unary methods can be stubbed",
+                method.full_name()
+            )));
+        }
+
+        let message = if registration.status == GRPC_STATUS_OK {
+            GrpcRegistry::encode_message(&method, &registration)?
+        } else {
+            Bytes::new()
+        };
+        let trailers = GrpcRegistry::build_trailers(&registration)?;
+
+        let removed = self.stubs.insert(
+            GrpcRegistry::route(&method),
+            GrpcStub {
+                registration,
+                message,
+                trailers,
+            },
+        );
+
+        Ok(removed.map(|stub| stub.registration))
+    }
+
+    pub fn get_registrations(&self) -> Vec<GrpcRegistration> {
+        self.stubs
+            .values()
+            .map(|stub| stub.registration.clone())
+            .collect()
+    }
+
+    pub fn router(&self) -> Router {
+        let mut router = Router::new();
+
+        for service in self.pool.services() {
+            for method in service.methods() {
+                let route = GrpcRegistry::route(&method);
+                let stub = self.stubs.get(&route).cloned();
+                let method = method.full_name().to_string();
+
+                router = router.route(
+                    &route,
+                    post(async move || {
+                        info!(%method, "Responding to gRPC call {method}.");
+                        GrpcRegistry::respond_with(stub)
+                    }),
+                );
+            }
+        }
+
+        router
+    }
+
+    /// The paths [`Self::router`] serves, one per method in the pool.
+    pub fn routes(&self) -> Vec<RoutePath> {
+        let mut routes = vec![];
+
+        for service in self.pool.services() {
+            for method in service.methods() {
+                if let Ok(route) = GrpcRegistry::route(&method).parse() {
+                    routes.push(route);
+                }
+            }
+        }
+
+        routes
+    }
+
+    fn respond_with(stub: Option<GrpcStub>) -> Response {
+        match stub {
+            Some(stub) => GrpcRegistry::respond(stub.message, stub.trailers),
+            None => GrpcRegistry::respond(
+                Bytes::new(),
+                GrpcRegistry::status_trailers(
+                    GRPC_STATUS_UNIMPLEMENTED,
+                    Some("Method is not stubbed."),
+                ),
+            ),
+        }
+    }
+
+    fn describe(&self) -> Vec<GrpcService> {
+        self.pool
+            .services()
+            .map(|service| {
+                let methods = service
+                    .methods()
+                    .map(|method| {
+                        GrpcMethod::new(
+                            method.name().to_string(),
+                            method.input().full_name().to_string(),
+                            method.output().full_name().to_string(),
+                            !method.is_client_streaming()
+                                && !method.is_server_streaming(),
+                        )
+                    })
+                    .collect();
+
+                GrpcService::new(service.full_name().to_string(), methods)
+            })
+            .collect()
+    }
+
+    fn find_method(
+        &self,
+        registration: &GrpcRegistration,
+    ) -> Result<MethodDescriptor, Error> {
+        let service = self
+            .pool
+            .get_service_by_name(&registration.service)
+            .ok_or_else(|| {
+                Error::Grpc(format!(
+                    "Service `{}` is not present in the uploaded descriptors",
+                    registration.service
+                ))
+            })?;
+
+        service
+            .methods()
+            .find(|method| method.name() == registration.method)
+            .ok_or_else(|| {
+                Error::Grpc(format!(
+                    "Method `{}` is not present in service `{}`",
+                    registration.method, registration.service
+                ))
+            })
+    }
+
+    fn encode_message(
+        method: &MethodDescriptor,
+        registration: &GrpcRegistration,
+    ) -> Result<Bytes, Error> {
+        let message = DynamicMessage::deserialize(
+            method.output(),
+            &registration.response,
+        )
+        .map_err(|err| {
+            Error::Grpc(format!(
+                "Response cannot be transcoded into `{}`, {err}",
+                method.output().full_name()
+            ))
+        })?;
+
+        let encoded = message.encode_to_vec();
+
+        let mut frame = BytesMut::with_capacity(5 + encoded.len());
+        frame.put_u8(0);
+        frame.put_u32(encoded.len() as u32);
+        frame.put_slice(&encoded);
+
+        Ok(frame.freeze())
+    }
+
+    fn build_trailers(
+        registration: &GrpcRegistration,
+    ) -> Result<HeaderMap, Error> {
+        if registration.status > GRPC_MAX_STATUS {
+            return Err(Error::Grpc(format!(
+                "Status `{}` is not a valid gRPC status code",
+                registration.status
+            )));
+        }
+
+        let mut trailers = GrpcRegistry::status_trailers(
+            registration.status,
+            registration.message.as_deref(),
+        );
+
+        for (name, value) in &registration.trailers {
+            let name =
+                HeaderName::from_bytes(name.as_bytes()).map_err(|err| {
+                    Error::Grpc(format!("Invalid trailer name `{name}`, {err}"))
+                })?;
+            if name.as_str().starts_with(GRPC_RESERVED_PREFIX) {
+                return Err(Error::Grpc(format!(
+                    "Trailer `{name}` is reserved and cannot be configured"
+                )));
+            }
+            let value = HeaderValue::from_str(value).map_err(|err| {
+                Error::Grpc(format!("Invalid trailer value `{value}`, {err}"))
+            })?;
+
+            trailers.insert(name, value);
+        }
+
+        Ok(trailers)
+    }
+
+    fn status_trailers(status: u32, message: Option<&str>) -> HeaderMap {
+        let mut trailers = HeaderMap::new();
+
+        trailers.insert(GRPC_STATUS_HEADER, HeaderValue::from(status));
+
+        if let Some(message) = message
+            && let Ok(message) = HeaderValue::from_str(&percent_encode(message))
+        {
+            trailers.insert(GRPC_MESSAGE_HEADER, message);
+        }
+
+        trailers
+    }
+
+    fn respond(message: Bytes, trailers: HeaderMap) -> Response {
+        let body =
+            Full::new(message).with_trailers(async move { Some(Ok(trailers)) });
+
+        let mut response = Response::new(Body::new(body));
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static(GRPC_CONTENT_TYPE));
+
+        response
+    }
+
+    fn route(method: &MethodDescriptor) -> String {
+        format!("/{}/{}", method.parent_service().full_name(), method.name())
+    }
+}
+
+fn percent_encode(message: &str) -> String {
+    message
+        .bytes()
+        .map(|byte| match byte {
+            b' '..=b'~' if byte != b'%' => (byte as char).to_string(),
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
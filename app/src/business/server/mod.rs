@@ -1,17 +1,93 @@
-use crate::model::http_method::HttpMethod;
+use axum::{
+    Json,
+    response::{IntoResponse, Response},
+};
+use http::HeaderMap;
+use serde_json::Value;
+
+use crate::{
+    business::server::rate_limit::RateLimiter,
+    model::{
+        graphql_operation::GraphQlOperation, http_method::HttpMethod,
+        route_path::RoutePath,
+    },
+    security::stub_auth::StubAuthenticator,
+};
 
 pub mod connection_establisher;
+pub mod cors;
+pub mod graphql;
+pub mod grpc;
+pub mod oidc;
+pub mod path_matching;
+pub mod rate_limit;
 pub mod restartable;
+#[allow(clippy::module_inception)]
 pub mod server;
+pub mod socket;
 
-#[derive(PartialEq, Eq, Hash)]
+#[derive(PartialEq, Eq, Hash, Clone)]
 struct RegistrationIdentifier {
-    pub path: String,
+    pub path: RoutePath,
     pub method: HttpMethod,
+    pub graphql: Option<GraphQlOperation>,
 }
 
 impl RegistrationIdentifier {
-    fn new(path: String, method: HttpMethod) -> Self {
-        Self { path, method }
+    fn new(
+        path: RoutePath,
+        method: HttpMethod,
+        graphql: Option<GraphQlOperation>,
+    ) -> Self {
+        Self {
+            path,
+            method,
+            graphql,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct RouteStub {
+    pub response: Value,
+    pub auth: Option<StubAuthenticator>,
+    pub rate_limiter: Option<RateLimiter>,
+}
+
+impl RouteStub {
+    fn new(
+        response: Value,
+        auth: Option<StubAuthenticator>,
+        rate_limiter: Option<RateLimiter>,
+    ) -> Self {
+        Self {
+            response,
+            auth,
+            rate_limiter,
+        }
+    }
+
+    async fn respond(&self, headers: &HeaderMap) -> Response {
+        let quota = match &self.rate_limiter {
+            Some(rate_limiter) => match rate_limiter.acquire(headers).await {
+                Ok(quota) => Some(quota),
+                Err(rejection) => return rejection.into_response(),
+            },
+            None => None,
+        };
+
+        let mut response = match &self.auth {
+            Some(auth) => match auth.verify(headers) {
+                Ok(()) => Json(self.response.clone()).into_response(),
+                Err(failure) => failure.into_response(),
+            },
+            None => Json(self.response.clone()).into_response(),
+        };
+
+        if let Some(quota) = quota {
+            quota.apply(response.headers_mut());
+        }
+
+        response
     }
 }
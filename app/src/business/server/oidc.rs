@@ -0,0 +1,554 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    Form, Json, Router,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use base64::{Engine, prelude::BASE64_URL_SAFE_NO_PAD};
+use http::{
+    HeaderMap, HeaderValue, StatusCode,
+    header::{AUTHORIZATION, CACHE_CONTROL, WWW_AUTHENTICATE},
+};
+use jsonwebtoken::{
+    Algorithm, EncodingKey, Header, encode, get_current_timestamp,
+};
+use ring::{
+    digest::{SHA256, digest},
+    rand::{SecureRandom, SystemRandom},
+    signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair, KeyPair},
+};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use tracing::{info, warn};
+
+use crate::{
+    business::store::{Store, store_error::StoreError},
+    model::{
+        error::Error, internal::server_registration::OidcRegistration,
+        port::Port,
+        request::oidc_registration_request::OidcRegistrationRequest,
+    },
+    security::credentials::{basic_credentials, constant_time_eq},
+};
+
+const DISCOVERY_PATH: &str = "/.well-known/openid-configuration";
+const JWKS_PATH: &str = "/.well-known/jwks.json";
+const TOKEN_PATH: &str = "/token";
+const AUTHORIZATION_PATH: &str = "/authorize";
+
+const DEFAULT_CLIENT_ID: &str = "api-gen";
+const OPENID_SCOPE: &str = "openid";
+
+const CLIENT_CREDENTIALS_GRANT: &str = "client_credentials";
+const PASSWORD_GRANT: &str = "password";
+const REFRESH_TOKEN_GRANT: &str = "refresh_token";
+
+const RESERVED_CLAIMS: &[&str] =
+    &["iss", "sub", "aud", "iat", "exp", "client_id", "scope"];
+
+#[derive(Clone)]
+pub struct OidcProvider {
+    registration: OidcRegistration,
+    signing_key: Arc<SigningKey>,
+    refresh_tokens: Store<HashMap<String, RefreshGrant>>,
+}
+
+pub struct OidcProviderUpdate {
+    pub port: Port,
+    pub provider: OidcProvider,
+}
+
+impl OidcProviderUpdate {
+    pub fn new(port: Port, provider: OidcProvider) -> Self {
+        Self { port, provider }
+    }
+}
+
+struct SigningKey {
+    key_id: String,
+    encoding_key: EncodingKey,
+    jwk: Value,
+}
+
+#[derive(Clone)]
+struct RefreshGrant {
+    client_id: String,
+    subject: String,
+    scope: Option<String>,
+    claims: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct TokenRequest {
+    grant_type: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    refresh_token: Option<String>,
+    scope: Option<String>,
+}
+
+struct TokenError {
+    status: StatusCode,
+    error: &'static str,
+    description: String,
+}
+
+impl OidcProvider {
+    pub fn new(
+        registration_request: OidcRegistrationRequest,
+    ) -> Result<Self, Error> {
+        if registration_request.token_ttl == 0 {
+            return Err(Error::Oidc(
+                "`tokenTtl` must be greater than zero".to_string(),
+            ));
+        }
+
+        reject_reserved_claims(&registration_request.claims)?;
+        for user in &registration_request.users {
+            reject_reserved_claims(&user.claims)?;
+        }
+
+        let signing_key = SigningKey::generate()?;
+
+        let issuer = registration_request
+            .issuer
+            .unwrap_or_else(|| {
+                format!("http://localhost:{}", registration_request.port)
+            })
+            .trim_end_matches('/')
+            .to_string();
+
+        let registration = OidcRegistration {
+            issuer,
+            key_id: signing_key.key_id.clone(),
+            clients: registration_request.clients,
+            users: registration_request.users,
+            claims: registration_request.claims,
+            token_ttl: registration_request.token_ttl,
+        };
+
+        Ok(Self {
+            registration,
+            signing_key: Arc::new(signing_key),
+            refresh_tokens: Store::default(),
+        })
+    }
+
+    pub fn get_registration(&self) -> OidcRegistration {
+        self.registration.clone()
+    }
+
+    pub fn router(&self) -> Router {
+        let discovery = Json(self.discovery());
+        let jwks = Json(json!({ "keys": [self.signing_key.jwk] }));
+        let provider = self.clone();
+
+        Router::new()
+            .route(DISCOVERY_PATH, get(async || discovery))
+            .route(JWKS_PATH, get(async || jwks))
+            .route(
+                AUTHORIZATION_PATH,
+                get(async || {
+                    TokenError::new(
+                        StatusCode::BAD_REQUEST,
+                        "unsupported_response_type",
+                        "Only the token endpoint grants are supported",
+                    )
+                }),
+            )
+            .route(
+                TOKEN_PATH,
+                post(
+                    async move |headers: HeaderMap,
+                                Form(token_request): Form<TokenRequest>| {
+                        provider.token(&headers, token_request).await
+                    },
+                ),
+            )
+    }
+
+    fn discovery(&self) -> Value {
+        let issuer = &self.registration.issuer;
+
+        json!({
+            "issuer": issuer,
+            "jwks_uri": format!("{issuer}{JWKS_PATH}"),
+            "authorization_endpoint": format!("{issuer}{AUTHORIZATION_PATH}"),
+            "token_endpoint": format!("{issuer}{TOKEN_PATH}"),
+            "grant_types_supported": [
+                CLIENT_CREDENTIALS_GRANT,
+                PASSWORD_GRANT,
+                REFRESH_TOKEN_GRANT,
+            ],
+            "response_types_supported": ["token"],
+            "subject_types_supported": ["public"],
+            "id_token_signing_alg_values_supported": ["ES256"],
+            "token_endpoint_auth_methods_supported": [
+                "client_secret_basic",
+                "client_secret_post",
+            ],
+        })
+    }
+
+    async fn token(
+        &self,
+        headers: &HeaderMap,
+        token_request: TokenRequest,
+    ) -> Response {
+        let result = match self.authenticate_client(headers, &token_request) {
+            Ok(client_id) => self.grant(client_id, token_request).await,
+            Err(token_error) => Err(token_error),
+        };
+
+        match result {
+            Ok(token) => {
+                let mut response = Json(token).into_response();
+                response.headers_mut().insert(
+                    CACHE_CONTROL,
+                    HeaderValue::from_static("no-store"),
+                );
+                response
+            }
+            Err(token_error) => token_error.into_response(),
+        }
+    }
+
+    fn authenticate_client(
+        &self,
+        headers: &HeaderMap,
+        token_request: &TokenRequest,
+    ) -> Result<String, TokenError> {
+        let credentials = basic_credentials(headers.get(AUTHORIZATION))
+            .map(|(client_id, client_secret)| (client_id, Some(client_secret)))
+            .or_else(|| {
+                token_request.client_id.clone().map(|client_id| {
+                    (client_id, token_request.client_secret.clone())
+                })
+            });
+
+        if self.registration.clients.is_empty() {
+            return Ok(credentials
+                .map(|(client_id, _)| client_id)
+                .unwrap_or(DEFAULT_CLIENT_ID.to_string()));
+        }
+
+        let authenticated = credentials.filter(|(client_id, client_secret)| {
+            self.registration.clients.iter().any(|client| {
+                client.client_id == *client_id
+                    && client_secret.as_deref().is_some_and(|client_secret| {
+                        constant_time_eq(client_secret, &client.client_secret)
+                    })
+            })
+        });
+
+        match authenticated {
+            Some((client_id, _)) => Ok(client_id),
+            None => Err(TokenError::new(
+                StatusCode::UNAUTHORIZED,
+                "invalid_client",
+                "Client authentication failed",
+            )),
+        }
+    }
+
+    async fn grant(
+        &self,
+        client_id: String,
+        token_request: TokenRequest,
+    ) -> Result<Value, TokenError> {
+        let TokenRequest {
+            grant_type,
+            username,
+            password,
+            refresh_token,
+            scope,
+            ..
+        } = token_request;
+
+        match grant_type.as_deref() {
+            Some(CLIENT_CREDENTIALS_GRANT) => {
+                let grant = RefreshGrant {
+                    subject: client_id.clone(),
+                    client_id,
+                    scope,
+                    claims: Map::new(),
+                };
+
+                self.issue(grant, false).await
+            }
+            Some(PASSWORD_GRANT) => {
+                let (subject, claims) =
+                    self.authenticate_user(username, password)?;
+                let grant = RefreshGrant {
+                    client_id,
+                    subject,
+                    scope,
+                    claims,
+                };
+
+                self.issue(grant, true).await
+            }
+            Some(REFRESH_TOKEN_GRANT) => {
+                let refresh_token = refresh_token.ok_or_else(|| {
+                    TokenError::invalid_request("Missing `refresh_token`")
+                })?;
+
+                let grant = self
+                    .refresh_tokens
+                    .write(|refresh_tokens| {
+                        refresh_tokens.remove(&refresh_token)
+                    })
+                    .await?
+                    .filter(|grant| grant.client_id == client_id)
+                    .ok_or_else(|| {
+                        TokenError::invalid_grant(
+                            "Unknown or revoked refresh token",
+                        )
+                    })?;
+
+                self.issue(grant, true).await
+            }
+            Some(grant_type) => Err(TokenError::new(
+                StatusCode::BAD_REQUEST,
+                "unsupported_grant_type",
+                &format!("Grant type `{grant_type}` is not supported"),
+            )),
+            None => Err(TokenError::invalid_request("Missing `grant_type`")),
+        }
+    }
+
+    fn authenticate_user(
+        &self,
+        username: Option<String>,
+        password: Option<String>,
+    ) -> Result<(String, Map<String, Value>), TokenError> {
+        let (Some(username), Some(password)) = (username, password) else {
+            return Err(TokenError::invalid_request(
+                "Missing `username` or `password`",
+            ));
+        };
+
+        if self.registration.users.is_empty() {
+            return Ok((username, Map::new()));
+        }
+
+        self.registration
+            .users
+            .iter()
+            .find(|user| {
+                user.username == username
+                    && constant_time_eq(&password, &user.password)
+            })
+            .map(|user| (username, user.claims.clone()))
+            .ok_or_else(|| {
+                TokenError::invalid_grant("Invalid resource owner credentials")
+            })
+    }
+
+    async fn issue(
+        &self,
+        grant: RefreshGrant,
+        with_refresh_token: bool,
+    ) -> Result<Value, TokenError> {
+        let OidcRegistration {
+            issuer,
+            claims,
+            token_ttl,
+            ..
+        } = &self.registration;
+
+        let issued_at = get_current_timestamp();
+
+        // Custom claims go first so they can never shadow the reserved ones.
+        let mut id_claims = claims.clone();
+        id_claims.extend(grant.claims.clone());
+        id_claims.insert("iss".to_string(), json!(issuer));
+        id_claims.insert("sub".to_string(), json!(grant.subject));
+        id_claims.insert("aud".to_string(), json!(grant.client_id));
+        id_claims.insert("iat".to_string(), json!(issued_at));
+        id_claims.insert("exp".to_string(), json!(issued_at + token_ttl));
+
+        let mut access_claims = id_claims.clone();
+        access_claims.insert("client_id".to_string(), json!(grant.client_id));
+        if let Some(scope) = &grant.scope {
+            access_claims.insert("scope".to_string(), json!(scope));
+        }
+
+        let mut token = Map::new();
+        token.insert(
+            "access_token".to_string(),
+            json!(self.sign(&access_claims)?),
+        );
+        token.insert("token_type".to_string(), json!("Bearer"));
+        token.insert("expires_in".to_string(), json!(token_ttl));
+
+        if let Some(scope) = &grant.scope {
+            token.insert("scope".to_string(), json!(scope));
+
+            if scope.split_whitespace().any(|scope| scope == OPENID_SCOPE) {
+                token.insert(
+                    "id_token".to_string(),
+                    json!(self.sign(&id_claims)?),
+                );
+            }
+        }
+
+        if with_refresh_token {
+            let refresh_token = random_token()?;
+            token.insert("refresh_token".to_string(), json!(refresh_token));
+
+            self.refresh_tokens
+                .write(|refresh_tokens| {
+                    refresh_tokens.insert(refresh_token, grant.clone())
+                })
+                .await?;
+        }
+
+        let subject = &grant.subject;
+        info!(%subject, "Issued tokens for {subject}.");
+
+        Ok(Value::Object(token))
+    }
+
+    fn sign(&self, claims: &Map<String, Value>) -> Result<String, TokenError> {
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some(self.signing_key.key_id.clone());
+
+        encode(&header, claims, &self.signing_key.encoding_key).map_err(|err| {
+            TokenError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "server_error",
+                &format!("Failed to sign token, {err}"),
+            )
+        })
+    }
+}
+
+impl SigningKey {
+    fn generate() -> Result<Self, Error> {
+        let random = SystemRandom::new();
+        let key_generation_error =
+            || Error::Oidc("Failed to generate a signing key".to_string());
+
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(
+            &ECDSA_P256_SHA256_FIXED_SIGNING,
+            &random,
+        )
+        .map_err(|_| key_generation_error())?;
+        let key_pair = EcdsaKeyPair::from_pkcs8(
+            &ECDSA_P256_SHA256_FIXED_SIGNING,
+            pkcs8.as_ref(),
+            &random,
+        )
+        .map_err(|_| key_generation_error())?;
+
+        // Uncompressed SEC1 point, `0x04 || x || y`.
+        let public_key = key_pair.public_key().as_ref();
+        let (x, y) = public_key[1..].split_at(32);
+        let key_id = BASE64_URL_SAFE_NO_PAD
+            .encode(&digest(&SHA256, public_key).as_ref()[..12]);
+
+        let jwk = json!({
+            "kty": "EC",
+            "crv": "P-256",
+            "use": "sig",
+            "alg": "ES256",
+            "kid": key_id,
+            "x": BASE64_URL_SAFE_NO_PAD.encode(x),
+            "y": BASE64_URL_SAFE_NO_PAD.encode(y),
+        });
+
+        Ok(Self {
+            key_id,
+            encoding_key: EncodingKey::from_ec_der(pkcs8.as_ref()),
+            jwk,
+        })
+    }
+}
+
+impl TokenError {
+    fn new(status: StatusCode, error: &'static str, description: &str) -> Self {
+        Self {
+            status,
+            error,
+            description: description.to_string(),
+        }
+    }
+
+    fn invalid_request(description: &str) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "invalid_request", description)
+    }
+
+    fn invalid_grant(description: &str) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "invalid_grant", description)
+    }
+}
+
+impl IntoResponse for TokenError {
+    fn into_response(self) -> Response {
+        let TokenError {
+            status,
+            error,
+            description,
+        } = self;
+
+        warn!(%error, "Rejected token request, {description}.");
+
+        let mut response = (
+            status,
+            Json(json!({
+                "error": error,
+                "error_description": description,
+            })),
+        )
+            .into_response();
+
+        if status == StatusCode::UNAUTHORIZED {
+            response.headers_mut().insert(
+                WWW_AUTHENTICATE,
+                HeaderValue::from_static("Basic realm=\"api-gen\""),
+            );
+        }
+
+        response
+    }
+}
+
+impl From<StoreError> for TokenError {
+    fn from(store_error: StoreError) -> Self {
+        Self::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "temporarily_unavailable",
+            &store_error.to_string(),
+        )
+    }
+}
+
+fn reject_reserved_claims(claims: &Map<String, Value>) -> Result<(), Error> {
+    match claims
+        .keys()
+        .find(|claim| RESERVED_CLAIMS.contains(&claim.as_str()))
+    {
+        Some(claim) => Err(Error::Oidc(format!(
+            "Claim `{claim}` is reserved and cannot be configured"
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn random_token() -> Result<String, TokenError> {
+    let mut bytes = [0; 32];
+
+    SystemRandom::new().fill(&mut bytes).map_err(|_| {
+        TokenError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "server_error",
+            "Failed to generate a refresh token",
+        )
+    })?;
+
+    Ok(BASE64_URL_SAFE_NO_PAD.encode(bytes))
+}
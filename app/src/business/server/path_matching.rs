@@ -0,0 +1,130 @@
+use std::str::FromStr;
+
+use axum::{
+    Router,
+    extract::{MatchedPath, Request},
+    http::Uri,
+    middleware::{self, Next},
+    response::Response,
+};
+use tower::ServiceExt;
+
+use crate::model::route_path::{RoutePath, normalize_path};
+
+/// How request paths are matched against registered routes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathMatching {
+    #[default]
+    CaseSensitive,
+    CaseInsensitive,
+}
+
+impl FromStr for PathMatching {
+    type Err = String;
+
+    fn from_str(path_matching: &str) -> Result<Self, Self::Err> {
+        match path_matching.to_ascii_lowercase().as_str() {
+            "case-sensitive" => Ok(PathMatching::CaseSensitive),
+            "case-insensitive" => Ok(PathMatching::CaseInsensitive),
+            _ => Err(format!(
+                "Unknown path matching `{path_matching}`, expected one of `case-sensitive` or `case-insensitive`"
+            )),
+        }
+    }
+}
+
+impl PathMatching {
+    /// The form a route is registered under.
+    pub fn route(&self, path: RoutePath) -> RoutePath {
+        match self {
+            PathMatching::CaseSensitive => path,
+            PathMatching::CaseInsensitive => path.to_lowercase(),
+        }
+    }
+
+    /// The form a request path is routed under, matching [`Self::route`].
+    fn request_path(&self, path: &str) -> String {
+        let path = normalize_path(path).unwrap_or(path.to_string());
+
+        match self {
+            PathMatching::CaseSensitive => path,
+            PathMatching::CaseInsensitive => path.to_lowercase(),
+        }
+    }
+
+    fn rewrite(&self, mut request: Request) -> Request {
+        let uri = request.uri();
+        let path = self.request_path(uri.path());
+
+        if path == uri.path() {
+            return request;
+        }
+
+        let path_and_query = match uri.query() {
+            Some(query) => format!("{path}?{query}"),
+            None => path,
+        };
+        let mut parts = uri.clone().into_parts();
+        parts.path_and_query = path_and_query.parse().ok();
+
+        if let Ok(uri) = Uri::from_parts(parts) {
+            *request.uri_mut() = uri;
+        }
+
+        request
+    }
+}
+
+pub trait PathMatchingMiddleware {
+    /// Normalizes request paths before they are routed.
+    fn with_path_matching(self, path_matching: PathMatching) -> Router;
+}
+
+impl PathMatchingMiddleware for Router {
+    fn with_path_matching(self, path_matching: PathMatching) -> Router {
+        // Layers only run after routing, so the routes are wrapped as a
+        // service that sees every request first.
+        let routes = self.layer(middleware::from_fn(expose_matched_path));
+
+        Router::new().fallback_service(
+            routes.map_request(move |request| path_matching.rewrite(request)),
+        )
+    }
+}
+
+/// Hands the matched route to the layers outside the rewrite, which only
+/// see the response.
+async fn expose_matched_path(request: Request, next: Next) -> Response {
+    let matched_path = request.extensions().get::<MatchedPath>().cloned();
+    let mut response = next.run(request).await;
+
+    if let Some(matched_path) = matched_path {
+        response.extensions_mut().insert(matched_path);
+    }
+
+    response
+}
+
+/// Finds a registered route that `path` can't be served alongside, like
+/// `/users/{id}` for `/users/{name}`. The same route is not a conflict.
+pub fn find_conflict<'a>(
+    routes: impl IntoIterator<Item = &'a RoutePath>,
+    path: &RoutePath,
+) -> Option<&'a RoutePath> {
+    let routes = routes
+        .into_iter()
+        .filter(|route| *route != path)
+        .collect::<Vec<_>>();
+
+    let mut router = matchit::Router::new();
+    for route in &routes {
+        let _ = router.insert(&***route, ());
+    }
+
+    match router.insert(&**path, ()) {
+        Err(matchit::InsertError::Conflict { with }) => {
+            routes.into_iter().find(|route| ***route == with)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,400 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    Router,
+    extract::Request,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use http::{
+    HeaderMap, HeaderName, HeaderValue, StatusCode, header::RETRY_AFTER,
+};
+use tracing::warn;
+
+use crate::{
+    business::store::{Store, store_error::StoreError},
+    model::{
+        error::Error,
+        port::Port,
+        rate_limit::{RateLimit, RateLimitStrategy},
+        response::{
+            http_response::HttpResponse, rate_limit_counter::RateLimitCounter,
+        },
+    },
+};
+
+const LIMIT_HEADER: &str = "x-ratelimit-limit";
+const REMAINING_HEADER: &str = "x-ratelimit-remaining";
+const RESET_HEADER: &str = "x-ratelimit-reset";
+
+/// Distinct `keyHeader` values tracked at once. Keys seen beyond this share
+/// the counter of requests without the header.
+const MAX_KEYS: usize = 10_000;
+
+#[derive(Clone)]
+pub struct RateLimiter {
+    rate_limit: RateLimit,
+    counters: Store<HashMap<Option<String>, Counter>>,
+}
+
+pub struct RateLimiterUpdate {
+    pub port: Port,
+    pub rate_limiter: RateLimiter,
+}
+
+impl RateLimiterUpdate {
+    pub fn new(port: Port, rate_limiter: RateLimiter) -> Self {
+        Self { port, rate_limiter }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Counter {
+    Window { started: Instant, count: u32 },
+    Bucket { tokens: f64, refilled: Instant },
+}
+
+pub struct Quota {
+    limit: u32,
+    remaining: u32,
+    reset_after: u64,
+}
+
+pub struct QuotaExceeded {
+    quota: Quota,
+    retry_after: u64,
+}
+
+pub enum Rejection {
+    QuotaExceeded(QuotaExceeded),
+    Store(StoreError),
+}
+
+impl RateLimiter {
+    pub fn new(rate_limit: RateLimit) -> Result<Self, Error> {
+        RateLimiter::validate(&rate_limit)?;
+
+        Ok(Self {
+            rate_limit,
+            counters: Store::default(),
+        })
+    }
+
+    pub fn validate(rate_limit: &RateLimit) -> Result<(), Error> {
+        match rate_limit.strategy {
+            RateLimitStrategy::TokenBucket {
+                capacity,
+                refill_per_second,
+            } => {
+                if capacity == 0 {
+                    return Err(invalid(
+                        "`capacity` must be greater than zero",
+                    ));
+                }
+                if !refill_per_second.is_finite() || refill_per_second <= 0.0 {
+                    return Err(invalid(
+                        "`refillPerSecond` must be a positive number",
+                    ));
+                }
+            }
+            RateLimitStrategy::FixedWindow {
+                limit,
+                window_seconds,
+            } => {
+                if limit == 0 {
+                    return Err(invalid("`limit` must be greater than zero"));
+                }
+                if window_seconds == 0 {
+                    return Err(invalid(
+                        "`windowSeconds` must be greater than zero",
+                    ));
+                }
+            }
+        }
+
+        if let Some(key_header) = &rate_limit.key_header {
+            HeaderName::try_from(key_header).map_err(|err| {
+                invalid(&format!("Invalid key header `{key_header}`, {err}"))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_rate_limit(&self) -> RateLimit {
+        self.rate_limit.clone()
+    }
+
+    pub async fn acquire(
+        &self,
+        headers: &HeaderMap,
+    ) -> Result<Quota, Rejection> {
+        let key = self.rate_limit.key_header.as_ref().and_then(|key_header| {
+            headers
+                .get(key_header.as_str())
+                .and_then(|key| key.to_str().ok())
+                .map(str::to_string)
+        });
+        let now = Instant::now();
+
+        self.counters
+            .write(|counters| {
+                let key = if counters.contains_key(&key) {
+                    key
+                } else {
+                    counters.retain(|_, counter| !self.is_idle(*counter, now));
+                    if counters.len() < MAX_KEYS { key } else { None }
+                };
+
+                let counter = counters
+                    .entry(key)
+                    .or_insert_with(|| self.fresh_counter(now));
+                *counter = self.refresh(*counter, now);
+
+                match counter {
+                    Counter::Window { count, .. } if *count < self.limit() => {
+                        *count += 1;
+                        Ok(self.quota(*counter, now))
+                    }
+                    Counter::Bucket { tokens, .. } if *tokens >= 1.0 => {
+                        *tokens -= 1.0;
+                        Ok(self.quota(*counter, now))
+                    }
+                    _ => Err(Rejection::QuotaExceeded(QuotaExceeded {
+                        quota: self.quota(*counter, now),
+                        retry_after: self.retry_after(*counter, now),
+                    })),
+                }
+            })
+            .await
+            .map_err(Rejection::Store)?
+    }
+
+    pub async fn get_counters(
+        &self,
+    ) -> Result<Vec<(Option<String>, Quota)>, StoreError> {
+        let now = Instant::now();
+
+        self.counters
+            .read(|counters| {
+                counters
+                    .iter()
+                    .map(|(key, counter)| {
+                        (
+                            key.clone(),
+                            self.quota(self.refresh(*counter, now), now),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .await
+    }
+
+    pub async fn reset(&self) -> Result<(), StoreError> {
+        self.counters.write(|counters| counters.clear()).await
+    }
+
+    fn limit(&self) -> u32 {
+        match self.rate_limit.strategy {
+            RateLimitStrategy::TokenBucket { capacity, .. } => capacity,
+            RateLimitStrategy::FixedWindow { limit, .. } => limit,
+        }
+    }
+
+    fn fresh_counter(&self, now: Instant) -> Counter {
+        match self.rate_limit.strategy {
+            RateLimitStrategy::TokenBucket { capacity, .. } => {
+                Counter::Bucket {
+                    tokens: capacity as f64,
+                    refilled: now,
+                }
+            }
+            RateLimitStrategy::FixedWindow { .. } => Counter::Window {
+                started: now,
+                count: 0,
+            },
+        }
+    }
+
+    /// An idle counter is indistinguishable from a fresh one and can be
+    /// dropped.
+    fn is_idle(&self, counter: Counter, now: Instant) -> bool {
+        match self.refresh(counter, now) {
+            Counter::Window { count, .. } => count == 0,
+            Counter::Bucket { tokens, .. } => tokens >= self.limit() as f64,
+        }
+    }
+
+    fn refresh(&self, counter: Counter, now: Instant) -> Counter {
+        match (counter, self.rate_limit.strategy) {
+            (
+                Counter::Window { started, .. },
+                RateLimitStrategy::FixedWindow { window_seconds, .. },
+            ) if now.duration_since(started)
+                >= Duration::from_secs(window_seconds) =>
+            {
+                self.fresh_counter(now)
+            }
+            (
+                Counter::Bucket { tokens, refilled },
+                RateLimitStrategy::TokenBucket {
+                    capacity,
+                    refill_per_second,
+                },
+            ) => Counter::Bucket {
+                tokens: (tokens
+                    + now.duration_since(refilled).as_secs_f64()
+                        * refill_per_second)
+                    .min(capacity as f64),
+                refilled: now,
+            },
+            (counter, _) => counter,
+        }
+    }
+
+    fn quota(&self, counter: Counter, now: Instant) -> Quota {
+        let (remaining, reset_after) = match (counter, self.rate_limit.strategy)
+        {
+            (
+                Counter::Window { started, count },
+                RateLimitStrategy::FixedWindow { window_seconds, .. },
+            ) => (
+                self.limit().saturating_sub(count),
+                Duration::from_secs(window_seconds)
+                    .saturating_sub(now.duration_since(started))
+                    .as_secs_f64(),
+            ),
+            (
+                Counter::Bucket { tokens, .. },
+                RateLimitStrategy::TokenBucket {
+                    capacity,
+                    refill_per_second,
+                },
+            ) => (
+                tokens.floor() as u32,
+                (capacity as f64 - tokens) / refill_per_second,
+            ),
+            _ => (self.limit(), 0.0),
+        };
+
+        Quota {
+            limit: self.limit(),
+            remaining,
+            reset_after: reset_after.ceil() as u64,
+        }
+    }
+
+    fn retry_after(&self, counter: Counter, now: Instant) -> u64 {
+        let retry_after = match (counter, self.rate_limit.strategy) {
+            (
+                Counter::Bucket { tokens, .. },
+                RateLimitStrategy::TokenBucket {
+                    refill_per_second, ..
+                },
+            ) => (1.0 - tokens) / refill_per_second,
+            _ => return self.quota(counter, now).reset_after.max(1),
+        };
+
+        (retry_after.ceil() as u64).max(1)
+    }
+}
+
+impl Quota {
+    pub fn apply(&self, headers: &mut HeaderMap) {
+        for (name, value) in [
+            (LIMIT_HEADER, self.limit as u64),
+            (REMAINING_HEADER, self.remaining as u64),
+            (RESET_HEADER, self.reset_after),
+        ] {
+            headers.insert(name, HeaderValue::from(value));
+        }
+    }
+
+    pub fn into_counter(self, key: Option<String>) -> RateLimitCounter {
+        RateLimitCounter {
+            method: None,
+            path: None,
+            graphql: None,
+            key,
+            limit: self.limit,
+            remaining: self.remaining,
+            reset_after: self.reset_after,
+        }
+    }
+}
+
+impl IntoResponse for QuotaExceeded {
+    fn into_response(self) -> Response {
+        let retry_after = self.retry_after;
+        warn!(%retry_after, "Rejected rate limited request, retry after {retry_after}s.");
+
+        let mut response = HttpResponse::<()>::failure(
+            StatusCode::TOO_MANY_REQUESTS,
+            Error::TooManyRequests("Rate limit exceeded".to_string()),
+        )
+        .into_response();
+
+        let headers = response.headers_mut();
+        self.quota.apply(headers);
+        headers.insert(RETRY_AFTER, HeaderValue::from(retry_after));
+
+        response
+    }
+}
+
+impl IntoResponse for Rejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::QuotaExceeded(exceeded) => exceeded.into_response(),
+            Self::Store(store_error) => HttpResponse::<()>::failure(
+                StatusCode::SERVICE_UNAVAILABLE,
+                Error::from(store_error),
+            )
+            .into_response(),
+        }
+    }
+}
+
+pub trait RateLimitMiddleware {
+    fn with_rate_limit(self, rate_limiter: Option<&RateLimiter>) -> Router<()>;
+}
+
+impl RateLimitMiddleware for Router<()> {
+    fn with_rate_limit(self, rate_limiter: Option<&RateLimiter>) -> Router<()> {
+        match rate_limiter {
+            Some(rate_limiter) => {
+                let rate_limiter = rate_limiter.clone();
+
+                self.layer(middleware::from_fn(
+                    move |request: Request, next: Next| {
+                        let rate_limiter = rate_limiter.clone();
+                        async move { limit(&rate_limiter, request, next).await }
+                    },
+                ))
+            }
+            None => self,
+        }
+    }
+}
+
+async fn limit(
+    rate_limiter: &RateLimiter,
+    request: Request,
+    next: Next,
+) -> Response {
+    match rate_limiter.acquire(request.headers()).await {
+        Ok(quota) => {
+            let mut response = next.run(request).await;
+            quota.apply(response.headers_mut());
+            response
+        }
+        Err(rejection) => rejection.into_response(),
+    }
+}
+
+fn invalid(message: &str) -> Error {
+    Error::RateLimit(message.to_string())
+}
@@ -1,14 +1,13 @@
-use crate::{
-    business::server::connection_establisher::ConnectionEstablisher,
-    model::request::registration_request::RegistrationRequest,
+use crate::business::{
+    app_state::AppState, server::connection_establisher::ConnectionEstablisher,
 };
 
-pub trait Restartable<T: ConnectionEstablisher> {
+pub trait Restartable<T: ConnectionEstablisher, R> {
     type Instance;
 
     fn restart(
         self,
-        connection_establisher: &T,
-        registration_request: RegistrationRequest,
+        app_state: &AppState<T>,
+        registration_request: R,
     ) -> impl Future<Output = Self::Instance>;
 }
@@ -1,109 +1,405 @@
 use axum::{
-    Json, Router,
-    routing::{delete, get, patch, post, put},
+    Router,
+    body::Bytes,
+    http::HeaderMap,
+    routing::{MethodFilter, on},
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
-use serde_json::Value;
 use tokio::task::JoinHandle;
-use tracing::info;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 
 use crate::{
-    business::server::{
-        RegistrationIdentifier, connection_establisher::ConnectionEstablisher,
-        restartable::Restartable,
+    business::{
+        app_state::AppState,
+        server::{
+            RegistrationIdentifier, RouteStub,
+            connection_establisher::{
+                BoundSocket, Connection, ConnectionEstablisher,
+                ConnectionHandler,
+            },
+            cors::{CorsMiddleware, CorsPolicy, CorsPolicyUpdate},
+            graphql::GraphQlResolver,
+            grpc::{GrpcRegistry, GrpcRegistryUpdate},
+            oidc::{OidcProvider, OidcProviderUpdate},
+            path_matching::{
+                PathMatching, PathMatchingMiddleware, find_conflict,
+            },
+            rate_limit::{RateLimitMiddleware, RateLimiter, RateLimiterUpdate},
+            restartable::Restartable,
+            socket::{SocketHandler, SocketHandlerUpdate},
+        },
+        store::store_error::StoreError,
     },
-    logging::http_trace::HttpTracingMiddleware,
     model::{
         error::Error,
+        graphql_operation::GraphQlOperation,
         http_method::HttpMethod,
-        internal::server_registration::{Registration, ServerRegistration},
+        internal::{
+            server_registration::{Registration, ServerRegistration},
+            stored_port::StoredPort,
+        },
+        port::Port,
         request::registration_request::RegistrationRequest,
+        response::rate_limit_counter::RateLimitCounter,
+        route_path::RoutePath,
     },
+    security::stub_auth::StubAuthenticator,
 };
 
 pub struct Server {
     connection: JoinHandle<()>,
-    port: String,
-    data: HashMap<RegistrationIdentifier, Value>,
+    shutdown: CancellationToken,
+    port: Port,
+    socket: Option<BoundSocket>,
+    state: ServerState,
 }
 
-impl Server {
-    async fn restart<T, F>(
-        connection_establisher: &T,
-        RegistrationRequest {
+pub struct RouteBatchUpdate {
+    pub port: Port,
+    pub registrations: Vec<RegistrationRequest>,
+}
+
+impl RouteBatchUpdate {
+    pub fn new(port: Port, registrations: Vec<RegistrationRequest>) -> Self {
+        Self {
             port,
-            method,
-            path,
-            response,
-        }: RegistrationRequest,
-        data_producer: F,
+            registrations,
+        }
+    }
+}
+
+/// Serves exactly `registrations` as the HTTP routes of the port, the
+/// other stubs on it are kept.
+pub struct RouteReplacement {
+    pub port: Port,
+    pub registrations: Vec<RegistrationRequest>,
+}
+
+impl RouteReplacement {
+    pub fn new(port: Port, registrations: Vec<RegistrationRequest>) -> Self {
+        Self {
+            port,
+            registrations,
+        }
+    }
+}
+
+/// A copy of a server's state, readable without holding the server store.
+pub struct ServerSnapshot {
+    port: Port,
+    state: ServerState,
+}
+
+impl ServerSnapshot {
+    pub fn get_port(&self) -> Port {
+        self.port
+    }
+
+    pub async fn get_rate_limit_counters(
+        &self,
+    ) -> Result<Vec<RateLimitCounter>, StoreError> {
+        let mut counters = vec![];
+
+        if let Some(rate_limiter) = &self.state.rate_limiter {
+            for (key, quota) in rate_limiter.get_counters().await? {
+                counters.push(quota.into_counter(key));
+            }
+        }
+
+        for (identifier, stub) in &self.state.data {
+            let Some(rate_limiter) = &stub.rate_limiter else {
+                continue;
+            };
+
+            for (key, quota) in rate_limiter.get_counters().await? {
+                counters.push(RateLimitCounter {
+                    method: Some(identifier.method.clone()),
+                    path: Some(identifier.path.to_string()),
+                    graphql: identifier.graphql.clone(),
+                    ..quota.into_counter(key)
+                });
+            }
+        }
+
+        Ok(counters)
+    }
+
+    pub async fn reset_rate_limit_counters(
+        &self,
+    ) -> Result<Vec<RateLimitCounter>, StoreError> {
+        let port = &self.port;
+        info!(%port, "Resetting rate limit counters on port {port}.");
+
+        let counters = self.get_rate_limit_counters().await?;
+
+        let rate_limiters = self.state.rate_limiter.iter().chain(
+            self.state
+                .data
+                .values()
+                .filter_map(|stub| stub.rate_limiter.as_ref()),
+        );
+        for rate_limiter in rate_limiters {
+            rate_limiter.reset().await?;
+        }
+
+        Ok(counters)
+    }
+}
+
+#[derive(Default, Clone)]
+struct ServerState {
+    data: HashMap<RegistrationIdentifier, RouteStub>,
+    grpc: GrpcRegistry,
+    socket: Option<SocketHandler>,
+    oidc: Option<OidcProvider>,
+    cors: Option<CorsPolicy>,
+    rate_limiter: Option<RateLimiter>,
+}
+
+impl Server {
+    /// Builds the handler for `state` and starts serving it on `port`,
+    /// taking over the socket of `previous`. `previous` keeps serving until
+    /// the caller shuts it down, so the port never goes unanswered.
+    async fn restart<T>(
+        app_state: &AppState<T>,
+        port: Port,
+        state: ServerState,
+        previous: Option<&Server>,
     ) -> Result<Self, Error>
     where
         T: ConnectionEstablisher,
-        F: FnOnce(
-            RegistrationIdentifier,
-            Value,
-        ) -> HashMap<RegistrationIdentifier, Value>,
     {
-        info!(%port, %method, %path, "Registering route [{method} (@{port})] {path}.");
+        let connection_establisher = app_state.get_connection_establisher();
+        let path_matching = connection_establisher.path_matching();
 
-        let registration_identifier =
-            RegistrationIdentifier::new(path.to_string(), method.clone());
+        if app_state.has_persistent_storage()
+            && let Some(stubs) = state.find_unstorable()
+        {
+            warn!(%port, "{stubs} on port {port} are not stored and won't be restored on the next start.");
+        }
+
+        if let Some((path, route)) = state.find_conflicts(path_matching) {
+            return Err(Error::Conflict(format!(
+                "`{path}` conflicts with the registered route `{route}` on port {port}"
+            )));
+        }
+
+        let handler = match (&state.socket, &state.oidc) {
+            (Some(socket), _) => ConnectionHandler::Socket(socket.clone()),
+            (None, Some(oidc)) => ConnectionHandler::Http(
+                oidc.router()
+                    .with_rate_limit(state.rate_limiter.as_ref())
+                    .with_cors(state.cors.as_ref()),
+                app_state.get_metrics(),
+            ),
+            (None, None) => ConnectionHandler::Http(
+                Server::create_router(
+                    &state.data,
+                    &state.grpc,
+                    state.rate_limiter.as_ref(),
+                    state.cors.as_ref(),
+                    path_matching,
+                ),
+                app_state.get_metrics(),
+            ),
+        };
 
-        let data = data_producer(registration_identifier, response);
-        let router = Server::create_router(port.clone(), &data);
-        let connection =
-            connection_establisher.connect(port.clone(), router).await?;
+        let inherited = previous.and_then(|previous| previous.socket.clone());
+        let shutdown = CancellationToken::new();
+        let Connection {
+            handle: connection,
+            port,
+            socket,
+        } = connection_establisher
+            .connect(port, handler, inherited, shutdown.clone())
+            .await?;
 
         Ok(Server {
             connection,
+            shutdown,
             port,
-            data,
+            socket,
+            state,
         })
     }
 
+    /// Clones the state, `server` is left untouched.
+    fn take_state(server: Option<&Server>, port: Port) -> ServerState {
+        match server {
+            Some(server) => {
+                info!(%port, "Restarting the server on port {port}.");
+                server.state.clone()
+            }
+            None => {
+                info!(%port, "Starting a server on port {port}.");
+                ServerState::default()
+            }
+        }
+    }
+
+    fn take_http_state(server: Option<&Server>, port: Port) -> ServerState {
+        let mut state = Server::take_state(server, port);
+
+        if state.socket.take().is_some() {
+            info!(%port, "Replacing the socket stub on port {port} with HTTP routes.");
+        }
+
+        if state.oidc.take().is_some() {
+            info!(%port, "Replacing the OIDC provider on port {port} with HTTP routes.");
+        }
+
+        state
+    }
+
     fn create_router(
-        port: String,
-        data: &HashMap<RegistrationIdentifier, Value>,
+        data: &HashMap<RegistrationIdentifier, RouteStub>,
+        grpc: &GrpcRegistry,
+        rate_limiter: Option<&RateLimiter>,
+        cors: Option<&CorsPolicy>,
+        path_matching: PathMatching,
     ) -> Router {
+        let mut routes: HashMap<(&RoutePath, &HttpMethod), Route> =
+            HashMap::new();
+
+        for (identifier, stub) in data {
+            let route = routes
+                .entry((&identifier.path, &identifier.method))
+                .or_default();
+
+            match &identifier.graphql {
+                Some(operation) => {
+                    route.operations.push((operation.clone(), stub.clone()))
+                }
+                None => route.stub = Some(stub.clone()),
+            }
+        }
+
         let mut router = Router::new();
 
-        for (request_identifier, response) in data {
-            let response = Json(response.clone());
+        for ((path, method), route) in routes {
+            let method_filter = MethodFilter::from(method);
 
-            let method_router = match &request_identifier.method {
-                HttpMethod::Get => get(async || response),
-                HttpMethod::Post => post(async || response),
-                HttpMethod::Put => put(async || response),
-                HttpMethod::Patch => patch(async || response),
-                HttpMethod::Delete => delete(async || response),
+            let method_router = if route.operations.is_empty() {
+                let stub = route.stub.unwrap_or_else(|| {
+                    RouteStub::new(Default::default(), None, None)
+                });
+                on(method_filter, async move |headers: HeaderMap| {
+                    stub.respond(&headers).await
+                })
+            } else {
+                let resolver = Arc::new(GraphQlResolver::new(
+                    route.operations,
+                    route.stub,
+                ));
+                on(
+                    method_filter,
+                    async move |headers: HeaderMap, body: Bytes| {
+                        resolver.resolve(&headers, &body).await
+                    },
+                )
             };
 
-            router = router.route(&request_identifier.path, method_router)
+            router = router.route(path, method_router)
         }
 
-        router.with_http_tracing(port)
+        router
+            .with_path_matching(path_matching)
+            .merge(grpc.router())
+            .with_rate_limit(rate_limiter)
+            .with_cors(cors)
     }
 
+    /// Stops accepting connections, in-flight requests are still drained.
     pub fn stop(&self) {
-        self.connection.abort();
+        self.shutdown.cancel();
+    }
+
+    /// Stops the server and waits until in-flight requests are drained and
+    /// its port has been released.
+    pub async fn shutdown(self) {
+        let port = &self.port;
+        info!(%port, "Shutting down the server on port {port}.");
+
+        self.shutdown.cancel();
+        let _ = self.connection.await;
+    }
+
+    pub fn snapshot(&self) -> ServerSnapshot {
+        ServerSnapshot {
+            port: self.port,
+            state: self.state.clone(),
+        }
+    }
+
+    pub fn get_port(&self) -> Port {
+        self.port
     }
 
     pub fn get_registration(
         &self,
-        path: String,
+        path: RoutePath,
         method: HttpMethod,
+        graphql: Option<GraphQlOperation>,
     ) -> Option<Registration> {
-        let registration_identifier =
-            RegistrationIdentifier::new(path.clone(), method.clone());
-        let response =
-            self.data.get(&registration_identifier).map(|r| r.clone());
+        let registration_identifier = RegistrationIdentifier::new(
+            path.clone(),
+            method.clone(),
+            graphql.clone(),
+        );
+        self.state
+            .data
+            .get(&registration_identifier)
+            .cloned()
+            .map(|stub| {
+                Registration::new(
+                    method,
+                    path,
+                    graphql,
+                    stub.auth.map(|auth| auth.get_auth().clone()),
+                    stub.rate_limiter.map(|limiter| limiter.get_rate_limit()),
+                    stub.response,
+                )
+            })
+    }
 
-        match response {
-            Some(response) => Some(Registration::new(method, path, response)),
-            None => None,
-        }
+    /// A registered route or gRPC method that `path` can't be served
+    /// alongside.
+    pub fn find_route_conflict(
+        &self,
+        path: &RoutePath,
+        path_matching: PathMatching,
+    ) -> Option<RoutePath> {
+        let routes = self.state.data.keys().map(|identifier| &identifier.path);
+
+        find_conflict(routes, path).cloned().or_else(|| {
+            self.state
+                .grpc
+                .routes()
+                .into_iter()
+                .find(|route| path_matching.route(route.clone()) == *path)
+        })
+    }
+
+    pub fn get_grpc_registry(&self) -> GrpcRegistry {
+        self.state.grpc.clone()
+    }
+
+    pub fn get_socket_handler(&self) -> Option<SocketHandler> {
+        self.state.socket.clone()
+    }
+
+    pub fn get_oidc_provider(&self) -> Option<OidcProvider> {
+        self.state.oidc.clone()
+    }
+
+    pub fn get_cors_policy(&self) -> Option<CorsPolicy> {
+        self.state.cors.clone()
+    }
+
+    pub fn get_rate_limiter(&self) -> Option<RateLimiter> {
+        self.state.rate_limiter.clone()
     }
 
     pub fn get_registrations(&self) -> ServerRegistration {
@@ -113,47 +409,351 @@ impl Server {
 
         let mut registrations = vec![];
 
-        for (identifier, response) in &self.data {
+        for (identifier, stub) in &self.state.data {
             registrations.push(Registration::new(
                 identifier.method.clone(),
                 identifier.path.clone(),
-                response.clone(),
+                identifier.graphql.clone(),
+                stub.auth.as_ref().map(|auth| auth.get_auth().clone()),
+                stub.rate_limiter.as_ref().map(RateLimiter::get_rate_limit),
+                stub.response.clone(),
             ));
         }
 
-        ServerRegistration::new(port.clone(), registrations)
+        ServerRegistration::new(
+            *port,
+            registrations,
+            self.state.grpc.get_registrations(),
+            self.state
+                .socket
+                .as_ref()
+                .map(SocketHandler::get_registration),
+            self.state.oidc.as_ref().map(OidcProvider::get_registration),
+            self.state.cors.as_ref().map(CorsPolicy::get_registration),
+            self.state
+                .rate_limiter
+                .as_ref()
+                .map(RateLimiter::get_rate_limit),
+        )
     }
 }
 
-impl<T: ConnectionEstablisher> Restartable<T> for Option<Server> {
+impl ServerState {
+    fn is_http(&self) -> bool {
+        !self.data.is_empty() || !self.grpc.get_registrations().is_empty()
+    }
+
+    /// The stubs a persistent storage couldn't bring back on restart.
+    fn find_unstorable(&self) -> Option<&'static str> {
+        if !self.grpc.routes().is_empty() {
+            Some("gRPC services")
+        } else if self.socket.is_some() {
+            Some("Socket stubs")
+        } else if self.oidc.is_some() {
+            Some("OIDC providers")
+        } else {
+            None
+        }
+    }
+
+    /// Two routes that can't be served together, a gRPC method shadows an
+    /// HTTP route on the same path.
+    fn find_conflicts(
+        &self,
+        path_matching: PathMatching,
+    ) -> Option<(RoutePath, RoutePath)> {
+        let mut paths = self
+            .data
+            .keys()
+            .map(|identifier| &identifier.path)
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths.dedup();
+
+        for (index, path) in paths.iter().enumerate() {
+            if let Some(route) = find_conflict(paths[..index].to_vec(), path) {
+                return Some(((*path).clone(), route.clone()));
+            }
+        }
+
+        self.grpc.routes().into_iter().find_map(|route| {
+            let path = path_matching.route(route.clone());
+            paths.contains(&&path).then_some((path, route))
+        })
+    }
+}
+
+#[derive(Default)]
+struct Route {
+    stub: Option<RouteStub>,
+    operations: Vec<(GraphQlOperation, RouteStub)>,
+}
+
+impl<T: ConnectionEstablisher> Restartable<T, RegistrationRequest>
+    for Option<&Server>
+{
     type Instance = Result<Server, Error>;
 
     async fn restart(
         self,
-        connection_establisher: &T,
-        registration_request: RegistrationRequest,
+        app_state: &AppState<T>,
+        RegistrationRequest {
+            port,
+            method,
+            path,
+            graphql,
+            auth,
+            rate_limit,
+            response,
+        }: RegistrationRequest,
+    ) -> Self::Instance {
+        let auth = auth.map(StubAuthenticator::new).transpose()?;
+        let rate_limiter = rate_limit.map(RateLimiter::new).transpose()?;
+        let mut state = Server::take_http_state(self, port);
+
+        info!(%port, %method, %path, "Registering route [{method} (@{port})] {path}.");
+
+        let registration_identifier =
+            RegistrationIdentifier::new(path, method, graphql);
+        state.data.insert(
+            registration_identifier,
+            RouteStub::new(response, auth, rate_limiter),
+        );
+
+        Server::restart(app_state, port, state, self).await
+    }
+}
+
+impl<T: ConnectionEstablisher> Restartable<T, RouteBatchUpdate>
+    for Option<&Server>
+{
+    type Instance = Result<Server, Error>;
+
+    async fn restart(
+        self,
+        app_state: &AppState<T>,
+        RouteBatchUpdate {
+            port,
+            registrations,
+        }: RouteBatchUpdate,
+    ) -> Self::Instance {
+        let stubs = route_stubs(port, registrations)?;
+
+        let mut state = Server::take_http_state(self, port);
+        state.data.extend(stubs);
+
+        Server::restart(app_state, port, state, self).await
+    }
+}
+
+impl<T: ConnectionEstablisher> Restartable<T, StoredPort> for Option<&Server> {
+    type Instance = Result<Server, Error>;
+
+    async fn restart(
+        self,
+        app_state: &AppState<T>,
+        StoredPort {
+            port,
+            registrations,
+            cors,
+            rate_limit,
+        }: StoredPort,
+    ) -> Self::Instance {
+        let stubs = route_stubs(port, registrations)?;
+
+        let mut state = Server::take_http_state(self, port);
+        state.data.extend(stubs);
+
+        if let Some(cors) = cors {
+            state.cors =
+                Some(CorsPolicy::new(cors.into_registration_request(port))?);
+        }
+
+        if let Some(rate_limit) = rate_limit {
+            state.rate_limiter = Some(RateLimiter::new(rate_limit)?);
+        }
+
+        Server::restart(app_state, port, state, self).await
+    }
+}
+
+impl<T: ConnectionEstablisher> Restartable<T, RouteReplacement>
+    for Option<&Server>
+{
+    type Instance = Result<Server, Error>;
+
+    async fn restart(
+        self,
+        app_state: &AppState<T>,
+        RouteReplacement {
+            port,
+            registrations,
+        }: RouteReplacement,
     ) -> Self::Instance {
-        let port = &registration_request.port;
+        info!(%port, "Replacing the routes on port {port}.");
 
-        if self.is_some() {
-            info!(%port, "Restarting the server on port {port}.");
+        // Without routes to serve, a socket or OIDC stub can stay.
+        let mut state = if registrations.is_empty() {
+            Server::take_state(self, port)
         } else {
-            info!(%port, "Starting a server on port {port}.");
+            Server::take_http_state(self, port)
+        };
+        state.data = route_stubs(port, registrations)?.into_iter().collect();
+
+        Server::restart(app_state, port, state, self).await
+    }
+}
+
+fn route_stubs(
+    port: Port,
+    registrations: Vec<RegistrationRequest>,
+) -> Result<Vec<(RegistrationIdentifier, RouteStub)>, Error> {
+    let mut stubs = vec![];
+
+    for RegistrationRequest {
+        method,
+        path,
+        graphql,
+        auth,
+        rate_limit,
+        response,
+        ..
+    } in registrations
+    {
+        let auth = auth.map(StubAuthenticator::new).transpose()?;
+        let rate_limiter = rate_limit.map(RateLimiter::new).transpose()?;
+
+        info!(%port, %method, %path, "Registering route [{method} (@{port})] {path}.");
+
+        stubs.push((
+            RegistrationIdentifier::new(path, method, graphql),
+            RouteStub::new(response, auth, rate_limiter),
+        ));
+    }
+
+    Ok(stubs)
+}
+
+impl<T: ConnectionEstablisher> Restartable<T, GrpcRegistryUpdate>
+    for Option<&Server>
+{
+    type Instance = Result<Server, Error>;
+
+    async fn restart(
+        self,
+        app_state: &AppState<T>,
+        GrpcRegistryUpdate { port, registry }: GrpcRegistryUpdate,
+    ) -> Self::Instance {
+        let mut state = Server::take_http_state(self, port);
+
+        info!(%port, "Updating gRPC registrations on port {port}.");
+
+        state.grpc = registry;
+
+        Server::restart(app_state, port, state, self).await
+    }
+}
+
+impl<T: ConnectionEstablisher> Restartable<T, SocketHandlerUpdate>
+    for Option<&Server>
+{
+    type Instance = Result<Server, Error>;
+
+    async fn restart(
+        self,
+        app_state: &AppState<T>,
+        SocketHandlerUpdate { port, handler }: SocketHandlerUpdate,
+    ) -> Self::Instance {
+        let state = Server::take_state(self, port);
+
+        if state.is_http() {
+            info!(%port, "Replacing the HTTP routes on port {port} with a socket stub.");
         }
 
-        let mut data = match self {
-            Some(mut server) => std::mem::take(&mut server.data),
-            None => HashMap::new(),
+        if state.oidc.is_some() {
+            info!(%port, "Replacing the OIDC provider on port {port} with a socket stub.");
+        }
+
+        info!(%port, "Registering socket stub on port {port}.");
+
+        let state = ServerState {
+            socket: Some(handler),
+            ..ServerState::default()
         };
 
-        Server::restart(
-            connection_establisher,
-            registration_request,
-            move |registration_identifier, response| {
-                data.insert(registration_identifier, response);
-                data
-            },
-        )
-        .await
+        Server::restart(app_state, port, state, self).await
+    }
+}
+
+impl<T: ConnectionEstablisher> Restartable<T, OidcProviderUpdate>
+    for Option<&Server>
+{
+    type Instance = Result<Server, Error>;
+
+    async fn restart(
+        self,
+        app_state: &AppState<T>,
+        OidcProviderUpdate { port, provider }: OidcProviderUpdate,
+    ) -> Self::Instance {
+        let state = Server::take_state(self, port);
+
+        if state.is_http() {
+            info!(%port, "Replacing the HTTP routes on port {port} with an OIDC provider.");
+        }
+
+        if state.socket.is_some() {
+            info!(%port, "Replacing the socket stub on port {port} with an OIDC provider.");
+        }
+
+        info!(%port, "Registering OIDC provider on port {port}.");
+
+        let state = ServerState {
+            oidc: Some(provider),
+            cors: state.cors,
+            rate_limiter: state.rate_limiter,
+            ..ServerState::default()
+        };
+
+        Server::restart(app_state, port, state, self).await
+    }
+}
+
+impl<T: ConnectionEstablisher> Restartable<T, CorsPolicyUpdate>
+    for Option<&Server>
+{
+    type Instance = Result<Server, Error>;
+
+    async fn restart(
+        self,
+        app_state: &AppState<T>,
+        CorsPolicyUpdate { port, policy }: CorsPolicyUpdate,
+    ) -> Self::Instance {
+        let mut state = Server::take_state(self, port);
+
+        info!(%port, "Updating CORS policy on port {port}.");
+
+        state.cors = Some(policy);
+
+        Server::restart(app_state, port, state, self).await
+    }
+}
+
+impl<T: ConnectionEstablisher> Restartable<T, RateLimiterUpdate>
+    for Option<&Server>
+{
+    type Instance = Result<Server, Error>;
+
+    async fn restart(
+        self,
+        app_state: &AppState<T>,
+        RateLimiterUpdate { port, rate_limiter }: RateLimiterUpdate,
+    ) -> Self::Instance {
+        let mut state = Server::take_state(self, port);
+
+        info!(%port, "Updating rate limit on port {port}.");
+
+        state.rate_limiter = Some(rate_limiter);
+
+        Server::restart(app_state, port, state, self).await
     }
 }
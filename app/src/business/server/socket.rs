@@ -0,0 +1,303 @@
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use regex::bytes::Regex;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+    net::{TcpStream, UdpSocket},
+};
+use tracing::{info, warn};
+
+use crate::{
+    business::store::{Store, store_error::StoreError},
+    model::{
+        error::Error,
+        internal::server_registration::SocketRegistration,
+        port::Port,
+        request::socket_registration_request::{
+            SocketFraming, SocketProtocol, SocketRegistrationRequest,
+        },
+        response::captured_message::CapturedMessage,
+    },
+};
+
+const MAX_FRAME_SIZE: usize = 64 * 1024;
+pub const MAX_CAPTURED_MESSAGES: usize = 1000;
+
+#[derive(Clone)]
+pub struct SocketHandler {
+    registration: SocketRegistration,
+    rules: Arc<Vec<SocketRule>>,
+    captured: Store<VecDeque<CapturedMessage>>,
+}
+
+pub struct SocketHandlerUpdate {
+    pub port: Port,
+    pub handler: SocketHandler,
+}
+
+impl SocketHandlerUpdate {
+    pub fn new(port: Port, handler: SocketHandler) -> Self {
+        Self { port, handler }
+    }
+}
+
+pub struct SocketReply {
+    pub data: Vec<u8>,
+    pub close: bool,
+}
+
+struct SocketRule {
+    pattern: Regex,
+    reply: String,
+    close: bool,
+}
+
+impl SocketHandler {
+    pub fn new(
+        registration_request: SocketRegistrationRequest,
+    ) -> Result<Self, Error> {
+        let registration = SocketRegistration::from(registration_request);
+
+        if registration.banner.is_some()
+            && registration.protocol == SocketProtocol::Udp
+        {
+            return Err(Error::Socket(
+                "Banners are only supported for TCP sockets".to_string(),
+            ));
+        }
+
+        let rules = registration
+            .rules
+            .iter()
+            .map(|rule| {
+                Regex::new(&rule.pattern)
+                    .map(|pattern| SocketRule {
+                        pattern,
+                        reply: rule.reply.clone(),
+                        close: rule.close,
+                    })
+                    .map_err(|err| {
+                        Error::Socket(format!(
+                            "Invalid pattern `{}`, {err}",
+                            rule.pattern
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            registration,
+            rules: Arc::new(rules),
+            captured: Store::default(),
+        })
+    }
+
+    pub fn protocol(&self) -> SocketProtocol {
+        self.registration.protocol
+    }
+
+    pub fn banner(&self) -> Option<&[u8]> {
+        self.registration.banner.as_deref().map(str::as_bytes)
+    }
+
+    pub fn get_registration(&self) -> SocketRegistration {
+        self.registration.clone()
+    }
+
+    pub async fn get_captured(
+        &self,
+    ) -> Result<Vec<CapturedMessage>, StoreError> {
+        self.captured
+            .read(|captured| captured.iter().cloned().collect())
+            .await
+    }
+
+    pub async fn clear_captured(
+        &self,
+    ) -> Result<Vec<CapturedMessage>, StoreError> {
+        self.captured
+            .write(|captured| std::mem::take(captured).into())
+            .await
+    }
+
+    pub async fn respond(&self, peer: &str, data: &[u8]) -> Vec<SocketReply> {
+        let frames = match self.registration.framing {
+            SocketFraming::Line => data
+                .split(|byte| *byte == b'\n')
+                .map(trim_line_ending)
+                .filter(|frame| !frame.is_empty())
+                .collect::<Vec<_>>(),
+            SocketFraming::Raw => vec![data],
+        };
+
+        let mut replies = vec![];
+        for frame in frames {
+            replies.extend(self.receive(peer, frame).await);
+        }
+
+        replies
+    }
+
+    async fn receive(&self, peer: &str, frame: &[u8]) -> Option<SocketReply> {
+        if self.registration.capture {
+            self.capture(peer, frame).await;
+        }
+
+        for rule in self.rules.iter() {
+            if let Some(captures) = rule.pattern.captures(frame) {
+                let mut data = vec![];
+                captures.expand(rule.reply.as_bytes(), &mut data);
+
+                return Some(SocketReply {
+                    data,
+                    close: rule.close,
+                });
+            }
+        }
+
+        if self.registration.echo {
+            let mut data = frame.to_vec();
+            if self.registration.framing == SocketFraming::Line {
+                data.push(b'\n');
+            }
+
+            return Some(SocketReply { data, close: false });
+        }
+
+        None
+    }
+
+    /// Replies don't depend on the capture, so a failing store is only
+    /// logged. Only the latest `MAX_CAPTURED_MESSAGES` are kept.
+    async fn capture(&self, peer: &str, frame: &[u8]) {
+        let received_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis())
+            .unwrap_or_default();
+
+        let message = CapturedMessage::new(
+            peer.to_string(),
+            received_at,
+            String::from_utf8_lossy(frame).to_string(),
+            BASE64_STANDARD.encode(frame),
+        );
+
+        let capture = |captured: &mut VecDeque<CapturedMessage>| {
+            if captured.len() == MAX_CAPTURED_MESSAGES {
+                captured.pop_front();
+            }
+            captured.push_back(message);
+        };
+
+        if let Err(err) = self.captured.write(capture).await {
+            warn!(%peer, %err, "Failed to capture a message from {peer}, {err}.");
+        }
+    }
+
+    pub async fn serve_tcp(self, stream: TcpStream, peer: String) {
+        info!(%peer, "Accepted socket connection from {peer}.");
+
+        let (reader, mut writer) = stream.into_split();
+
+        if let Some(banner) = self.banner()
+            && writer.write_all(banner).await.is_err()
+        {
+            return;
+        }
+
+        match self.registration.framing {
+            SocketFraming::Line => {
+                let mut reader = BufReader::new(reader);
+                let mut line = vec![];
+
+                loop {
+                    line.clear();
+                    match (&mut reader)
+                        .take(MAX_FRAME_SIZE as u64)
+                        .read_until(b'\n', &mut line)
+                        .await
+                    {
+                        Ok(0) | Err(_) => break,
+                        Ok(_) => {}
+                    }
+
+                    if line.len() == MAX_FRAME_SIZE && !line.ends_with(b"\n") {
+                        warn!(
+                            %peer,
+                            "Closing socket connection from {peer}, line exceeds {MAX_FRAME_SIZE} bytes."
+                        );
+                        break;
+                    }
+
+                    let reply =
+                        self.receive(&peer, trim_line_ending(&line)).await;
+                    if !SocketHandler::write_reply(&mut writer, reply).await {
+                        break;
+                    }
+                }
+            }
+            SocketFraming::Raw => {
+                let mut reader = reader;
+                let mut buffer = vec![0; MAX_FRAME_SIZE];
+
+                loop {
+                    let length = match reader.read(&mut buffer).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(length) => length,
+                    };
+
+                    let reply = self.receive(&peer, &buffer[..length]).await;
+                    if !SocketHandler::write_reply(&mut writer, reply).await {
+                        break;
+                    }
+                }
+            }
+        }
+
+        info!(%peer, "Closed socket connection from {peer}.");
+    }
+
+    pub async fn serve_udp(self, socket: UdpSocket) {
+        let mut buffer = vec![0; MAX_FRAME_SIZE];
+
+        loop {
+            let (length, peer) = match socket.recv_from(&mut buffer).await {
+                Ok(received) => received,
+                Err(err) => {
+                    warn!(%err, "Failed to receive datagram, {err}.");
+                    continue;
+                }
+            };
+
+            for reply in
+                self.respond(&peer.to_string(), &buffer[..length]).await
+            {
+                if let Err(err) = socket.send_to(&reply.data, peer).await {
+                    warn!(%peer, %err, "Failed to reply to {peer}, {err}.");
+                }
+            }
+        }
+    }
+
+    async fn write_reply<W: AsyncWrite + Unpin>(
+        writer: &mut W,
+        reply: Option<SocketReply>,
+    ) -> bool {
+        match reply {
+            Some(reply) => {
+                writer.write_all(&reply.data).await.is_ok() && !reply.close
+            }
+            None => true,
+        }
+    }
+}
+
+fn trim_line_ending(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
@@ -0,0 +1,78 @@
+use crate::{
+    business::{
+        app_state::AppState,
+        server::connection_establisher::ConnectionEstablisher,
+        store::store_error::StoreError,
+    },
+    model::{
+        internal::server_registration::{Registration, ServerRegistration},
+        request::{page_request::PageRequest, route_filter::RouteFilter},
+        response::{page::Page, server_summary::ServerSummary},
+        route_path::RoutePath,
+    },
+};
+
+/// Summarises one page of the running servers, ordered by port.
+pub async fn list_servers<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+    page_request: PageRequest,
+) -> Result<Page<ServerSummary>, StoreError> {
+    let servers = app_state
+        .get_registrations()
+        .await?
+        .iter()
+        .map(ServerSummary::from)
+        .collect();
+
+    Ok(Page::new(servers, page_request))
+}
+
+/// Looks up the routes of a server matching `filter`, ordered by path, then
+/// method, then GraphQL operation.
+pub fn find_routes(
+    server_registration: ServerRegistration,
+    filter: &RouteFilter,
+    page_request: PageRequest,
+) -> Page<Registration> {
+    let mut routes = server_registration
+        .registrations
+        .into_iter()
+        .filter(|registration| matches(registration, filter))
+        .collect::<Vec<_>>();
+
+    routes.sort_by_cached_key(|registration| {
+        (
+            registration.path.to_string(),
+            registration.method.as_str(),
+            registration.graphql.as_ref().map(|graphql| {
+                serde_json::to_string(graphql).unwrap_or_default()
+            }),
+        )
+    });
+
+    Page::new(routes, page_request)
+}
+
+fn matches(registration: &Registration, filter: &RouteFilter) -> bool {
+    let method_matches = filter
+        .method
+        .as_ref()
+        .is_none_or(|method| *method == registration.method);
+
+    let path_matches = filter
+        .path
+        .as_deref()
+        .is_none_or(|path| matches_path(&registration.path, path));
+
+    method_matches && path_matches
+}
+
+/// A route matches its own template as well as every path it would serve.
+fn matches_path(route: &RoutePath, path: &str) -> bool {
+    if &**route == path {
+        return true;
+    }
+
+    let mut router = matchit::Router::new();
+    router.insert(&**route, ()).is_ok() && router.at(path).is_ok()
+}
@@ -0,0 +1,76 @@
+use tracing::error;
+
+use crate::{
+    business::{
+        app_state::AppState,
+        server::{
+            connection_establisher::ConnectionEstablisher,
+            restartable::Restartable, server::Server,
+        },
+    },
+    model::{error::Error, port::Port},
+};
+
+/// Restarts the server on `port` with `update` and returns the port it
+/// ended up on.
+pub async fn update_server<T, U>(
+    app_state: &AppState<T>,
+    port: Port,
+    update: U,
+) -> Result<Port, Error>
+where
+    T: ConnectionEstablisher,
+    for<'a> Option<&'a Server>:
+        Restartable<T, U, Instance = Result<Server, Error>>,
+{
+    let previous = if port.is_ephemeral() {
+        None
+    } else {
+        app_state.take_server(port).await?
+    };
+
+    let result = match previous.as_ref().restart(app_state, update).await {
+        Ok(server) => add_restarted_server(app_state, server).await,
+        Err(err) => Err(err),
+    };
+
+    match (result, previous) {
+        (Ok(port), Some(previous)) => {
+            previous.shutdown().await;
+            Ok(port)
+        }
+        (Ok(port), None) => Ok(port),
+        (Err(err), Some(previous)) => {
+            let context = match app_state.put_back_server(previous).await {
+                Ok(()) => {
+                    format!("the previous server on port {port} keeps serving")
+                }
+                Err(restore_err) => {
+                    error!(%port, "Failed to keep the previous server on port {port}, {restore_err}.");
+                    format!(
+                        "the previous server on port {port} could not be kept either, {restore_err}"
+                    )
+                }
+            };
+            Err(err.with_context(&context))
+        }
+        (Err(err), None) => Err(err),
+    }
+}
+
+/// Shuts the server down again if it can't be stored.
+pub async fn add_restarted_server<T: ConnectionEstablisher>(
+    app_state: &AppState<T>,
+    server: Server,
+) -> Result<Port, Error> {
+    let port = server.get_port();
+
+    if let Err(err) = app_state.add_server(server).await {
+        if let Ok(Some(server)) = app_state.take_server(port).await {
+            server.shutdown().await;
+        }
+        return Err(err.into());
+    }
+
+    Ok(port)
+}
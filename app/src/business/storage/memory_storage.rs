@@ -0,0 +1,37 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    business::{
+        storage::{RegistrationStorage, StorageFuture},
+        store::Store,
+    },
+    model::{internal::stored_port::StoredPort, port::Port},
+};
+
+/// Keeps registrations for as long as the process runs.
+#[derive(Default)]
+pub struct MemoryStorage {
+    ports: Store<BTreeMap<Port, StoredPort>>,
+}
+
+impl RegistrationStorage for MemoryStorage {
+    fn save_port(&self, stored_port: StoredPort) -> StorageFuture<'_, ()> {
+        Box::pin(self.ports.write(move |ports| {
+            ports.insert(stored_port.port, stored_port);
+        }))
+    }
+
+    fn remove_port(&self, port: Port) -> StorageFuture<'_, ()> {
+        Box::pin(self.ports.write(move |ports| {
+            ports.remove(&port);
+        }))
+    }
+
+    fn clear(&self) -> StorageFuture<'_, ()> {
+        Box::pin(self.ports.write(|ports| ports.clear()))
+    }
+
+    fn load(&self) -> StorageFuture<'_, Vec<StoredPort>> {
+        Box::pin(self.ports.read(|ports| ports.values().cloned().collect()))
+    }
+}
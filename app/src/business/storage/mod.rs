@@ -0,0 +1,70 @@
+use std::{future::Future, path::PathBuf, pin::Pin, str::FromStr, sync::Arc};
+
+use crate::{
+    business::{
+        storage::{
+            memory_storage::MemoryStorage, sqlite_storage::SqliteStorage,
+        },
+        store::store_error::StoreError,
+    },
+    model::{internal::stored_port::StoredPort, port::Port},
+};
+
+pub mod memory_storage;
+pub mod sqlite_storage;
+
+const SQLITE_PREFIX: &str = "sqlite:";
+
+pub type StorageFuture<'a, R> =
+    Pin<Box<dyn Future<Output = Result<R, StoreError>> + Send + 'a>>;
+
+/// Keeps the HTTP routes, CORS policy and rate limit of every port for the
+/// next start. gRPC services, socket stubs and OIDC providers are not kept.
+pub trait RegistrationStorage: Send + Sync {
+    /// Replaces everything kept for the port of `stored_port`.
+    fn save_port(&self, stored_port: StoredPort) -> StorageFuture<'_, ()>;
+
+    fn remove_port(&self, port: Port) -> StorageFuture<'_, ()>;
+
+    fn clear(&self) -> StorageFuture<'_, ()>;
+
+    /// Every kept port, ordered by port.
+    fn load(&self) -> StorageFuture<'_, Vec<StoredPort>>;
+
+    /// Whether the registrations outlive the process.
+    fn is_persistent(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Storage {
+    #[default]
+    Memory,
+    Sqlite(PathBuf),
+}
+
+impl Storage {
+    pub fn open(&self) -> Result<Arc<dyn RegistrationStorage>, String> {
+        match self {
+            Storage::Memory => Ok(Arc::new(MemoryStorage::default())),
+            Storage::Sqlite(path) => Ok(Arc::new(SqliteStorage::open(path)?)),
+        }
+    }
+}
+
+impl FromStr for Storage {
+    type Err = String;
+
+    fn from_str(storage: &str) -> Result<Self, Self::Err> {
+        match storage.strip_prefix(SQLITE_PREFIX) {
+            Some(path) if !path.is_empty() => {
+                Ok(Storage::Sqlite(PathBuf::from(path)))
+            }
+            _ if storage.eq_ignore_ascii_case("memory") => Ok(Storage::Memory),
+            _ => Err(format!(
+                "Unknown storage `{storage}`, expected one of `memory` or `sqlite:<file>`"
+            )),
+        }
+    }
+}
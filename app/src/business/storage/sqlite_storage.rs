@@ -0,0 +1,414 @@
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use rusqlite::{Connection, params};
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::task;
+use tracing::info;
+
+use crate::{
+    business::{
+        storage::{RegistrationStorage, StorageFuture},
+        store::store_error::StoreError,
+    },
+    model::{internal::stored_port::StoredPort, port::Port},
+};
+
+/// Applied in order, `PRAGMA user_version` counts the ones already applied.
+const MIGRATIONS: [&str; 2] = [
+    "CREATE TABLE registrations (
+        port INTEGER NOT NULL,
+        position INTEGER NOT NULL,
+        registration TEXT NOT NULL,
+        PRIMARY KEY (port, position)
+    )",
+    "CREATE TABLE port_settings (
+        port INTEGER PRIMARY KEY,
+        cors TEXT,
+        rate_limit TEXT
+    )",
+];
+
+/// Keeps registrations in an SQLite file, so they outlive the process.
+pub struct SqliteStorage {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteStorage {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let file = path.display();
+        let mut connection = Connection::open(path)
+            .map_err(|err| format!("Failed to open `{file}`, {err}"))?;
+
+        SqliteStorage::migrate(&mut connection)
+            .map_err(|err| format!("Failed to migrate `{file}`, {err}"))?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    fn migrate(connection: &mut Connection) -> rusqlite::Result<()> {
+        let version: usize =
+            connection
+                .query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            let transaction = connection.transaction()?;
+            transaction.execute_batch(migration)?;
+            transaction.pragma_update(None, "user_version", index + 1)?;
+            transaction.commit()?;
+
+            let version = index + 1;
+            info!(%version, "Migrated the registration storage to version {version}.");
+        }
+
+        Ok(())
+    }
+
+    /// Runs `operation` off the async runtime, SQLite calls block.
+    fn run<R, F>(&self, operation: F) -> StorageFuture<'_, R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<R, StoreError> + Send + 'static,
+    {
+        let connection = self.connection.clone();
+
+        Box::pin(async move {
+            task::spawn_blocking(move || {
+                let mut connection =
+                    connection.lock().map_err(|_| StoreError::Poisoned)?;
+                operation(&mut connection)
+            })
+            .await
+            .map_err(|err| {
+                StoreError::Backend(format!(
+                    "The SQLite storage failed to run, {err}"
+                ))
+            })?
+        })
+    }
+}
+
+impl RegistrationStorage for SqliteStorage {
+    fn save_port(&self, stored_port: StoredPort) -> StorageFuture<'_, ()> {
+        self.run(move |connection| {
+            let port = stored_port.port.number();
+            let transaction = connection.transaction().map_err(sqlite_error)?;
+            delete_port(&transaction, port)?;
+
+            for (position, registration) in
+                stored_port.registrations.iter().enumerate()
+            {
+                transaction
+                    .execute(
+                        "INSERT INTO registrations (port, position, registration) VALUES (?1, ?2, ?3)",
+                        params![port, position, to_json(registration)?],
+                    )
+                    .map_err(sqlite_error)?;
+            }
+
+            if stored_port.cors.is_some() || stored_port.rate_limit.is_some() {
+                let cors = stored_port.cors.as_ref().map(to_json).transpose()?;
+                let rate_limit =
+                    stored_port.rate_limit.as_ref().map(to_json).transpose()?;
+
+                transaction
+                    .execute(
+                        "INSERT INTO port_settings (port, cors, rate_limit) VALUES (?1, ?2, ?3)",
+                        params![port, cors, rate_limit],
+                    )
+                    .map_err(sqlite_error)?;
+            }
+
+            transaction.commit().map_err(sqlite_error)
+        })
+    }
+
+    fn remove_port(&self, port: Port) -> StorageFuture<'_, ()> {
+        self.run(move |connection| {
+            let transaction = connection.transaction().map_err(sqlite_error)?;
+            delete_port(&transaction, port.number())?;
+            transaction.commit().map_err(sqlite_error)
+        })
+    }
+
+    fn clear(&self) -> StorageFuture<'_, ()> {
+        self.run(|connection| {
+            connection
+                .execute_batch(
+                    "DELETE FROM registrations; DELETE FROM port_settings;",
+                )
+                .map_err(sqlite_error)
+        })
+    }
+
+    fn load(&self) -> StorageFuture<'_, Vec<StoredPort>> {
+        self.run(|connection| {
+            let mut ports = BTreeMap::new();
+
+            let mut statement = connection
+                .prepare(
+                    "SELECT port, registration FROM registrations ORDER BY port, position",
+                )
+                .map_err(sqlite_error)?;
+            let rows = statement
+                .query_map([], |row| {
+                    Ok((row.get::<_, u16>(0)?, row.get::<_, String>(1)?))
+                })
+                .map_err(sqlite_error)?;
+
+            for row in rows {
+                let (port, registration) = row.map_err(sqlite_error)?;
+                stored_port(&mut ports, port)
+                    .registrations
+                    .push(from_json(&registration)?);
+            }
+
+            let mut statement = connection
+                .prepare("SELECT port, cors, rate_limit FROM port_settings")
+                .map_err(sqlite_error)?;
+            let rows = statement
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, u16>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                    ))
+                })
+                .map_err(sqlite_error)?;
+
+            for row in rows {
+                let (port, cors, rate_limit) = row.map_err(sqlite_error)?;
+                let stored_port = stored_port(&mut ports, port);
+                stored_port.cors = cors.as_deref().map(from_json).transpose()?;
+                stored_port.rate_limit =
+                    rate_limit.as_deref().map(from_json).transpose()?;
+            }
+
+            Ok(ports.into_values().collect())
+        })
+    }
+
+    fn is_persistent(&self) -> bool {
+        true
+    }
+}
+
+fn delete_port(connection: &Connection, port: u16) -> Result<(), StoreError> {
+    for statement in [
+        "DELETE FROM registrations WHERE port = ?1",
+        "DELETE FROM port_settings WHERE port = ?1",
+    ] {
+        connection
+            .execute(statement, [port])
+            .map_err(sqlite_error)?;
+    }
+
+    Ok(())
+}
+
+fn stored_port(
+    ports: &mut BTreeMap<u16, StoredPort>,
+    port: u16,
+) -> &mut StoredPort {
+    ports
+        .entry(port)
+        .or_insert_with(|| StoredPort::new(Port::new(port)))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, StoreError> {
+    serde_json::to_string(value).map_err(|err| {
+        StoreError::Backend(format!(
+            "Failed to serialize a registration, {err}"
+        ))
+    })
+}
+
+fn from_json<T: DeserializeOwned>(value: &str) -> Result<T, StoreError> {
+    serde_json::from_str(value).map_err(|err| {
+        StoreError::Backend(format!(
+            "Failed to read a stored registration, {err}"
+        ))
+    })
+}
+
+fn sqlite_error(err: rusqlite::Error) -> StoreError {
+    StoreError::Backend(format!("The SQLite storage failed, {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf, process};
+
+    use rusqlite::Connection;
+    use serde_json::json;
+
+    use crate::{
+        business::storage::RegistrationStorage,
+        model::{internal::stored_port::StoredPort, port::Port},
+    };
+
+    use super::{MIGRATIONS, SqliteStorage};
+
+    fn database(name: &str) -> PathBuf {
+        env::temp_dir()
+            .join(format!("api-gen-storage-{}-{name}.db", process::id()))
+    }
+
+    fn stored_port(port: u16, paths: &[&str]) -> StoredPort {
+        serde_json::from_value(json!({
+            "port": port.to_string(),
+            "registrations": paths
+                .iter()
+                .map(|path| json!({
+                    "port": port.to_string(),
+                    "method": "GET",
+                    "path": path,
+                    "response": [path],
+                }))
+                .collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    fn paths(stored_ports: Vec<StoredPort>) -> Vec<String> {
+        stored_ports
+            .into_iter()
+            .flat_map(|stored_port| stored_port.registrations)
+            .map(|registration| {
+                format!("{}{}", registration.port, registration.path)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn should_replace_registrations_of_a_port() {
+        let file = database("replace");
+        let storage = SqliteStorage::open(&file).unwrap();
+
+        storage
+            .save_port(stored_port(3001, &["/b", "/a"]))
+            .await
+            .unwrap();
+        storage
+            .save_port(stored_port(3000, &["/orders"]))
+            .await
+            .unwrap();
+        storage
+            .save_port(stored_port(3000, &["/users"]))
+            .await
+            .unwrap();
+        let loaded = storage.load().await;
+        fs::remove_file(&file).unwrap();
+
+        assert_eq!(
+            vec!["3000/users", "3001/b", "3001/a"],
+            paths(loaded.unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_remove_and_clear_registrations() {
+        let file = database("remove");
+        let storage = SqliteStorage::open(&file).unwrap();
+
+        for port in [3000, 3001, 3002] {
+            storage.save_port(stored_port(port, &["/"])).await.unwrap();
+        }
+        storage.remove_port(Port::new(3001)).await.unwrap();
+        let after_remove = storage.load().await.unwrap();
+        storage.clear().await.unwrap();
+        let after_clear = storage.load().await.unwrap();
+        fs::remove_file(&file).unwrap();
+
+        assert_eq!(vec!["3000/", "3002/"], paths(after_remove));
+        assert!(after_clear.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_keep_registrations_when_reopened() {
+        let file = database("reopen");
+        let storage = SqliteStorage::open(&file).unwrap();
+        storage
+            .save_port(stored_port(3000, &["/orders"]))
+            .await
+            .unwrap();
+        drop(storage);
+
+        let loaded = SqliteStorage::open(&file).unwrap().load().await;
+        let version: usize = Connection::open(&file)
+            .unwrap()
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        fs::remove_file(&file).unwrap();
+
+        assert_eq!(vec!["3000/orders"], paths(loaded.unwrap()));
+        assert_eq!(MIGRATIONS.len(), version);
+    }
+
+    #[tokio::test]
+    async fn should_keep_cors_policy_and_rate_limit_of_a_port() {
+        let file = database("settings");
+        let storage = SqliteStorage::open(&file).unwrap();
+        let mut settings = stored_port(3000, &[]);
+        settings.cors = serde_json::from_value(json!({
+            "allowedOrigins": ["https://app.example.com"],
+            "allowedMethods": ["GET"],
+            "allowedHeaders": [],
+            "exposedHeaders": [],
+            "allowCredentials": false,
+        }))
+        .unwrap();
+        settings.rate_limit = serde_json::from_value(json!({
+            "strategy": "fixedWindow",
+            "limit": 5,
+            "windowSeconds": 60,
+        }))
+        .unwrap();
+
+        storage.save_port(settings).await.unwrap();
+        let loaded = storage.load().await.unwrap();
+        storage.remove_port(Port::new(3000)).await.unwrap();
+        let after_remove = storage.load().await.unwrap();
+        fs::remove_file(&file).unwrap();
+
+        assert_eq!(1, loaded.len());
+        assert_eq!(
+            json!({
+                "port": "3000",
+                "registrations": [],
+                "cors": {
+                    "allowedOrigins": ["https://app.example.com"],
+                    "allowedMethods": ["GET"],
+                    "allowedHeaders": [],
+                    "exposedHeaders": [],
+                    "allowCredentials": false,
+                },
+                "rateLimit": {
+                    "strategy": "fixedWindow",
+                    "limit": 5,
+                    "windowSeconds": 60,
+                },
+            }),
+            serde_json::to_value(&loaded[0]).unwrap()
+        );
+        assert!(after_remove.is_empty());
+    }
+
+    #[test]
+    fn should_fail_for_unusable_file() {
+        let error = SqliteStorage::open(&PathBuf::from(
+            "/nonexistent/api-gen/stubs.db",
+        ))
+        .err()
+        .unwrap();
+
+        assert!(
+            error.starts_with("Failed to open `/nonexistent/api-gen/stubs.db`")
+        );
+    }
+}
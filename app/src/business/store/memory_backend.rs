@@ -0,0 +1,159 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use tokio::{sync::RwLock, time};
+use tracing::error;
+
+use crate::business::store::{
+    ReadOperation, StoreBackend, StoreFuture, WriteOperation,
+    store_error::StoreError,
+};
+
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Keeps the value in memory behind an async lock.
+///
+/// An operation that panics poisons the backend, since the value may have
+/// been left half changed, and every later operation fails.
+pub struct MemoryBackend<V> {
+    value: RwLock<V>,
+    poisoned: AtomicBool,
+    lock_timeout: Duration,
+}
+
+impl<V> MemoryBackend<V> {
+    pub fn new(value: V) -> Self {
+        Self {
+            value: RwLock::new(value),
+            poisoned: AtomicBool::new(false),
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
+        }
+    }
+
+    pub fn with_lock_timeout(mut self, lock_timeout: Duration) -> Self {
+        self.lock_timeout = lock_timeout;
+        self
+    }
+
+    fn check_poisoned(&self) -> Result<(), StoreError> {
+        match self.poisoned.load(Ordering::Acquire) {
+            true => Err(StoreError::Poisoned),
+            false => Ok(()),
+        }
+    }
+
+    fn contention(&self) -> StoreError {
+        let timeout = self.lock_timeout.as_millis();
+        error!(%timeout, "Failed to lock the state store within {timeout}ms.");
+
+        StoreError::Contention(self.lock_timeout)
+    }
+
+    fn run(&self, operation: impl FnOnce()) -> Result<(), StoreError> {
+        panic::catch_unwind(AssertUnwindSafe(operation)).map_err(|_| {
+            error!("A state store operation panicked, poisoning the store.");
+            self.poisoned.store(true, Ordering::Release);
+
+            StoreError::Poisoned
+        })
+    }
+}
+
+impl<V: Send + Sync> StoreBackend<V> for MemoryBackend<V> {
+    fn read<'a>(&'a self, operation: ReadOperation<'a, V>) -> StoreFuture<'a> {
+        Box::pin(async move {
+            self.check_poisoned()?;
+            let guard = time::timeout(self.lock_timeout, self.value.read())
+                .await
+                .map_err(|_| self.contention())?;
+
+            self.run(|| operation(&guard))
+        })
+    }
+
+    fn write<'a>(
+        &'a self,
+        operation: WriteOperation<'a, V>,
+    ) -> StoreFuture<'a> {
+        Box::pin(async move {
+            self.check_poisoned()?;
+            let mut guard =
+                time::timeout(self.lock_timeout, self.value.write())
+                    .await
+                    .map_err(|_| self.contention())?;
+
+            self.run(|| operation(&mut guard))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::business::store::{
+        Store, StoreBackend, store_error::StoreError,
+    };
+
+    use super::MemoryBackend;
+
+    async fn poison(store: &Store<i32>) {
+        let result = store
+            .write(|_| {
+                panic!("Poisoned the store explicitly.");
+            })
+            .await;
+
+        assert_eq!(Err(StoreError::Poisoned), result);
+    }
+
+    #[tokio::test]
+    async fn should_write_successfully() {
+        let store = Store::memory(1);
+
+        let result = store
+            .write(|value| {
+                *value = 100;
+                2 * *value
+            })
+            .await;
+
+        assert_eq!(Ok(200), result);
+        assert_eq!(Ok(100), store.read(|value| *value).await);
+    }
+
+    #[tokio::test]
+    async fn should_read_successfully() {
+        let store = Store::memory(1);
+
+        assert_eq!(Ok(2), store.read(|value| 2 * *value).await);
+    }
+
+    #[tokio::test]
+    async fn should_fail_every_operation_once_poisoned() {
+        let store = Store::memory(1);
+
+        poison(&store).await;
+
+        assert_eq!(Err(StoreError::Poisoned), store.read(|value| *value).await);
+        assert_eq!(
+            Err(StoreError::Poisoned),
+            store.write(|value| *value = 100).await
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_lock_is_held_too_long() {
+        let timeout = Duration::from_millis(50);
+        let backend = MemoryBackend::new(1).with_lock_timeout(timeout);
+        let _guard = backend.value.write().await;
+
+        assert_eq!(
+            Err(StoreError::Contention(timeout)),
+            backend.read(Box::new(|_| {})).await
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use crate::business::store::{
+    memory_backend::MemoryBackend, store_error::StoreError,
+};
+
+pub mod memory_backend;
+pub mod store_error;
+
+pub type StoreFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<(), StoreError>> + Send + 'a>>;
+
+pub type ReadOperation<'a, V> = Box<dyn FnOnce(&V) + Send + 'a>;
+pub type WriteOperation<'a, V> = Box<dyn FnOnce(&mut V) + Send + 'a>;
+
+/// Holds a value that is shared between requests.
+///
+/// Operations must run exactly once, or fail with a `StoreError` that tells
+/// the caller why the value couldn't be reached.
+pub trait StoreBackend<V>: Send + Sync {
+    fn read<'a>(&'a self, operation: ReadOperation<'a, V>) -> StoreFuture<'a>;
+
+    fn write<'a>(&'a self, operation: WriteOperation<'a, V>)
+    -> StoreFuture<'a>;
+}
+
+pub struct Store<V> {
+    backend: Arc<dyn StoreBackend<V>>,
+}
+
+impl<V> Clone for Store<V> {
+    fn clone(&self) -> Self {
+        Self {
+            backend: self.backend.clone(),
+        }
+    }
+}
+
+impl<V: Default + Send + Sync + 'static> Default for Store<V> {
+    fn default() -> Self {
+        Self::memory(V::default())
+    }
+}
+
+impl<V: Send + Sync + 'static> Store<V> {
+    pub fn new(backend: impl StoreBackend<V> + 'static) -> Self {
+        Self {
+            backend: Arc::new(backend),
+        }
+    }
+
+    pub fn memory(value: V) -> Self {
+        Self::new(MemoryBackend::new(value))
+    }
+
+    pub async fn read<R, F>(&self, operation: F) -> Result<R, StoreError>
+    where
+        R: Send,
+        F: FnOnce(&V) -> R + Send,
+    {
+        let mut result = None;
+        self.backend
+            .read(Box::new(|value| result = Some(operation(value))))
+            .await?;
+
+        result.ok_or_else(StoreError::skipped)
+    }
+
+    pub async fn write<R, F>(&self, operation: F) -> Result<R, StoreError>
+    where
+        R: Send,
+        F: FnOnce(&mut V) -> R + Send,
+    {
+        let mut result = None;
+        self.backend
+            .write(Box::new(|value| result = Some(operation(value))))
+            .await?;
+
+        result.ok_or_else(StoreError::skipped)
+    }
+}
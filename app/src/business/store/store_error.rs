@@ -0,0 +1,42 @@
+use std::{fmt, time::Duration};
+
+use crate::model::error::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoreError {
+    Poisoned,
+    Contention(Duration),
+    Backend(String),
+}
+
+impl StoreError {
+    pub fn skipped() -> Self {
+        Self::Backend(
+            "The store backend completed without running the operation"
+                .to_string(),
+        )
+    }
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Poisoned => write!(
+                f,
+                "The state store is poisoned, an earlier operation panicked while changing it"
+            ),
+            Self::Contention(timeout) => write!(
+                f,
+                "The state store is busy, it could not be locked within {}ms",
+                timeout.as_millis()
+            ),
+            Self::Backend(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl From<StoreError> for Error {
+    fn from(store_error: StoreError) -> Self {
+        Error::Store(store_error.to_string())
+    }
+}
@@ -0,0 +1,204 @@
+use reqwest::{Client, Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    client::client_error::{ClientError, FailureBody},
+    model::{
+        internal::server_registration::{Registration, ServerRegistration},
+        port::Port,
+        request::{
+            batch_registration_request::BatchRegistrationRequest,
+            page_request::PageRequest, peer_update_request::PeerUpdateRequest,
+            registration_request::RegistrationRequest,
+            route_filter::RouteFilter,
+        },
+        response::{
+            batch_registration_response::BatchRegistrationResponse, page::Page,
+            peer_state_response::PeerStateResponse,
+            peer_update_response::PeerUpdateResponse,
+            registration_response::RegistrationResponse,
+            reset_response::ResetResponse,
+        },
+    },
+    security::admin_auth::AdminAuth,
+};
+
+pub const DEFAULT_ADMIN_URL: &str = "http://localhost:8080";
+
+/// Talks to the admin API of a running api-gen instance.
+#[derive(Clone)]
+pub struct AdminClient {
+    base_url: String,
+    admin_auth: Option<AdminAuth>,
+    http: Client,
+}
+
+impl AdminClient {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            admin_auth: None,
+            http: Client::new(),
+        }
+    }
+
+    pub fn with_admin_auth(mut self, admin_auth: Option<AdminAuth>) -> Self {
+        self.admin_auth = admin_auth;
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub async fn register(
+        &self,
+        registration_request: &RegistrationRequest,
+    ) -> Result<RegistrationResponse, ClientError> {
+        self.send(
+            self.request(Method::POST, "/register")
+                .json(registration_request),
+        )
+        .await
+    }
+
+    pub async fn register_batch(
+        &self,
+        registrations: Vec<RegistrationRequest>,
+    ) -> Result<BatchRegistrationResponse, ClientError> {
+        self.send(
+            self.request(Method::POST, "/register/batch")
+                .json(&BatchRegistrationRequest { registrations }),
+        )
+        .await
+    }
+
+    pub async fn list(&self) -> Result<Vec<ServerRegistration>, ClientError> {
+        self.send(self.request(Method::GET, "/info")).await
+    }
+
+    pub async fn server(
+        &self,
+        port: Port,
+    ) -> Result<ServerRegistration, ClientError> {
+        self.send(self.request(Method::GET, &format!("/servers/{port}")))
+            .await
+    }
+
+    pub async fn routes(
+        &self,
+        port: Port,
+        filter: &RouteFilter,
+        page: PageRequest,
+    ) -> Result<Page<Registration>, ClientError> {
+        self.send(
+            self.request(Method::GET, &format!("/servers/{port}/routes"))
+                .query(filter)
+                .query(&page),
+        )
+        .await
+    }
+
+    pub async fn remove(
+        &self,
+        port: Port,
+    ) -> Result<ServerRegistration, ClientError> {
+        self.send(self.request(Method::DELETE, &format!("/servers/{port}")))
+            .await
+    }
+
+    pub async fn reset(
+        &self,
+        keep_pinned: bool,
+    ) -> Result<ResetResponse, ClientError> {
+        self.send(
+            self.request(
+                Method::POST,
+                &format!("/reset?keepPinned={keep_pinned}"),
+            ),
+        )
+        .await
+    }
+
+    /// Hands a change made on another instance to this one.
+    pub async fn sync(
+        &self,
+        port: Port,
+        update: &PeerUpdateRequest,
+    ) -> Result<PeerUpdateResponse, ClientError> {
+        self.send(
+            self.request(Method::POST, &format!("/sync/{port}"))
+                .json(update),
+        )
+        .await
+    }
+
+    /// The latest change to every port the instance has synced.
+    pub async fn peer_state(
+        &self,
+    ) -> Result<Vec<PeerStateResponse>, ClientError> {
+        self.send(self.request(Method::GET, "/sync")).await
+    }
+
+    /// Every HTTP route as a registration that can be registered again.
+    pub async fn export(
+        &self,
+    ) -> Result<BatchRegistrationRequest, ClientError> {
+        let registrations = self
+            .list()
+            .await?
+            .into_iter()
+            .flat_map(ServerRegistration::into_registration_requests)
+            .collect();
+
+        Ok(BatchRegistrationRequest { registrations })
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self
+            .http
+            .request(method, format!("{}{path}", self.base_url));
+
+        match &self.admin_auth {
+            Some(admin_auth) => request.header(
+                reqwest::header::AUTHORIZATION,
+                admin_auth.authorization(),
+            ),
+            None => request,
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T, ClientError> {
+        let response = request.send().await?;
+
+        if response.status().is_success() {
+            return Ok(response.json().await?);
+        }
+
+        Err(Self::failure(response).await)
+    }
+
+    async fn failure(response: Response) -> ClientError {
+        let status = response.status().as_u16();
+        let body = response.text().await.unwrap_or_default();
+
+        match serde_json::from_str::<FailureBody>(&body) {
+            Ok(FailureBody {
+                failure_type,
+                failure_message,
+            }) => ClientError::Api {
+                status,
+                failure_type,
+                failure_message,
+            },
+            Err(_) => ClientError::Api {
+                status,
+                failure_type: "Http".to_string(),
+                failure_message: body,
+            },
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use tokio::{net::TcpListener, runtime::Handle, task::JoinHandle};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    app,
+    business::{
+        app_state::AppState,
+        server::connection_establisher::{
+            DEFAULT_DRAIN_TIMEOUT, TcpConnectionEstablisher, serve_with_drain,
+        },
+    },
+    client::{
+        admin_client::AdminClient, client_error::ClientError, stub::Stub,
+    },
+    model::{
+        request::registration_request::RegistrationRequest,
+        response::reset_response::ResetResponse,
+    },
+};
+
+/// An api-gen instance running inside the current tokio runtime.
+///
+/// The admin API listens on an ephemeral loopback port and stub servers are
+/// bound like they are by the binary. Dropping the instance stops it without
+/// waiting, [`ApiGen::shutdown`] waits until every port is released.
+pub struct ApiGen {
+    client: AdminClient,
+    app_state: Arc<AppState<TcpConnectionEstablisher>>,
+    shutdown: CancellationToken,
+    handle: JoinHandle<()>,
+}
+
+impl ApiGen {
+    pub async fn start() -> Result<Self, ClientError> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+
+        let app_state =
+            Arc::new(AppState::new(TcpConnectionEstablisher::default()));
+        let router = app(&address.port().to_string(), app_state.clone());
+
+        let shutdown = CancellationToken::new();
+        let handle = tokio::spawn(serve_with_drain(
+            listener,
+            router,
+            shutdown.clone(),
+            DEFAULT_DRAIN_TIMEOUT,
+        ));
+
+        Ok(Self {
+            client: AdminClient::new(&format!("http://{address}")),
+            app_state,
+            shutdown,
+            handle,
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        self.client.base_url()
+    }
+
+    pub fn client(&self) -> &AdminClient {
+        &self.client
+    }
+
+    pub async fn register(
+        &self,
+        registration_request: RegistrationRequest,
+    ) -> Result<Stub, ClientError> {
+        let response = self.client.register(&registration_request).await?;
+
+        Ok(Stub::new(
+            response.port,
+            response.added.method,
+            response.added.path,
+            self.client.clone(),
+        ))
+    }
+
+    pub async fn reset(&self) -> Result<ResetResponse, ClientError> {
+        self.client.reset(true).await
+    }
+
+    /// Stops every stub server, then the admin API.
+    pub async fn shutdown(mut self) {
+        let _ = self.app_state.shutdown_servers().await;
+        self.shutdown.cancel();
+        let _ = (&mut self.handle).await;
+    }
+}
+
+impl Drop for ApiGen {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
+
+        if let Ok(runtime) = Handle::try_current() {
+            let app_state = self.app_state.clone();
+            runtime.spawn(async move {
+                let _ = app_state.shutdown_servers().await;
+            });
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::{fmt, io};
+
+use serde::Deserialize;
+
+/// A failure talking to the admin API, either on the wire or reported by
+/// the server in its standard error shape.
+#[derive(Debug)]
+pub enum ClientError {
+    Transport(String),
+    Api {
+        status: u16,
+        failure_type: String,
+        failure_message: String,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct FailureBody {
+    pub failure_type: String,
+    pub failure_message: String,
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(message) => formatter.write_str(message),
+            Self::Api {
+                status,
+                failure_type,
+                failure_message,
+            } => write!(
+                formatter,
+                "{failure_type} ({status}): {failure_message}"
+            ),
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Transport(format!("Failed to reach the admin API, {err}"))
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(err: io::Error) -> Self {
+        Self::Transport(format!("Failed to start the admin API, {err}"))
+    }
+}
+
+impl From<ClientError> for String {
+    fn from(err: ClientError) -> Self {
+        err.to_string()
+    }
+}
@@ -0,0 +1,5 @@
+pub mod admin_client;
+pub mod api_gen;
+pub mod client_error;
+pub mod registration_builder;
+pub mod stub;
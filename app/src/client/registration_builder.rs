@@ -0,0 +1,89 @@
+use serde_json::Value;
+
+use crate::model::{
+    graphql_operation::GraphQlOperation, http_method::HttpMethod, port::Port,
+    rate_limit::RateLimit, request::registration_request::RegistrationRequest,
+    stub_auth::StubAuth,
+};
+
+/// Assembles a [`RegistrationRequest`], validating its path on `build`.
+pub struct RegistrationBuilder {
+    port: Port,
+    method: HttpMethod,
+    path: String,
+    graphql: Option<GraphQlOperation>,
+    auth: Option<StubAuth>,
+    rate_limit: Option<RateLimit>,
+    response: Value,
+}
+
+impl RegistrationBuilder {
+    pub fn new(method: HttpMethod, path: &str) -> Self {
+        Self {
+            port: Port::EPHEMERAL,
+            method,
+            path: path.to_string(),
+            graphql: None,
+            auth: None,
+            rate_limit: None,
+            response: Value::Null,
+        }
+    }
+
+    pub fn get(path: &str) -> Self {
+        Self::new(HttpMethod::Get, path)
+    }
+
+    pub fn post(path: &str) -> Self {
+        Self::new(HttpMethod::Post, path)
+    }
+
+    pub fn put(path: &str) -> Self {
+        Self::new(HttpMethod::Put, path)
+    }
+
+    pub fn patch(path: &str) -> Self {
+        Self::new(HttpMethod::Patch, path)
+    }
+
+    pub fn delete(path: &str) -> Self {
+        Self::new(HttpMethod::Delete, path)
+    }
+
+    pub fn with_port(mut self, port: Port) -> Self {
+        self.port = port;
+        self
+    }
+
+    pub fn with_graphql(mut self, graphql: GraphQlOperation) -> Self {
+        self.graphql = Some(graphql);
+        self
+    }
+
+    pub fn with_auth(mut self, auth: StubAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    pub fn with_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    pub fn with_response(mut self, response: Value) -> Self {
+        self.response = response;
+        self
+    }
+
+    pub fn build(self) -> Result<RegistrationRequest, String> {
+        Ok(RegistrationRequest {
+            port: self.port,
+            path: self.path.parse()?,
+            method: self.method,
+            graphql: self.graphql,
+            auth: self.auth,
+            rate_limit: self.rate_limit,
+            response: self.response,
+        })
+    }
+}
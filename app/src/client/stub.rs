@@ -0,0 +1,83 @@
+use crate::{
+    client::{admin_client::AdminClient, client_error::ClientError},
+    model::{
+        http_method::HttpMethod,
+        internal::server_registration::ServerRegistration,
+        port::Port,
+        request::{page_request::PageRequest, route_filter::RouteFilter},
+        route_path::RoutePath,
+    },
+};
+
+/// A registered route, bound to the port its server actually listens on.
+pub struct Stub {
+    port: Port,
+    method: HttpMethod,
+    path: RoutePath,
+    client: AdminClient,
+}
+
+impl Stub {
+    pub(super) fn new(
+        port: Port,
+        method: HttpMethod,
+        path: RoutePath,
+        client: AdminClient,
+    ) -> Self {
+        Self {
+            port,
+            method,
+            path,
+            client,
+        }
+    }
+
+    pub fn port(&self) -> Port {
+        self.port
+    }
+
+    pub fn method(&self) -> &HttpMethod {
+        &self.method
+    }
+
+    pub fn path(&self) -> &RoutePath {
+        &self.path
+    }
+
+    /// The address of the stub server, e.g. `http://127.0.0.1:49152`.
+    pub fn base_url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+
+    /// The address of the route itself, captures are left as registered.
+    pub fn url(&self) -> String {
+        format!("{}{}", self.base_url(), self.path)
+    }
+
+    /// Whether the route is still served, a later registration or a reset
+    /// may have replaced or removed it.
+    pub async fn is_registered(&self) -> Result<bool, ClientError> {
+        let filter = RouteFilter {
+            method: Some(self.method.clone()),
+            path: Some(self.path.to_string()),
+        };
+        let page = PageRequest {
+            offset: 0,
+            limit: 1,
+        };
+
+        let routes = match self.client.routes(self.port, &filter, page).await {
+            Ok(routes) => routes,
+            Err(ClientError::Api { status: 404, .. }) => return Ok(false),
+            Err(err) => return Err(err),
+        };
+
+        Ok(routes.items.iter().any(|route| route.path == self.path))
+    }
+
+    /// Stops the whole server on the stub's port, along with every other
+    /// route registered on it.
+    pub async fn remove(self) -> Result<ServerRegistration, ClientError> {
+        self.client.remove(self.port).await
+    }
+}
@@ -0,0 +1,656 @@
+use std::{
+    fs,
+    net::{IpAddr, Ipv4Addr},
+    ops::RangeInclusive,
+    time::Duration,
+};
+
+use tracing_subscriber::EnvFilter;
+
+use crate::{
+    business::{
+        server::{
+            connection_establisher::DEFAULT_DRAIN_TIMEOUT,
+            path_matching::PathMatching,
+        },
+        storage::Storage,
+    },
+    logging::setup::LogFormat,
+    model::{
+        port::Port,
+        request::{
+            batch_registration_request::BatchRegistrationRequest,
+            registration_request::RegistrationRequest,
+        },
+    },
+    security::admin_auth::AdminAuth,
+    util::arguments::Arguments,
+};
+
+const DEFAULT_PORT: Port = Port::new(8080);
+const DEFAULT_BIND_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+const DEFAULT_LOG_LEVEL: &str = "info";
+
+const PORT_OPTION: &str = "port";
+const BIND_ADDRESS_OPTION: &str = "bind-address";
+const ADMIN_TOKEN_OPTION: &str = "admin-token";
+const ADMIN_BASIC_AUTH_OPTION: &str = "admin-basic-auth";
+const LOG_FORMAT_OPTION: &str = "log-format";
+const LOG_LEVEL_OPTION: &str = "log-level";
+const OTLP_ENDPOINT_OPTION: &str = "otlp-endpoint";
+const REGISTRATIONS_OPTION: &str = "registrations";
+const DRAIN_TIMEOUT_OPTION: &str = "drain-timeout";
+const PORT_RANGE_OPTION: &str = "port-range";
+const PATH_MATCHING_OPTION: &str = "path-matching";
+const STORAGE_OPTION: &str = "storage";
+const PEERS_OPTION: &str = "peers";
+const INSTANCE_ID_OPTION: &str = "instance-id";
+
+const OPTIONS: &[&str] = &[
+    PORT_OPTION,
+    BIND_ADDRESS_OPTION,
+    ADMIN_TOKEN_OPTION,
+    ADMIN_BASIC_AUTH_OPTION,
+    LOG_FORMAT_OPTION,
+    LOG_LEVEL_OPTION,
+    OTLP_ENDPOINT_OPTION,
+    REGISTRATIONS_OPTION,
+    DRAIN_TIMEOUT_OPTION,
+    PORT_RANGE_OPTION,
+    PATH_MATCHING_OPTION,
+    STORAGE_OPTION,
+    PEERS_OPTION,
+    INSTANCE_ID_OPTION,
+];
+
+const PORT_ENV_VAR: &str = "API_GEN_PORT";
+const BIND_ADDRESS_ENV_VAR: &str = "API_GEN_BIND_ADDRESS";
+const ADMIN_TOKEN_ENV_VAR: &str = "API_GEN_ADMIN_TOKEN";
+const ADMIN_BASIC_AUTH_ENV_VAR: &str = "API_GEN_ADMIN_BASIC_AUTH";
+const LOG_FORMAT_ENV_VAR: &str = "API_GEN_LOG_FORMAT";
+const LOG_LEVEL_ENV_VAR: &str = "RUST_LOG";
+const OTLP_ENDPOINT_ENV_VAR: &str = "API_GEN_OTLP_ENDPOINT";
+const REGISTRATIONS_ENV_VAR: &str = "API_GEN_REGISTRATIONS";
+const DRAIN_TIMEOUT_ENV_VAR: &str = "API_GEN_DRAIN_TIMEOUT";
+const PORT_RANGE_ENV_VAR: &str = "API_GEN_PORT_RANGE";
+const PATH_MATCHING_ENV_VAR: &str = "API_GEN_PATH_MATCHING";
+const STORAGE_ENV_VAR: &str = "API_GEN_STORAGE";
+const PEERS_ENV_VAR: &str = "API_GEN_PEERS";
+const INSTANCE_ID_ENV_VAR: &str = "API_GEN_INSTANCE_ID";
+
+#[derive(Debug)]
+pub struct Config {
+    pub port: Port,
+    /// Where the admin API and the stub servers listen, distinct loopback
+    /// addresses let several instances share one host.
+    pub bind_address: IpAddr,
+    pub admin_auth: Option<AdminAuth>,
+    pub log_format: LogFormat,
+    pub log_level: String,
+    pub otlp_endpoint: Option<String>,
+    pub registrations_file: Option<String>,
+    pub drain_timeout: Duration,
+    pub port_range: Option<RangeInclusive<u16>>,
+    pub path_matching: PathMatching,
+    pub storage: Storage,
+    pub peers: Vec<String>,
+    pub instance_id: Option<String>,
+}
+
+impl Config {
+    pub fn parse<E>(args: &[String], env: E) -> Result<Self, String>
+    where
+        E: Fn(&str) -> Option<String>,
+    {
+        let arguments = Arguments::parse(args, OPTIONS, &[])?;
+
+        if let Some(argument) = arguments.positionals.first() {
+            return Err(format!("Unexpected argument `{argument}`"));
+        }
+
+        let lookup = |option: &str, env_var: &str| {
+            arguments
+                .options
+                .get(option)
+                .cloned()
+                .or_else(|| env(env_var))
+        };
+
+        let port = lookup(PORT_OPTION, PORT_ENV_VAR)
+            .map(|port| port.parse::<Port>())
+            .transpose()
+            .map_err(|err| format!("Invalid port, {err}"))?
+            .unwrap_or(DEFAULT_PORT);
+
+        let bind_address = lookup(BIND_ADDRESS_OPTION, BIND_ADDRESS_ENV_VAR)
+            .map(|bind_address| {
+                bind_address.parse::<IpAddr>().map_err(|_| {
+                    format!(
+                        "Invalid bind address `{bind_address}`, expected an IP address"
+                    )
+                })
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_BIND_ADDRESS);
+
+        let admin_token = lookup(ADMIN_TOKEN_OPTION, ADMIN_TOKEN_ENV_VAR);
+        let admin_basic_auth =
+            lookup(ADMIN_BASIC_AUTH_OPTION, ADMIN_BASIC_AUTH_ENV_VAR);
+
+        let admin_auth = match (admin_token, admin_basic_auth) {
+            (Some(_), Some(_)) => {
+                return Err(format!(
+                    "Only one of `--{ADMIN_TOKEN_OPTION}` and `--{ADMIN_BASIC_AUTH_OPTION}` can be configured"
+                ));
+            }
+            (Some(token), None) => Some(AdminAuth::bearer(&token)?),
+            (None, Some(credentials)) => Some(AdminAuth::basic(&credentials)?),
+            (None, None) => None,
+        };
+
+        let log_format = lookup(LOG_FORMAT_OPTION, LOG_FORMAT_ENV_VAR)
+            .map(|format| format.parse())
+            .transpose()?
+            .unwrap_or_default();
+
+        let log_level = lookup(LOG_LEVEL_OPTION, LOG_LEVEL_ENV_VAR)
+            .unwrap_or(DEFAULT_LOG_LEVEL.to_string());
+        EnvFilter::try_new(&log_level)
+            .map_err(|err| format!("Invalid log level `{log_level}`, {err}"))?;
+
+        let otlp_endpoint = lookup(OTLP_ENDPOINT_OPTION, OTLP_ENDPOINT_ENV_VAR);
+        let registrations_file =
+            lookup(REGISTRATIONS_OPTION, REGISTRATIONS_ENV_VAR);
+
+        let drain_timeout = match lookup(DRAIN_TIMEOUT_OPTION, DRAIN_TIMEOUT_ENV_VAR)
+        {
+            Some(seconds) => seconds
+                .parse::<f64>()
+                .ok()
+                .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                .ok_or(format!(
+                    "Invalid drain timeout `{seconds}`, expected a number of seconds"
+                ))?,
+            None => DEFAULT_DRAIN_TIMEOUT,
+        };
+
+        let port_range = lookup(PORT_RANGE_OPTION, PORT_RANGE_ENV_VAR)
+            .map(|port_range| parse_port_range(&port_range))
+            .transpose()?;
+
+        let path_matching = lookup(PATH_MATCHING_OPTION, PATH_MATCHING_ENV_VAR)
+            .map(|path_matching| path_matching.parse())
+            .transpose()?
+            .unwrap_or_default();
+
+        let storage = lookup(STORAGE_OPTION, STORAGE_ENV_VAR)
+            .map(|storage| storage.parse())
+            .transpose()?
+            .unwrap_or_default();
+
+        let peers = lookup(PEERS_OPTION, PEERS_ENV_VAR)
+            .map(|peers| parse_peers(&peers))
+            .transpose()?
+            .unwrap_or_default();
+        let instance_id = lookup(INSTANCE_ID_OPTION, INSTANCE_ID_ENV_VAR);
+
+        Ok(Self {
+            port,
+            bind_address,
+            admin_auth,
+            log_format,
+            log_level,
+            otlp_endpoint,
+            registrations_file,
+            drain_timeout,
+            port_range,
+            path_matching,
+            storage,
+            peers,
+            instance_id,
+        })
+    }
+
+    /// Reads the registrations pinned at startup, these survive `/reset`.
+    pub fn load_pinned_registrations(
+        &self,
+    ) -> Result<Vec<RegistrationRequest>, String> {
+        let Some(registrations_file) = &self.registrations_file else {
+            return Ok(vec![]);
+        };
+
+        let content =
+            fs::read_to_string(registrations_file).map_err(|err| {
+                format!("Failed to read `{registrations_file}`, {err}")
+            })?;
+
+        serde_json::from_str::<BatchRegistrationRequest>(&content)
+            .map(|batch_request| batch_request.registrations)
+            .map_err(|err| {
+                format!("Failed to parse `{registrations_file}`, {err}")
+            })
+    }
+}
+
+fn parse_port_range(port_range: &str) -> Result<RangeInclusive<u16>, String> {
+    let invalid = || {
+        format!(
+            "Invalid port range `{port_range}`, expected `<start>-<end>` within 1-65535"
+        )
+    };
+
+    let (start, end) = port_range.split_once('-').ok_or_else(invalid)?;
+    let start = start.trim().parse::<u16>().map_err(|_| invalid())?;
+    let end = end.trim().parse::<u16>().map_err(|_| invalid())?;
+
+    if start == 0 || start > end {
+        return Err(invalid());
+    }
+
+    Ok(start..=end)
+}
+
+/// Reads the comma separated admin URLs of the peers to sync with.
+fn parse_peers(peers: &str) -> Result<Vec<String>, String> {
+    peers
+        .split(',')
+        .map(str::trim)
+        .filter(|peer| !peer.is_empty())
+        .map(|peer| {
+            if peer.starts_with("http://") || peer.starts_with("https://") {
+                Ok(peer.to_string())
+            } else {
+                Err(format!(
+                    "Invalid peer `{peer}`, expected the URL of its admin API"
+                ))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap, env, fs, path::PathBuf, process, time::Duration,
+    };
+
+    use crate::{
+        business::{server::path_matching::PathMatching, storage::Storage},
+        logging::setup::LogFormat,
+        model::port::Port,
+        security::admin_auth::AdminAuth,
+    };
+
+    use super::Config;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn should_use_defaults_without_configuration() {
+        let config = Config::parse(&[], env(&[])).unwrap();
+
+        assert_eq!(Port::new(8080), config.port);
+        assert_eq!("0.0.0.0", config.bind_address.to_string());
+        assert_eq!(None, config.admin_auth);
+        assert_eq!(LogFormat::Compact, config.log_format);
+        assert_eq!("info", config.log_level);
+        assert_eq!(None, config.otlp_endpoint);
+        assert_eq!(Duration::from_secs(10), config.drain_timeout);
+        assert_eq!(None, config.port_range);
+        assert_eq!(PathMatching::CaseSensitive, config.path_matching);
+        assert_eq!(Storage::Memory, config.storage);
+        assert!(config.peers.is_empty());
+        assert_eq!(None, config.instance_id);
+    }
+
+    #[test]
+    fn should_prefer_arguments_over_environment() {
+        let config = Config::parse(
+            &args(&["--port", "9090", "--admin-token", "from-args"]),
+            env(&[
+                ("API_GEN_PORT", "7070"),
+                ("API_GEN_ADMIN_TOKEN", "from-env"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(Port::new(9090), config.port);
+        assert_eq!(
+            Some(AdminAuth::Bearer("from-args".to_string())),
+            config.admin_auth
+        );
+    }
+
+    #[test]
+    fn should_read_basic_auth_from_environment() {
+        let config = Config::parse(
+            &[],
+            env(&[("API_GEN_ADMIN_BASIC_AUTH", "admin:secret")]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(AdminAuth::Basic {
+                username: "admin".to_string(),
+                password: "secret".to_string(),
+            }),
+            config.admin_auth
+        );
+    }
+
+    #[test]
+    fn should_redact_admin_credentials_in_debug_output() {
+        for option in [
+            ["--admin-token", "secret"],
+            ["--admin-basic-auth", "admin:secret"],
+        ] {
+            let config = Config::parse(&args(&option), env(&[])).unwrap();
+
+            assert!(!format!("{config:?}").contains("secret"));
+        }
+    }
+
+    #[test]
+    fn should_fail_for_conflicting_admin_auth() {
+        let config = Config::parse(
+            &args(&["--admin-token", "token"]),
+            env(&[("API_GEN_ADMIN_BASIC_AUTH", "admin:secret")]),
+        );
+
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn should_fail_for_malformed_basic_auth() {
+        let config =
+            Config::parse(&args(&["--admin-basic-auth", "admin"]), env(&[]));
+
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn should_fail_for_empty_admin_credentials() {
+        for (option, value, error) in [
+            ("--admin-token", "", "The admin token must not be empty"),
+            ("--admin-token", "  ", "The admin token must not be empty"),
+            (
+                "--admin-basic-auth",
+                "admin:",
+                "Basic auth credentials must be of the form `username:password`",
+            ),
+        ] {
+            let config = Config::parse(&args(&[option, value]), env(&[]));
+
+            assert_eq!(Some(error.to_string()), config.err());
+        }
+
+        let config = Config::parse(&[], env(&[("API_GEN_ADMIN_TOKEN", "")]));
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn should_fail_for_unknown_option() {
+        let config = Config::parse(
+            &args(&["--port", "8081", "--prot", "8082"]),
+            env(&[]),
+        );
+
+        assert_eq!(Some("Unknown option `--prot`".to_string()), config.err());
+    }
+
+    #[test]
+    fn should_fail_for_unknown_flag() {
+        let config = Config::parse(&args(&["--verbose"]), env(&[]));
+
+        assert_eq!(
+            Some("Unknown option `--verbose`".to_string()),
+            config.err()
+        );
+    }
+
+    #[test]
+    fn should_fail_for_unexpected_argument() {
+        let config = Config::parse(&args(&["8081"]), env(&[]));
+
+        assert_eq!(
+            Some("Unexpected argument `8081`".to_string()),
+            config.err()
+        );
+    }
+
+    #[test]
+    fn should_fail_for_option_without_value() {
+        let config = Config::parse(&args(&["--admin-token"]), env(&[]));
+
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn should_read_logging_configuration() {
+        let config = Config::parse(
+            &args(&["--log-format", "JSON", "--log-level", "api_gen=debug"]),
+            env(&[("API_GEN_OTLP_ENDPOINT", "http://localhost:4318")]),
+        )
+        .unwrap();
+
+        assert_eq!(LogFormat::Json, config.log_format);
+        assert_eq!("api_gen=debug", config.log_level);
+        assert_eq!(
+            Some("http://localhost:4318".to_string()),
+            config.otlp_endpoint
+        );
+    }
+
+    #[test]
+    fn should_fail_for_unknown_log_format() {
+        let config = Config::parse(&args(&["--log-format", "xml"]), env(&[]));
+
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn should_fail_for_invalid_log_level() {
+        let config = Config::parse(&[], env(&[("RUST_LOG", "api_gen=loud")]));
+
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn should_load_pinned_registrations() {
+        let registrations_file = env::temp_dir()
+            .join(format!("api-gen-pinned-{}.json", process::id()));
+        fs::write(
+            &registrations_file,
+            r#"{"registrations": [{"port": "3000", "method": "GET", "path": "/health", "response": "UP"}]}"#,
+        )
+        .unwrap();
+
+        let config = Config::parse(
+            &args(&["--registrations", registrations_file.to_str().unwrap()]),
+            env(&[]),
+        )
+        .unwrap();
+        let pinned_registrations = config.load_pinned_registrations();
+        fs::remove_file(registrations_file).unwrap();
+
+        let pinned_registrations = pinned_registrations.unwrap();
+        assert_eq!(1, pinned_registrations.len());
+        assert_eq!(Port::new(3000), pinned_registrations[0].port);
+        assert_eq!("/health", &*pinned_registrations[0].path);
+    }
+
+    #[test]
+    fn should_fail_for_missing_registrations_file() {
+        let config = Config::parse(
+            &args(&["--registrations", "/nonexistent/registrations.json"]),
+            env(&[]),
+        )
+        .unwrap();
+
+        assert!(config.load_pinned_registrations().is_err());
+    }
+
+    #[test]
+    fn should_read_drain_timeout() {
+        let config =
+            Config::parse(&args(&["--drain-timeout", "2.5"]), env(&[]))
+                .unwrap();
+
+        assert_eq!(Duration::from_millis(2500), config.drain_timeout);
+    }
+
+    #[test]
+    fn should_fail_for_invalid_drain_timeout() {
+        let config =
+            Config::parse(&[], env(&[("API_GEN_DRAIN_TIMEOUT", "-1")]));
+
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn should_read_port_range() {
+        let config =
+            Config::parse(&[], env(&[("API_GEN_PORT_RANGE", "20000-20099")]))
+                .unwrap();
+
+        assert_eq!(Some(20000..=20099), config.port_range);
+    }
+
+    #[test]
+    fn should_fail_for_invalid_port() {
+        let config = Config::parse(&args(&["--port", "80800"]), env(&[]));
+
+        assert_eq!(
+            "Invalid port, `80800` is not a port, expected a number between 1 and 65535",
+            config.unwrap_err()
+        );
+    }
+
+    #[test]
+    fn should_fail_for_invalid_port_range() {
+        for port_range in ["20000", "0-100", "200-100", "1-70000"] {
+            let config =
+                Config::parse(&args(&["--port-range", port_range]), env(&[]));
+
+            assert!(config.is_err(), "`{port_range}` should be rejected");
+        }
+    }
+
+    #[test]
+    fn should_read_bind_address() {
+        let config = Config::parse(
+            &args(&["--bind-address", "127.0.0.2"]),
+            env(&[("API_GEN_BIND_ADDRESS", "::1")]),
+        )
+        .unwrap();
+
+        assert_eq!("127.0.0.2", config.bind_address.to_string());
+    }
+
+    #[test]
+    fn should_fail_for_invalid_bind_address() {
+        let error =
+            Config::parse(&args(&["--bind-address", "localhost"]), env(&[]))
+                .unwrap_err();
+
+        assert_eq!(
+            "Invalid bind address `localhost`, expected an IP address",
+            error
+        );
+    }
+
+    #[test]
+    fn should_read_path_matching() {
+        let config = Config::parse(
+            &args(&["--path-matching", "case-insensitive"]),
+            env(&[]),
+        )
+        .unwrap();
+
+        assert_eq!(PathMatching::CaseInsensitive, config.path_matching);
+    }
+
+    #[test]
+    fn should_fail_for_unknown_path_matching() {
+        let error =
+            Config::parse(&[], env(&[("API_GEN_PATH_MATCHING", "fuzzy")]))
+                .unwrap_err();
+
+        assert_eq!(
+            "Unknown path matching `fuzzy`, expected one of `case-sensitive` or `case-insensitive`",
+            error
+        );
+    }
+
+    #[test]
+    fn should_read_storage() {
+        let config = Config::parse(
+            &[],
+            env(&[("API_GEN_STORAGE", "sqlite:/var/lib/api-gen/stubs.db")]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Storage::Sqlite(PathBuf::from("/var/lib/api-gen/stubs.db")),
+            config.storage
+        );
+    }
+
+    #[test]
+    fn should_fail_for_unknown_storage() {
+        for storage in ["redis", "sqlite:"] {
+            let error = Config::parse(&args(&["--storage", storage]), env(&[]))
+                .unwrap_err();
+
+            assert_eq!(
+                format!(
+                    "Unknown storage `{storage}`, expected one of `memory` or `sqlite:<file>`"
+                ),
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn should_read_peers() {
+        let config = Config::parse(
+            &args(&["--instance-id", "replica-1"]),
+            env(&[(
+                "API_GEN_PEERS",
+                "http://localhost:8081, https://replica-2:8080/,",
+            )]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec!["http://localhost:8081", "https://replica-2:8080/"],
+            config.peers
+        );
+        assert_eq!(Some("replica-1".to_string()), config.instance_id);
+    }
+
+    #[test]
+    fn should_fail_for_invalid_peer() {
+        let error = Config::parse(
+            &args(&["--peers", "http://localhost:8081,localhost:8082"]),
+            env(&[]),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            "Invalid peer `localhost:8082`, expected the URL of its admin API",
+            error
+        );
+    }
+}
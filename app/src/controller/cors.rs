@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode};
+use tracing::{Instrument, info_span};
+
+use crate::{
+    business::{
+        app_state::AppState,
+        server::{
+            connection_establisher::ConnectionEstablisher,
+            cors::{CorsPolicy, CorsPolicyUpdate},
+        },
+        server_update::update_server,
+    },
+    controller::registration_failure,
+    model::{
+        internal::request_json::RequestJson,
+        request::cors_registration_request::CorsRegistrationRequest,
+        response::{
+            cors_registration_response::CorsRegistrationResponse,
+            http_response::HttpResponse,
+        },
+    },
+};
+
+pub async fn register_cors_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestJson(registration_request): RequestJson<CorsRegistrationRequest>,
+) -> HttpResponse<CorsRegistrationResponse> {
+    let span = info_span!("[Controller: Register CORS Policy]");
+
+    async move {
+        let port = registration_request.port;
+
+        let _port_lock = match app_state.lock_port(port).await {
+            Ok(port_lock) => port_lock,
+            Err(err) => return registration_failure(err.into()),
+        };
+
+        let policy = match CorsPolicy::new(registration_request.clone()) {
+            Ok(policy) => policy,
+            Err(err) => {
+                return registration_failure(err);
+            }
+        };
+
+        let registration_to_be_removed =
+            match app_state.get_cors_policy(port).await {
+                Ok(policy) => policy.map(|policy| policy.get_registration()),
+                Err(err) => return registration_failure(err.into()),
+            };
+
+        match update_server(
+            &app_state,
+            port,
+            CorsPolicyUpdate::new(port, policy),
+        )
+        .await
+        {
+            Ok(_) => {
+                let response = CorsRegistrationResponse::new(
+                    registration_request,
+                    registration_to_be_removed,
+                );
+                HttpResponse::success(StatusCode::OK, response)
+            }
+            Err(err) => registration_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
@@ -0,0 +1,32 @@
+use axum::response::{Html, IntoResponse, Response};
+use http::{HeaderValue, header::CONTENT_TYPE};
+
+const INDEX_HTML: &str = include_str!("../../assets/dashboard/index.html");
+const DASHBOARD_JS: &str = include_str!("../../assets/dashboard/dashboard.js");
+const DASHBOARD_CSS: &str =
+    include_str!("../../assets/dashboard/dashboard.css");
+
+const JAVASCRIPT_CONTENT_TYPE: &str = "text/javascript; charset=utf-8";
+const CSS_CONTENT_TYPE: &str = "text/css; charset=utf-8";
+
+/// The dashboard page, its data is loaded from the admin API.
+pub async fn dashboard_controller() -> Html<&'static str> {
+    Html(INDEX_HTML)
+}
+
+pub async fn dashboard_script_controller() -> Response {
+    asset(DASHBOARD_JS, JAVASCRIPT_CONTENT_TYPE)
+}
+
+pub async fn dashboard_style_controller() -> Response {
+    asset(DASHBOARD_CSS, CSS_CONTENT_TYPE)
+}
+
+fn asset(content: &'static str, content_type: &'static str) -> Response {
+    let mut response = content.into_response();
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+
+    response
+}
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode};
+use tracing::{Instrument, info_span};
+
+use crate::{
+    business::{
+        app_state::AppState,
+        server::{
+            connection_establisher::ConnectionEstablisher,
+            grpc::GrpcRegistryUpdate,
+        },
+        server_update::update_server,
+    },
+    controller::registration_failure,
+    model::{
+        internal::request_json::RequestJson,
+        request::{
+            grpc_descriptor_request::GrpcDescriptorRequest,
+            grpc_registration_request::GrpcRegistrationRequest,
+        },
+        response::{
+            grpc_descriptor_response::GrpcDescriptorResponse,
+            grpc_registration_response::GrpcRegistrationResponse,
+            http_response::HttpResponse,
+        },
+    },
+};
+
+pub async fn register_grpc_descriptor_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestJson(descriptor_request): RequestJson<GrpcDescriptorRequest>,
+) -> HttpResponse<GrpcDescriptorResponse> {
+    let span = info_span!("[Controller: Register gRPC Descriptor]");
+
+    async move {
+        let port = descriptor_request.port;
+
+        let _port_lock = match app_state.lock_port(port).await {
+            Ok(port_lock) => port_lock,
+            Err(err) => return registration_failure(err.into()),
+        };
+
+        let mut registry = match app_state.get_grpc_registry(port).await {
+            Ok(registry) => registry,
+            Err(err) => return registration_failure(err.into()),
+        };
+        let services = match registry
+            .add_descriptor_set(&descriptor_request.descriptor_set)
+        {
+            Ok(services) => services,
+            Err(err) => {
+                return registration_failure(err);
+            }
+        };
+
+        match update_server(
+            &app_state,
+            port,
+            GrpcRegistryUpdate::new(port, registry),
+        )
+        .await
+        {
+            Ok(_) => {
+                let response = GrpcDescriptorResponse::new(port, services);
+                HttpResponse::success(StatusCode::OK, response)
+            }
+            Err(err) => registration_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+pub async fn register_grpc_method_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestJson(registration_request): RequestJson<GrpcRegistrationRequest>,
+) -> HttpResponse<GrpcRegistrationResponse> {
+    let span = info_span!("[Controller: Register gRPC Method]");
+
+    async move {
+        let port = registration_request.port;
+
+        let _port_lock = match app_state.lock_port(port).await {
+            Ok(port_lock) => port_lock,
+            Err(err) => return registration_failure(err.into()),
+        };
+
+        let mut registry = match app_state.get_grpc_registry(port).await {
+            Ok(registry) => registry,
+            Err(err) => return registration_failure(err.into()),
+        };
+        let registration_to_be_removed =
+            match registry.add_stub(registration_request.clone()) {
+                Ok(removed) => removed,
+                Err(err) => {
+                    return registration_failure(err);
+                }
+            };
+
+        match update_server(
+            &app_state,
+            port,
+            GrpcRegistryUpdate::new(port, registry),
+        )
+        .await
+        {
+            Ok(_) => {
+                let response = GrpcRegistrationResponse::new(
+                    registration_request,
+                    registration_to_be_removed,
+                );
+                HttpResponse::success(StatusCode::OK, response)
+            }
+            Err(err) => registration_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
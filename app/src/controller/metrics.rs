@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+};
+use http::{HeaderValue, header::CONTENT_TYPE};
+use tracing::{Instrument, info_span};
+
+use crate::{
+    business::{
+        app_state::AppState,
+        server::connection_establisher::ConnectionEstablisher,
+    },
+    controller::store_failure,
+};
+
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+pub async fn metrics_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+) -> Response {
+    let span = info_span!("[Controller: Metrics]");
+
+    async move {
+        let registrations = match app_state.get_registrations().await {
+            Ok(registrations) => registrations,
+            Err(err) => return store_failure::<()>(err).into_response(),
+        };
+
+        let mut response = app_state
+            .get_metrics()
+            .render(&registrations)
+            .into_response();
+        response.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static(PROMETHEUS_CONTENT_TYPE),
+        );
+
+        response
+    }
+    .instrument(span)
+    .await
+}
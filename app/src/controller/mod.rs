@@ -1,2 +1,38 @@
+use http::StatusCode;
+use serde::Serialize;
+
+use crate::{
+    business::store::store_error::StoreError,
+    model::{error::Error, response::http_response::HttpResponse},
+};
+
+pub mod cors;
+pub mod dashboard;
+pub mod grpc;
+pub mod metrics;
+pub mod oidc;
+pub mod peer_sync;
+pub mod rate_limit;
 pub mod register;
 pub mod registrations;
+pub mod reset;
+pub mod servers;
+pub mod socket;
+
+fn registration_failure<T: Serialize>(error: Error) -> HttpResponse<T> {
+    let status_code = match &error {
+        Error::Conflict(_) => StatusCode::CONFLICT,
+        Error::Validation(..) => StatusCode::UNPROCESSABLE_ENTITY,
+        Error::Store(_) => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::BAD_REQUEST,
+    };
+
+    HttpResponse::registration_failure(status_code, error)
+}
+
+fn store_failure<T: Serialize>(store_error: StoreError) -> HttpResponse<T> {
+    HttpResponse::failure(
+        StatusCode::SERVICE_UNAVAILABLE,
+        Error::from(store_error),
+    )
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode};
+use tracing::{Instrument, info_span};
+
+use crate::{
+    business::{
+        app_state::AppState,
+        server::{
+            connection_establisher::ConnectionEstablisher,
+            oidc::{OidcProvider, OidcProviderUpdate},
+        },
+        server_update::update_server,
+    },
+    controller::registration_failure,
+    model::{
+        internal::request_json::RequestJson,
+        request::oidc_registration_request::OidcRegistrationRequest,
+        response::{
+            http_response::HttpResponse,
+            oidc_registration_response::OidcRegistrationResponse,
+        },
+    },
+};
+
+pub async fn register_oidc_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestJson(registration_request): RequestJson<OidcRegistrationRequest>,
+) -> HttpResponse<OidcRegistrationResponse> {
+    let span = info_span!("[Controller: Register OIDC Provider]");
+
+    async move {
+        let port = registration_request.port;
+
+        let _port_lock = match app_state.lock_port(port).await {
+            Ok(port_lock) => port_lock,
+            Err(err) => return registration_failure(err.into()),
+        };
+
+        let provider = match OidcProvider::new(registration_request) {
+            Ok(provider) => provider,
+            Err(err) => {
+                return registration_failure(err);
+            }
+        };
+        let registration = provider.get_registration();
+
+        let registration_to_be_removed =
+            match app_state.get_oidc_provider(port).await {
+                Ok(provider) => {
+                    provider.map(|provider| provider.get_registration())
+                }
+                Err(err) => return registration_failure(err.into()),
+            };
+
+        match update_server(
+            &app_state,
+            port,
+            OidcProviderUpdate::new(port, provider),
+        )
+        .await
+        {
+            Ok(_) => {
+                let response = OidcRegistrationResponse::new(
+                    registration,
+                    registration_to_be_removed,
+                );
+                HttpResponse::success(StatusCode::OK, response)
+            }
+            Err(err) => registration_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
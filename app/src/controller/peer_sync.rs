@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode};
+use serde::Serialize;
+use tracing::{Instrument, info_span};
+
+use crate::{
+    business::{
+        app_state::AppState,
+        peer_sync::{apply_peer_update, peer_state},
+        server::connection_establisher::ConnectionEstablisher,
+    },
+    model::{
+        error::Error,
+        internal::{request_json::RequestJson, request_path::RequestPath},
+        port::Port,
+        request::peer_update_request::PeerUpdateRequest,
+        response::{
+            http_response::HttpResponse,
+            peer_state_response::PeerStateResponse,
+            peer_update_response::PeerUpdateResponse,
+        },
+    },
+};
+
+pub async fn peer_update_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestPath(port): RequestPath<Port>,
+    RequestJson(peer_update_request): RequestJson<PeerUpdateRequest>,
+) -> HttpResponse<PeerUpdateResponse> {
+    let span = info_span!("[Controller: Peer Update]");
+
+    async move {
+        match apply_peer_update(&app_state, port, peer_update_request).await {
+            Ok(version) => HttpResponse::success(
+                StatusCode::OK,
+                PeerUpdateResponse::new(port, version),
+            ),
+            Err(err) => peer_sync_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+pub async fn peer_state_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+) -> HttpResponse<Vec<PeerStateResponse>> {
+    let span = info_span!("[Controller: Peer State]");
+
+    async move {
+        match peer_state(&app_state).await {
+            Ok(state) => HttpResponse::success(StatusCode::OK, state),
+            Err(err) => peer_sync_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+fn peer_sync_failure<T: Serialize>(err: Error) -> HttpResponse<T> {
+    let status_code = match &err {
+        Error::NotFound(_) => StatusCode::NOT_FOUND,
+        Error::Conflict(_) => StatusCode::CONFLICT,
+        Error::Validation(..) => StatusCode::UNPROCESSABLE_ENTITY,
+        Error::Store(_) => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::BAD_REQUEST,
+    };
+
+    HttpResponse::failure(status_code, err)
+}
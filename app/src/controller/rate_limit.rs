@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode};
+use tracing::{Instrument, info_span};
+
+use crate::{
+    business::{
+        app_state::AppState,
+        server::{
+            connection_establisher::ConnectionEstablisher,
+            rate_limit::{RateLimiter, RateLimiterUpdate},
+        },
+        server_update::update_server,
+    },
+    controller::{registration_failure, store_failure},
+    model::{
+        error::Error,
+        internal::{request_json::RequestJson, request_path::RequestPath},
+        port::Port,
+        request::rate_limit_registration_request::RateLimitRegistrationRequest,
+        response::{
+            http_response::HttpResponse, rate_limit_counter::RateLimitCounter,
+            rate_limit_registration_response::RateLimitRegistrationResponse,
+        },
+    },
+};
+
+pub async fn register_rate_limit_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestJson(registration_request): RequestJson<
+        RateLimitRegistrationRequest,
+    >,
+) -> HttpResponse<RateLimitRegistrationResponse> {
+    let span = info_span!("[Controller: Register Rate Limit]");
+
+    async move {
+        let RateLimitRegistrationRequest { port, rate_limit } =
+            registration_request;
+
+        let _port_lock = match app_state.lock_port(port).await {
+            Ok(port_lock) => port_lock,
+            Err(err) => return registration_failure(err.into()),
+        };
+
+        let rate_limiter = match RateLimiter::new(rate_limit.clone()) {
+            Ok(rate_limiter) => rate_limiter,
+            Err(err) => {
+                return registration_failure(err);
+            }
+        };
+
+        let rate_limit_to_be_removed =
+            match app_state.get_rate_limiter(port).await {
+                Ok(rate_limiter) => rate_limiter
+                    .map(|rate_limiter| rate_limiter.get_rate_limit()),
+                Err(err) => return registration_failure(err.into()),
+            };
+
+        match update_server(
+            &app_state,
+            port,
+            RateLimiterUpdate::new(port, rate_limiter),
+        )
+        .await
+        {
+            Ok(_) => {
+                let response = RateLimitRegistrationResponse::new(
+                    rate_limit,
+                    rate_limit_to_be_removed,
+                );
+                HttpResponse::success(StatusCode::OK, response)
+            }
+            Err(err) => registration_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+pub async fn list_rate_limit_counters_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestPath(port): RequestPath<Port>,
+) -> HttpResponse<Vec<RateLimitCounter>> {
+    let span = info_span!("[Controller: List Rate Limit Counters]");
+
+    async move {
+        match app_state.get_rate_limit_counters(port).await {
+            Ok(Some(counters)) => {
+                HttpResponse::success(StatusCode::OK, counters)
+            }
+            Ok(None) => HttpResponse::failure(
+                StatusCode::NOT_FOUND,
+                no_server_error(port),
+            ),
+            Err(err) => store_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+pub async fn reset_rate_limit_counters_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestPath(port): RequestPath<Port>,
+) -> HttpResponse<Vec<RateLimitCounter>> {
+    let span = info_span!("[Controller: Reset Rate Limit Counters]");
+
+    async move {
+        match app_state.reset_rate_limit_counters(port).await {
+            Ok(Some(counters)) => {
+                HttpResponse::success(StatusCode::OK, counters)
+            }
+            Ok(None) => HttpResponse::failure(
+                StatusCode::NOT_FOUND,
+                no_server_error(port),
+            ),
+            Err(err) => store_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+fn no_server_error(port: Port) -> Error {
+    Error::NotFound(format!("No server is running on port {port}"))
+}
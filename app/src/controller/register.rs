@@ -6,15 +6,22 @@ use tracing::{Instrument, info_span};
 use crate::{
     business::{
         app_state::AppState,
-        server::{
-            connection_establisher::ConnectionEstablisher,
-            restartable::Restartable,
+        batch_registration::{
+            check_conflict, normalize, register_locked_batch, validate,
         },
+        peer_sync::publish_ports,
+        server::connection_establisher::ConnectionEstablisher,
+        server_update::update_server,
     },
+    controller::registration_failure,
     model::{
         internal::request_json::RequestJson,
-        request::registration_request::RegistrationRequest,
+        request::{
+            batch_registration_request::BatchRegistrationRequest,
+            registration_request::RegistrationRequest,
+        },
         response::{
+            batch_registration_response::BatchRegistrationResponse,
             http_response::HttpResponse,
             registration_response::RegistrationResponse,
         },
@@ -28,36 +35,101 @@ pub async fn register_endpoint_controller<T: ConnectionEstablisher>(
     let span = info_span!("[Controller: Register Endpoint]");
 
     async move {
-        let port = registration_request.port.to_string();
-
-        let server = app_state.remove_server(&port);
+        let registration_request = normalize(&app_state, registration_request);
+        let port = registration_request.port;
 
-        let registration_to_be_removed = match &server {
-            Some(server) => server.get_registration(
-                registration_request.path.clone(),
-                registration_request.method.clone(),
-            ),
-            None => None,
+        let _port_lock = match app_state.lock_port(port).await {
+            Ok(port_lock) => port_lock,
+            Err(err) => return registration_failure(err.into()),
         };
 
-        let server = server
-            .restart(
-                app_state.get_connection_establisher(),
-                registration_request.clone(),
-            )
-            .await;
+        if let Err(err) = validate(&registration_request) {
+            return registration_failure(err);
+        }
+
+        if let Err(err) =
+            check_conflict(&app_state, &registration_request, []).await
+        {
+            return registration_failure(err);
+        }
 
-        match server {
-            Ok(server) => {
-                app_state.add_server(&port, server);
+        let registration_to_be_removed = if port.is_ephemeral() {
+            None
+        } else {
+            match app_state
+                .get_registration(
+                    port,
+                    registration_request.path.clone(),
+                    registration_request.method.clone(),
+                    registration_request.graphql.clone(),
+                )
+                .await
+            {
+                Ok(registration) => registration,
+                Err(err) => return registration_failure(err.into()),
+            }
+        };
+
+        match update_server(&app_state, port, registration_request.clone())
+            .await
+        {
+            Ok(allocated) => {
+                // The ephemeral lock doesn't cover the port it allocated.
+                let _allocated_lock = if port.is_ephemeral() {
+                    app_state.lock_port(allocated).await.ok()
+                } else {
+                    None
+                };
+                publish_ports(&app_state, [allocated]).await;
 
                 let response = RegistrationResponse::new(
+                    allocated,
                     registration_request,
                     registration_to_be_removed,
                 );
                 HttpResponse::success(StatusCode::OK, response)
             }
-            Err(err) => HttpResponse::failure(StatusCode::BAD_REQUEST, err),
+            Err(err) => registration_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+pub async fn register_batch_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestJson(BatchRegistrationRequest { registrations }): RequestJson<
+        BatchRegistrationRequest,
+    >,
+) -> HttpResponse<BatchRegistrationResponse> {
+    let span = info_span!("[Controller: Register Batch]");
+
+    async move {
+        // Held until the ports are published, see `publish_ports`.
+        let _port_locks = match app_state
+            .lock_ports(
+                registrations.iter().map(|registration| registration.port),
+            )
+            .await
+        {
+            Ok(port_locks) => port_locks,
+            Err(err) => return registration_failure(err.into()),
+        };
+
+        match register_locked_batch(&app_state, registrations).await {
+            Ok(registrations) => {
+                publish_ports(
+                    &app_state,
+                    registrations.iter().map(|registration| registration.port),
+                )
+                .await;
+
+                HttpResponse::success(
+                    StatusCode::OK,
+                    BatchRegistrationResponse::new(registrations),
+                )
+            }
+            Err(err) => registration_failure(err),
         }
     }
     .instrument(span)
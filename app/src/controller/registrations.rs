@@ -2,13 +2,14 @@ use std::sync::Arc;
 
 use axum::extract::State;
 use http::StatusCode;
-use tracing::info_span;
+use tracing::{Instrument, info_span};
 
 use crate::{
     business::{
         app_state::AppState,
         server::connection_establisher::ConnectionEstablisher,
     },
+    controller::store_failure,
     model::{
         internal::server_registration::ServerRegistration,
         response::http_response::HttpResponse,
@@ -18,9 +19,16 @@ use crate::{
 pub async fn list_all_registrations_controller<T: ConnectionEstablisher>(
     State(app_state): State<Arc<AppState<T>>>,
 ) -> HttpResponse<Vec<ServerRegistration>> {
-    let _entered = info_span!("[Controller: List All Registrations]").entered();
+    let span = info_span!("[Controller: List All Registrations]");
 
-    let registrations = app_state.get_registrations();
-
-    HttpResponse::success(StatusCode::OK, registrations)
+    async move {
+        match app_state.get_registrations().await {
+            Ok(registrations) => {
+                HttpResponse::success(StatusCode::OK, registrations)
+            }
+            Err(err) => store_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
 }
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+};
+use tracing::{Instrument, info_span};
+
+use crate::{
+    business::{
+        app_state::AppState, batch_registration::register_locked_batch,
+        peer_sync::publish_ports,
+        server::connection_establisher::ConnectionEstablisher,
+    },
+    controller::{registration_failure, store_failure},
+    model::{
+        request::reset_request::ResetRequest,
+        response::{
+            http_response::HttpResponse, reset_response::ResetResponse,
+        },
+    },
+};
+
+pub async fn reset_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    Query(reset_request): Query<ResetRequest>,
+) -> HttpResponse<ResetResponse> {
+    let span = info_span!("[Controller: Reset]");
+
+    async move {
+        // Registrations racing the reset would otherwise survive it or fail
+        // on a port the pinned routes are restarted on.
+        let pinned_ports = app_state
+            .get_pinned_registrations()
+            .into_iter()
+            .map(|registration| registration.port);
+        let _port_locks = match app_state.lock_all_ports(pinned_ports).await {
+            Ok(port_locks) => port_locks,
+            Err(err) => return store_failure(err),
+        };
+
+        let removed = match app_state.reset_servers().await {
+            Ok(removed) => removed,
+            Err(err) => return store_failure(err),
+        };
+
+        let pinned = if reset_request.keep_pinned {
+            match register_locked_batch(
+                &app_state,
+                app_state.get_pinned_registrations(),
+            )
+            .await
+            {
+                Ok(registrations) => registrations,
+                Err(err) => return registration_failure(err),
+            }
+        } else {
+            vec![]
+        };
+
+        publish_ports(
+            &app_state,
+            removed
+                .iter()
+                .map(|server_registration| server_registration.port)
+                .chain(pinned.iter().map(|registration| registration.port)),
+        )
+        .await;
+
+        let pinned = pinned
+            .into_iter()
+            .map(|registration| registration.added)
+            .collect();
+
+        HttpResponse::success(
+            StatusCode::OK,
+            ResetResponse::new(removed, pinned),
+        )
+    }
+    .instrument(span)
+    .await
+}
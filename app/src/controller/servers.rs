@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode};
+use tracing::{Instrument, info_span};
+
+use crate::{
+    business::{
+        app_state::AppState,
+        peer_sync::publish_ports,
+        server::connection_establisher::ConnectionEstablisher,
+        server_query::{find_routes, list_servers},
+    },
+    controller::store_failure,
+    model::{
+        error::Error,
+        internal::{
+            request_path::RequestPath,
+            request_query::RequestQuery,
+            server_registration::{Registration, ServerRegistration},
+        },
+        port::Port,
+        request::{page_request::PageRequest, route_filter::RouteFilter},
+        response::{
+            http_response::HttpResponse, page::Page,
+            server_summary::ServerSummary,
+        },
+    },
+};
+
+pub async fn list_servers_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestQuery(page_request): RequestQuery<PageRequest>,
+) -> HttpResponse<Page<ServerSummary>> {
+    let span = info_span!("[Controller: List Servers]");
+
+    async move {
+        match list_servers(&app_state, page_request).await {
+            Ok(servers) => HttpResponse::success(StatusCode::OK, servers),
+            Err(err) => store_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+pub async fn get_server_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestPath(port): RequestPath<Port>,
+) -> HttpResponse<ServerRegistration> {
+    let span = info_span!("[Controller: Get Server]");
+
+    async move {
+        match app_state.get_server_registration(port).await {
+            Ok(Some(server_registration)) => {
+                HttpResponse::success(StatusCode::OK, server_registration)
+            }
+            Ok(None) => HttpResponse::failure(
+                StatusCode::NOT_FOUND,
+                no_server_error(port),
+            ),
+            Err(err) => store_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+pub async fn list_routes_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestPath(port): RequestPath<Port>,
+    RequestQuery(route_filter): RequestQuery<RouteFilter>,
+    RequestQuery(page_request): RequestQuery<PageRequest>,
+) -> HttpResponse<Page<Registration>> {
+    let span = info_span!("[Controller: List Routes]");
+
+    async move {
+        match app_state.get_server_registration(port).await {
+            Ok(Some(server_registration)) => HttpResponse::success(
+                StatusCode::OK,
+                find_routes(server_registration, &route_filter, page_request),
+            ),
+            Ok(None) => HttpResponse::failure(
+                StatusCode::NOT_FOUND,
+                no_server_error(port),
+            ),
+            Err(err) => store_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+pub async fn remove_server_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestPath(port): RequestPath<Port>,
+) -> HttpResponse<ServerRegistration> {
+    let span = info_span!("[Controller: Remove Server]");
+
+    async move {
+        let _port_lock = match app_state.lock_port(port).await {
+            Ok(port_lock) => port_lock,
+            Err(err) => return store_failure(err),
+        };
+
+        match app_state.remove_server(port).await {
+            Ok(Some(server)) => {
+                let server_registration = server.get_registrations();
+                server.shutdown().await;
+                publish_ports(&app_state, [port]).await;
+
+                HttpResponse::success(StatusCode::OK, server_registration)
+            }
+            Ok(None) => HttpResponse::failure(
+                StatusCode::NOT_FOUND,
+                no_server_error(port),
+            ),
+            Err(err) => store_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+fn no_server_error(port: Port) -> Error {
+    Error::NotFound(format!("No server is running on port {port}"))
+}
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode};
+use tracing::{Instrument, info_span};
+
+use crate::{
+    business::{
+        app_state::AppState,
+        server::{
+            connection_establisher::ConnectionEstablisher,
+            socket::{SocketHandler, SocketHandlerUpdate},
+        },
+        server_update::update_server,
+    },
+    controller::{registration_failure, store_failure},
+    model::{
+        error::Error,
+        internal::{request_json::RequestJson, request_path::RequestPath},
+        port::Port,
+        request::socket_registration_request::SocketRegistrationRequest,
+        response::{
+            captured_message::CapturedMessage, http_response::HttpResponse,
+            socket_registration_response::SocketRegistrationResponse,
+        },
+    },
+};
+
+pub async fn register_socket_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestJson(registration_request): RequestJson<SocketRegistrationRequest>,
+) -> HttpResponse<SocketRegistrationResponse> {
+    let span = info_span!("[Controller: Register Socket]");
+
+    async move {
+        let port = registration_request.port;
+
+        let _port_lock = match app_state.lock_port(port).await {
+            Ok(port_lock) => port_lock,
+            Err(err) => return registration_failure(err.into()),
+        };
+
+        let handler = match SocketHandler::new(registration_request.clone()) {
+            Ok(handler) => handler,
+            Err(err) => {
+                return registration_failure(err);
+            }
+        };
+
+        let registration_to_be_removed = match app_state
+            .get_socket_handler(port)
+            .await
+        {
+            Ok(handler) => handler.map(|handler| handler.get_registration()),
+            Err(err) => return registration_failure(err.into()),
+        };
+
+        match update_server(
+            &app_state,
+            port,
+            SocketHandlerUpdate::new(port, handler),
+        )
+        .await
+        {
+            Ok(_) => {
+                let response = SocketRegistrationResponse::new(
+                    registration_request,
+                    registration_to_be_removed,
+                );
+                HttpResponse::success(StatusCode::OK, response)
+            }
+            Err(err) => registration_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+pub async fn list_captured_messages_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestPath(port): RequestPath<Port>,
+) -> HttpResponse<Vec<CapturedMessage>> {
+    let span = info_span!("[Controller: List Captured Messages]");
+
+    async move {
+        let handler = match app_state.get_socket_handler(port).await {
+            Ok(Some(handler)) => handler,
+            Ok(None) => {
+                return HttpResponse::failure(
+                    StatusCode::NOT_FOUND,
+                    no_socket_error(port),
+                );
+            }
+            Err(err) => return store_failure(err),
+        };
+
+        match handler.get_captured().await {
+            Ok(messages) => HttpResponse::success(StatusCode::OK, messages),
+            Err(err) => store_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+pub async fn clear_captured_messages_controller<T: ConnectionEstablisher>(
+    State(app_state): State<Arc<AppState<T>>>,
+    RequestPath(port): RequestPath<Port>,
+) -> HttpResponse<Vec<CapturedMessage>> {
+    let span = info_span!("[Controller: Clear Captured Messages]");
+
+    async move {
+        let handler = match app_state.get_socket_handler(port).await {
+            Ok(Some(handler)) => handler,
+            Ok(None) => {
+                return HttpResponse::failure(
+                    StatusCode::NOT_FOUND,
+                    no_socket_error(port),
+                );
+            }
+            Err(err) => return store_failure(err),
+        };
+
+        match handler.clear_captured().await {
+            Ok(messages) => HttpResponse::success(StatusCode::OK, messages),
+            Err(err) => store_failure(err),
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+fn no_socket_error(port: Port) -> Error {
+    Error::NotFound(format!("No socket stub is registered on port {port}"))
+}
@@ -0,0 +1,96 @@
+use serde_json::{Map, Value, json};
+
+use crate::model::{
+    port::Port, request::registration_request::RegistrationRequest,
+};
+
+#[derive(Debug)]
+pub enum Command {
+    Register(Box<RegistrationRequest>),
+    List { port: Option<Port> },
+    Remove { port: Port },
+    Reset { keep_pinned: bool },
+    Import { file: String },
+    Export { file: Option<String> },
+}
+
+impl Command {
+    /// Builds a command from its positional arguments and options.
+    pub fn parse(
+        positionals: &[String],
+        option: impl Fn(&str) -> Option<String>,
+        flag: impl Fn(&str) -> bool,
+    ) -> Result<Self, String> {
+        let (name, arguments) = positionals
+            .split_first()
+            .ok_or("Missing command".to_string())?;
+
+        match (name.as_str(), arguments) {
+            ("register", []) => {
+                register(option).map(Box::new).map(Self::Register)
+            }
+            ("list", []) => Ok(Self::List {
+                port: option("port").map(|port| port.parse()).transpose()?,
+            }),
+            ("remove", [port]) => Ok(Self::Remove {
+                port: port.parse()?,
+            }),
+            ("reset", []) => Ok(Self::Reset {
+                keep_pinned: !flag("drop-pinned"),
+            }),
+            ("import", [file]) => Ok(Self::Import { file: file.clone() }),
+            ("export", []) => Ok(Self::Export { file: None }),
+            ("export", [file]) => Ok(Self::Export {
+                file: Some(file.clone()),
+            }),
+            // Needs a journal of served requests, which api-gen doesn't keep.
+            ("tail", [source]) if source == "requests" => Err(
+                "`tail requests` isn't supported yet, api-gen doesn't keep a journal of stub requests"
+                    .to_string(),
+            ),
+            (
+                "register" | "list" | "remove" | "reset" | "import" | "export"
+                | "tail",
+                _,
+            ) => Err(format!("Unexpected arguments for `{name}`")),
+            _ => Err(format!("Unknown command `{name}`")),
+        }
+    }
+}
+
+/// Reads a registration from `--file`, or assembles it from `--port`,
+/// `--method`, `--path` and `--response`.
+fn register(
+    option: impl Fn(&str) -> Option<String>,
+) -> Result<RegistrationRequest, String> {
+    let registration = match option("file") {
+        Some(file) => {
+            let content = std::fs::read_to_string(&file)
+                .map_err(|err| format!("Failed to read `{file}`, {err}"))?;
+            serde_json::from_str::<Value>(&content)
+                .map_err(|err| format!("Failed to parse `{file}`, {err}"))?
+        }
+        None => {
+            let mut registration = Map::new();
+            for field in ["port", "method", "path"] {
+                if let Some(value) = option(field) {
+                    registration.insert(field.to_string(), json!(value));
+                }
+            }
+
+            // Anything that isn't JSON is sent as a plain string response.
+            let response = option("response").map(|response| {
+                serde_json::from_str(&response).unwrap_or(json!(response))
+            });
+            registration.insert(
+                "response".to_string(),
+                response.unwrap_or(Value::Null),
+            );
+
+            Value::Object(registration)
+        }
+    };
+
+    serde_json::from_value(registration)
+        .map_err(|err| format!("Invalid registration, {err}"))
+}
@@ -0,0 +1,362 @@
+use std::{fs, slice};
+
+use crate::{
+    client::admin_client::{AdminClient, DEFAULT_ADMIN_URL},
+    ctl::{
+        command::Command,
+        output::{
+            OutputFormat, json, registrations_table, render, reset_summary,
+            servers_table,
+        },
+    },
+    model::request::batch_registration_request::BatchRegistrationRequest,
+    security::admin_auth::AdminAuth,
+    util::arguments::Arguments,
+};
+
+pub mod command;
+pub mod output;
+
+pub const USAGE: &str = "\
+Usage: api-gen-ctl [options] <command>
+
+Commands:
+  register              Register a route from --file, or from --port,
+                        --method, --path and --response
+  list                  List every registration, or those of --port
+  remove <port>         Stop the server on a port
+  reset                 Remove all servers, --drop-pinned also drops the
+                        registrations pinned at startup
+  import <file>         Register every route in a registrations file
+  export [file]         Write every HTTP route as a registrations file
+
+Options:
+  --url <url>                   Admin API, defaults to http://localhost:8080
+  --admin-token <token>         Bearer token for the admin API
+  --admin-basic-auth <user:pw>  Basic credentials for the admin API
+  --output <table|json>         Output format, defaults to table";
+
+const URL_OPTION: &str = "url";
+const ADMIN_TOKEN_OPTION: &str = "admin-token";
+const ADMIN_BASIC_AUTH_OPTION: &str = "admin-basic-auth";
+const OUTPUT_OPTION: &str = "output";
+
+const URL_ENV_VAR: &str = "API_GEN_URL";
+const ADMIN_TOKEN_ENV_VAR: &str = "API_GEN_ADMIN_TOKEN";
+const ADMIN_BASIC_AUTH_ENV_VAR: &str = "API_GEN_ADMIN_BASIC_AUTH";
+
+const OPTIONS: &[&str] = &[
+    URL_OPTION,
+    ADMIN_TOKEN_OPTION,
+    ADMIN_BASIC_AUTH_OPTION,
+    OUTPUT_OPTION,
+    "port",
+    "method",
+    "path",
+    "response",
+    "file",
+];
+const FLAGS: &[&str] = &["drop-pinned", "help"];
+
+#[derive(Debug)]
+pub struct Invocation {
+    pub url: String,
+    pub admin_auth: Option<AdminAuth>,
+    pub output: OutputFormat,
+    pub command: Command,
+}
+
+impl Invocation {
+    pub fn parse<E>(args: &[String], env: E) -> Result<Self, String>
+    where
+        E: Fn(&str) -> Option<String>,
+    {
+        let arguments = Arguments::parse(args, OPTIONS, FLAGS)?;
+        let lookup = |option: &str, env_var: &str| {
+            arguments
+                .options
+                .get(option)
+                .cloned()
+                .or_else(|| env(env_var))
+        };
+
+        if arguments.flags.contains("help") {
+            return Err(USAGE.to_string());
+        }
+
+        let url = lookup(URL_OPTION, URL_ENV_VAR)
+            .unwrap_or(DEFAULT_ADMIN_URL.to_string());
+
+        let admin_token = lookup(ADMIN_TOKEN_OPTION, ADMIN_TOKEN_ENV_VAR);
+        let admin_basic_auth =
+            lookup(ADMIN_BASIC_AUTH_OPTION, ADMIN_BASIC_AUTH_ENV_VAR);
+
+        let admin_auth = match (admin_token, admin_basic_auth) {
+            (Some(_), Some(_)) => {
+                return Err(format!(
+                    "Only one of `--{ADMIN_TOKEN_OPTION}` and `--{ADMIN_BASIC_AUTH_OPTION}` can be configured"
+                ));
+            }
+            (Some(token), None) => Some(AdminAuth::bearer(&token)?),
+            (None, Some(credentials)) => Some(AdminAuth::basic(&credentials)?),
+            (None, None) => None,
+        };
+
+        let output = arguments
+            .options
+            .get(OUTPUT_OPTION)
+            .map(|format| format.parse())
+            .transpose()?
+            .unwrap_or_default();
+
+        let command = Command::parse(
+            &arguments.positionals,
+            |option| arguments.options.get(option).cloned(),
+            |flag| arguments.flags.contains(flag),
+        )?;
+
+        Ok(Self {
+            url,
+            admin_auth,
+            output,
+            command,
+        })
+    }
+
+    /// Runs the command against the admin API and renders its result.
+    pub async fn run(self) -> Result<String, String> {
+        let client = AdminClient::new(&self.url)
+            .with_admin_auth(self.admin_auth.clone());
+
+        match self.command {
+            Command::Register(registration_request) => {
+                let response = client.register(&registration_request).await?;
+
+                Ok(render(self.output, &response, |response| {
+                    registrations_table(slice::from_ref(response))
+                }))
+            }
+            Command::List { port: Some(port) } => {
+                let server = client.server(port).await?;
+
+                Ok(render(self.output, &server, |server| {
+                    servers_table(slice::from_ref(server))
+                }))
+            }
+            Command::List { port: None } => {
+                let servers = client.list().await?;
+
+                Ok(render(self.output, &servers, |servers| {
+                    servers_table(servers)
+                }))
+            }
+            Command::Remove { port } => {
+                let server = client.remove(port).await?;
+
+                Ok(render(self.output, &server, |server| {
+                    servers_table(slice::from_ref(server))
+                }))
+            }
+            Command::Reset { keep_pinned } => {
+                let response = client.reset(keep_pinned).await?;
+
+                Ok(render(self.output, &response, reset_summary))
+            }
+            Command::Import { file } => {
+                let content = fs::read_to_string(&file)
+                    .map_err(|err| format!("Failed to read `{file}`, {err}"))?;
+                let BatchRegistrationRequest { registrations } =
+                    serde_json::from_str(&content).map_err(|err| {
+                        format!("Failed to parse `{file}`, {err}")
+                    })?;
+
+                let response = client.register_batch(registrations).await?;
+
+                Ok(render(self.output, &response, |response| {
+                    registrations_table(&response.registrations)
+                }))
+            }
+            Command::Export { file } => {
+                let registrations = json(&client.export().await?);
+
+                match file {
+                    Some(file) => {
+                        fs::write(&file, registrations).map_err(|err| {
+                            format!("Failed to write `{file}`, {err}")
+                        })?;
+                        Ok(format!("Exported registrations to `{file}`."))
+                    }
+                    None => Ok(registrations),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use crate::{
+        ctl::{command::Command, output::OutputFormat},
+        model::{http_method::HttpMethod, port::Port},
+        security::admin_auth::AdminAuth,
+    };
+
+    use super::Invocation;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn should_use_defaults_without_options() {
+        let invocation = Invocation::parse(&args(&["list"]), env(&[])).unwrap();
+
+        assert_eq!("http://localhost:8080", invocation.url);
+        assert_eq!(None, invocation.admin_auth);
+        assert_eq!(OutputFormat::Table, invocation.output);
+        assert!(matches!(invocation.command, Command::List { port: None }));
+    }
+
+    #[test]
+    fn should_read_connection_options_from_environment() {
+        let invocation = Invocation::parse(
+            &args(&["--output", "json", "reset", "--drop-pinned"]),
+            env(&[
+                ("API_GEN_URL", "http://stubs:9090"),
+                ("API_GEN_ADMIN_TOKEN", "secret"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!("http://stubs:9090", invocation.url);
+        assert_eq!(
+            Some(AdminAuth::Bearer("secret".to_string())),
+            invocation.admin_auth
+        );
+        assert_eq!(OutputFormat::Json, invocation.output);
+        assert!(matches!(
+            invocation.command,
+            Command::Reset { keep_pinned: false }
+        ));
+    }
+
+    #[test]
+    fn should_assemble_registration_from_options() {
+        let invocation = Invocation::parse(
+            &args(&[
+                "register",
+                "--port",
+                "3000",
+                "--method",
+                "POST",
+                "--path",
+                "/orders",
+                "--response",
+                r#"{"id": 1}"#,
+            ]),
+            env(&[]),
+        )
+        .unwrap();
+
+        let Command::Register(registration_request) = invocation.command else {
+            panic!("Expected a register command!");
+        };
+        assert_eq!(Port::new(3000), registration_request.port);
+        assert_eq!(HttpMethod::Post, registration_request.method);
+        assert_eq!("/orders", &*registration_request.path);
+        assert_eq!(json!({ "id": 1 }), registration_request.response);
+    }
+
+    #[test]
+    fn should_send_non_json_response_as_string() {
+        let invocation = Invocation::parse(
+            &args(&[
+                "register",
+                "--method",
+                "GET",
+                "--path",
+                "/",
+                "--response",
+                "UP",
+            ]),
+            env(&[]),
+        )
+        .unwrap();
+
+        let Command::Register(registration_request) = invocation.command else {
+            panic!("Expected a register command!");
+        };
+        assert!(registration_request.port.is_ephemeral());
+        assert_eq!(json!("UP"), registration_request.response);
+    }
+
+    #[test]
+    fn should_parse_positional_arguments() {
+        let invocation =
+            Invocation::parse(&args(&["remove", "3000"]), env(&[])).unwrap();
+        assert!(matches!(
+            invocation.command,
+            Command::Remove { port } if port == Port::new(3000)
+        ));
+
+        let invocation =
+            Invocation::parse(&args(&["export", "stubs.json"]), env(&[]))
+                .unwrap();
+        assert!(matches!(
+            invocation.command,
+            Command::Export { file: Some(file) } if file == "stubs.json"
+        ));
+    }
+
+    #[test]
+    fn should_fail_for_invalid_invocations() {
+        for (arguments, error) in [
+            (vec![], "Missing command"),
+            (vec!["deploy"], "Unknown command `deploy`"),
+            (vec!["remove"], "Unexpected arguments for `remove`"),
+            (vec!["list", "--url"], "Missing value for option `--url`"),
+            (vec!["list", "--prot", "3000"], "Unknown option `--prot`"),
+            (
+                vec!["tail", "requests"],
+                "`tail requests` isn't supported yet, api-gen doesn't keep a journal of stub requests",
+            ),
+            (
+                vec!["remove", "abc"],
+                "`abc` is not a port, expected a number between 1 and 65535",
+            ),
+            (
+                vec!["list", "--output", "yaml"],
+                "Unknown output format `yaml`, expected one of `table` or `json`",
+            ),
+        ] {
+            assert_eq!(
+                error,
+                Invocation::parse(&args(&arguments), env(&[])).unwrap_err()
+            );
+        }
+    }
+
+    #[test]
+    fn should_fail_for_invalid_registration() {
+        let error = Invocation::parse(
+            &args(&["register", "--method", "GET", "--path", "orders"]),
+            env(&[]),
+        )
+        .unwrap_err();
+
+        assert_eq!("Invalid registration, `orders` must start with `/`", error);
+    }
+}
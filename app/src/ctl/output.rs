@@ -0,0 +1,176 @@
+use std::str::FromStr;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::model::{
+    internal::server_registration::ServerRegistration,
+    response::{
+        registration_response::RegistrationResponse,
+        reset_response::ResetResponse,
+    },
+};
+
+const RESPONSE_COLUMN_WIDTH: usize = 48;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_ascii_lowercase().as_str() {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!(
+                "Unknown output format `{format}`, expected one of `table` or `json`"
+            )),
+        }
+    }
+}
+
+pub fn render<T: Serialize>(
+    output: OutputFormat,
+    value: &T,
+    table: impl Fn(&T) -> String,
+) -> String {
+    match output {
+        OutputFormat::Table => table(value),
+        OutputFormat::Json => json(value),
+    }
+}
+
+pub fn json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+pub fn servers_table(servers: &[ServerRegistration]) -> String {
+    let mut rows = vec![];
+
+    for server in servers {
+        let port = server.port.to_string();
+
+        for registration in &server.registrations {
+            rows.push([
+                port.clone(),
+                "http".to_string(),
+                registration.method.to_string(),
+                registration.path.to_string(),
+                response_cell(&registration.response),
+            ]);
+        }
+        for grpc in &server.grpc {
+            rows.push([
+                port.clone(),
+                "grpc".to_string(),
+                grpc.method.clone(),
+                grpc.service.clone(),
+                response_cell(&grpc.response),
+            ]);
+        }
+        if let Some(socket) = &server.socket {
+            rows.push([
+                port.clone(),
+                "socket".to_string(),
+                json_cell(&socket.protocol),
+                String::new(),
+                format!("{} rule(s)", socket.rules.len()),
+            ]);
+        }
+        if let Some(oidc) = &server.oidc {
+            rows.push([
+                port.clone(),
+                "oidc".to_string(),
+                String::new(),
+                oidc.issuer.clone(),
+                format!("{} client(s)", oidc.clients.len()),
+            ]);
+        }
+    }
+
+    table(["PORT", "KIND", "METHOD", "PATH", "RESPONSE"], rows)
+}
+
+pub fn registrations_table(registrations: &[RegistrationResponse]) -> String {
+    let rows = registrations
+        .iter()
+        .map(|registration| {
+            [
+                registration.port.to_string(),
+                registration.added.method.to_string(),
+                registration.added.path.to_string(),
+                response_cell(&registration.added.response),
+                if registration.removed.is_some() {
+                    "replaced"
+                } else {
+                    "added"
+                }
+                .to_string(),
+            ]
+        })
+        .collect();
+
+    table(["PORT", "METHOD", "PATH", "RESPONSE", "STATUS"], rows)
+}
+
+pub fn reset_summary(reset_response: &ResetResponse) -> String {
+    format!(
+        "Removed {} server(s), restored {} pinned registration(s).",
+        reset_response.removed.len(),
+        reset_response.pinned.len()
+    )
+}
+
+fn response_cell(response: &Value) -> String {
+    let response = match response {
+        Value::String(response) => response.clone(),
+        response => response.to_string(),
+    };
+
+    match response.char_indices().nth(RESPONSE_COLUMN_WIDTH) {
+        Some((index, _)) => format!("{}...", &response[..index]),
+        None => response,
+    }
+}
+
+fn json_cell<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(Value::String(value)) => value,
+        Ok(value) => value.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+/// Left aligns every column to its widest cell.
+fn table<const N: usize>(headers: [&str; N], rows: Vec<[String; N]>) -> String {
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    std::iter::once(line(headers.to_vec()))
+        .chain(
+            rows.iter()
+                .map(|row| line(row.iter().map(String::as_str).collect())),
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -11,26 +11,94 @@ use crate::{
         server::connection_establisher::ConnectionEstablisher,
     },
     controller::{
-        register::register_endpoint_controller,
+        cors::register_cors_controller,
+        dashboard::{
+            dashboard_controller, dashboard_script_controller,
+            dashboard_style_controller,
+        },
+        grpc::{
+            register_grpc_descriptor_controller,
+            register_grpc_method_controller,
+        },
+        metrics::metrics_controller,
+        oidc::register_oidc_controller,
+        peer_sync::{peer_state_controller, peer_update_controller},
+        rate_limit::{
+            list_rate_limit_counters_controller,
+            register_rate_limit_controller,
+            reset_rate_limit_counters_controller,
+        },
+        register::{register_batch_controller, register_endpoint_controller},
         registrations::list_all_registrations_controller,
+        reset::reset_controller,
+        servers::{
+            get_server_controller, list_routes_controller,
+            list_servers_controller, remove_server_controller,
+        },
+        socket::{
+            clear_captured_messages_controller,
+            list_captured_messages_controller, register_socket_controller,
+        },
     },
     logging::http_trace::HttpTracingMiddleware,
+    security::admin_auth::AdminAuthMiddleware,
 };
 
 pub mod business;
+pub mod client;
+pub mod config;
 pub mod controller;
+pub mod ctl;
 pub mod logging;
 pub mod model;
+pub mod security;
 pub mod util;
 
 pub fn app<T: ConnectionEstablisher + Send + Sync + 'static>(
     port: &str,
     app_state: Arc<AppState<T>>,
 ) -> Router {
+    let admin_auth = app_state.get_admin_auth();
+    let metrics = app_state.get_metrics();
+
     Router::new()
-        .route("/health", get(|| async { "Up and running..." }))
         .route("/register", post(register_endpoint_controller))
+        .route("/register/batch", post(register_batch_controller))
+        .route(
+            "/register/grpc/descriptor",
+            post(register_grpc_descriptor_controller),
+        )
+        .route("/register/grpc", post(register_grpc_method_controller))
+        .route("/register/socket", post(register_socket_controller))
+        .route("/register/oidc", post(register_oidc_controller))
+        .route("/register/cors", post(register_cors_controller))
+        .route("/register/rate-limit", post(register_rate_limit_controller))
         .route("/info", get(list_all_registrations_controller))
+        .route("/servers", get(list_servers_controller))
+        .route(
+            "/servers/{port}",
+            get(get_server_controller).delete(remove_server_controller),
+        )
+        .route("/servers/{port}/routes", get(list_routes_controller))
+        .route("/reset", post(reset_controller))
+        .route("/sync", get(peer_state_controller))
+        .route("/sync/{port}", post(peer_update_controller))
+        .route("/metrics", get(metrics_controller))
+        .route(
+            "/sockets/{port}/captured",
+            get(list_captured_messages_controller)
+                .delete(clear_captured_messages_controller),
+        )
+        .route(
+            "/rate-limits/{port}",
+            get(list_rate_limit_counters_controller)
+                .delete(reset_rate_limit_counters_controller),
+        )
+        .with_admin_auth(admin_auth)
+        .route("/health", get(|| async { "Up and running..." }))
+        .route("/dashboard", get(dashboard_controller))
+        .route("/dashboard/dashboard.js", get(dashboard_script_controller))
+        .route("/dashboard/dashboard.css", get(dashboard_style_controller))
         .with_state(app_state)
-        .with_http_tracing(port.to_string())
+        .with_http_tracing(port.to_string(), metrics)
 }
@@ -1,18 +1,52 @@
-use std::time::Duration;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use axum::{Router, body::Body};
-use http::{Request, Response};
+use axum::{
+    Router,
+    body::Body,
+    extract::{MatchedPath, Request as AxumRequest},
+    middleware::{self, Next},
+    response::Response as AxumResponse,
+};
+use http::{HeaderMap, Request, Response};
+use opentelemetry::{
+    global,
+    propagation::Extractor,
+    trace::{TraceContextExt, TraceId},
+};
 use tower_http::trace::TraceLayer;
-use tracing::{Span, info, info_span};
+use tracing::{Span, field, info, info_span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::logging::metrics::{FailedRegistration, Metrics, UNMATCHED_ROUTE};
 
 pub trait HttpTracingMiddleware {
-    fn with_http_tracing(self, port: String) -> Router<()>;
+    fn with_http_tracing(
+        self,
+        port: String,
+        metrics: Arc<Metrics>,
+    ) -> Router<()>;
 }
 
 impl HttpTracingMiddleware for Router<()> {
-    fn with_http_tracing(self, port: String) -> Router<()> {
+    fn with_http_tracing(
+        self,
+        port: String,
+        metrics: Arc<Metrics>,
+    ) -> Router<()> {
+        let metrics_port = port.clone();
+        let metrics_layer = middleware::from_fn(
+            move |request: AxumRequest, next: Next| {
+                let port = metrics_port.clone();
+                let metrics = metrics.clone();
+                async move { record_metrics(&metrics, &port, request, next).await }
+            },
+        );
+
         let trace_layer = TraceLayer::new_for_http()
-            .make_span_with(|_: &Request<Body>| info_span!("[HTTP]"))
+            .make_span_with(make_span)
             .on_request(move |request: &Request<Body>, _: &Span| {
                 let method = request.method();
                 let path = request.uri();
@@ -28,6 +62,72 @@ impl HttpTracingMiddleware for Router<()> {
                 },
             );
 
-        self.layer(trace_layer)
+        self.layer(metrics_layer).layer(trace_layer)
+    }
+}
+
+fn make_span(request: &Request<Body>) -> Span {
+    let span = info_span!("[HTTP]", trace_id = field::Empty);
+
+    // Continue the caller's trace when a `traceparent` header is present.
+    let parent = global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(request.headers()))
+    });
+    let trace_id = parent.span().span_context().trace_id();
+
+    if trace_id != TraceId::INVALID {
+        span.record("trace_id", field::display(trace_id));
+        // Fails only when no OpenTelemetry layer is installed.
+        let _ = span.set_parent(parent);
+    }
+
+    span
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
     }
 }
+
+async fn record_metrics(
+    metrics: &Metrics,
+    port: &str,
+    request: AxumRequest,
+    next: Next,
+) -> AxumResponse {
+    let matched_path = request.extensions().get::<MatchedPath>().cloned();
+    let method = request.method().to_string();
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+
+    // Stub routes are matched behind the path rewrite and report their
+    // route on the response instead.
+    let route = matched_path
+        .or_else(|| response.extensions().get::<MatchedPath>().cloned())
+        .map(|matched_path| matched_path.as_str().to_string())
+        .unwrap_or(UNMATCHED_ROUTE.to_string());
+
+    metrics.record_request(
+        port,
+        &route,
+        &method,
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+
+    if let Some(FailedRegistration(failure_type)) =
+        response.extensions().get::<FailedRegistration>()
+    {
+        metrics.record_registration_failure(&route, failure_type);
+    }
+
+    response
+}
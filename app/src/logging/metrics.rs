@@ -0,0 +1,167 @@
+use std::{sync::Mutex, time::Duration};
+
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec,
+    Opts, Registry, TextEncoder, core::Collector,
+};
+use tracing::error;
+
+use crate::model::internal::server_registration::ServerRegistration;
+
+pub const UNMATCHED_ROUTE: &str = "<unmatched>";
+
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    active_ports: IntGauge,
+    registrations: IntGaugeVec,
+    registration_failures: IntCounterVec,
+    scrape: Mutex<()>,
+}
+
+/// Marks a response as a rejected registration, the admin router counts it
+/// against the endpoint that produced it.
+#[derive(Clone, Copy)]
+pub struct FailedRegistration(pub &'static str);
+
+impl Metrics {
+    pub fn new() -> Self {
+        let http_requests = IntCounterVec::new(
+            Opts::new(
+                "api_gen_http_requests_total",
+                "HTTP requests served, by port, route, method and status.",
+            ),
+            &["port", "route", "method", "status"],
+        )
+        .expect("Invalid HTTP request counter definition");
+
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "api_gen_http_request_duration_seconds",
+                "HTTP request latencies, by port, route and method.",
+            ),
+            &["port", "route", "method"],
+        )
+        .expect("Invalid HTTP request histogram definition");
+
+        let active_ports = IntGauge::new(
+            "api_gen_active_ports",
+            "Ports with a running stub server.",
+        )
+        .expect("Invalid active ports gauge definition");
+
+        let registrations = IntGaugeVec::new(
+            Opts::new(
+                "api_gen_registrations",
+                "Active registrations, by port and kind.",
+            ),
+            &["port", "kind"],
+        )
+        .expect("Invalid registrations gauge definition");
+
+        let registration_failures = IntCounterVec::new(
+            Opts::new(
+                "api_gen_registration_failures_total",
+                "Rejected registrations, by endpoint and failure type.",
+            ),
+            &["endpoint", "failure_type"],
+        )
+        .expect("Invalid registration failure counter definition");
+
+        let registry = Registry::new();
+        for collector in [
+            Box::new(http_requests.clone()) as Box<dyn Collector>,
+            Box::new(http_request_duration.clone()),
+            Box::new(active_ports.clone()),
+            Box::new(registrations.clone()),
+            Box::new(registration_failures.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("Metric registered more than once");
+        }
+
+        Self {
+            registry,
+            http_requests,
+            http_request_duration,
+            active_ports,
+            registrations,
+            registration_failures,
+            scrape: Mutex::new(()),
+        }
+    }
+
+    pub fn record_request(
+        &self,
+        port: &str,
+        route: &str,
+        method: &str,
+        status: u16,
+        latency: Duration,
+    ) {
+        self.http_requests
+            .with_label_values(&[port, route, method, &status.to_string()])
+            .inc();
+        self.http_request_duration
+            .with_label_values(&[port, route, method])
+            .observe(latency.as_secs_f64());
+    }
+
+    pub fn record_registration_failure(
+        &self,
+        endpoint: &str,
+        failure_type: &str,
+    ) {
+        self.registration_failures
+            .with_label_values(&[endpoint, failure_type])
+            .inc();
+    }
+
+    pub fn render(&self, servers: &[ServerRegistration]) -> String {
+        // Gauges are shared, concurrent scrapes must not interleave updates.
+        let _scrape = self.scrape.lock();
+
+        self.update_server_gauges(servers);
+        self.encode()
+    }
+
+    fn update_server_gauges(&self, servers: &[ServerRegistration]) {
+        self.active_ports.set(servers.len() as i64);
+
+        self.registrations.reset();
+        for server in servers {
+            let port = server.port.to_string();
+
+            for (kind, count) in [
+                ("http", server.registrations.len()),
+                ("grpc", server.grpc.len()),
+                ("socket", server.socket.iter().count()),
+                ("oidc", server.oidc.iter().count()),
+            ] {
+                self.registrations
+                    .with_label_values(&[port.as_str(), kind])
+                    .set(count as i64);
+            }
+        }
+    }
+
+    fn encode(&self) -> String {
+        let mut buffer = vec![];
+
+        if let Err(err) =
+            TextEncoder::new().encode(&self.registry.gather(), &mut buffer)
+        {
+            error!(%err, "Failed to encode metrics, {err}.");
+        }
+
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
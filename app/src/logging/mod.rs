@@ -1,2 +1,3 @@
 pub mod http_trace;
+pub mod metrics;
 pub mod setup;
@@ -1,17 +1,119 @@
-use std::env;
+use std::str::FromStr;
 
-const LOG_LEVEL_ENV_VAR: &str = "RUST_LOG";
-const DEFAULT_LOG_LEVEL: &str = "TRACE";
+use opentelemetry::{global, trace::TracerProvider};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    Resource, propagation::TraceContextPropagator, trace::SdkTracerProvider,
+};
+use tracing_subscriber::{
+    EnvFilter, Layer, Registry, fmt, layer::SubscriberExt,
+    util::SubscriberInitExt,
+};
 
-fn configure_log_level() {
-    if let Err(_) = env::var(LOG_LEVEL_ENV_VAR) {
-        unsafe {
-            env::set_var(LOG_LEVEL_ENV_VAR, DEFAULT_LOG_LEVEL);
+use crate::config::Config;
+
+const SERVICE_NAME: &str = "api-gen";
+const OTLP_TRACES_PATH: &str = "/v1/traces";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    Pretty,
+    #[default]
+    Compact,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_ascii_lowercase().as_str() {
+            "pretty" => Ok(LogFormat::Pretty),
+            "compact" => Ok(LogFormat::Compact),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!(
+                "Unknown log format `{format}`, expected one of `pretty`, `compact` or `json`"
+            )),
         }
     }
 }
 
-pub fn setup_logging() {
-    configure_log_level();
-    tracing_subscriber::fmt::init();
+/// Flushes pending spans to the collector when dropped.
+pub struct LoggingGuard {
+    tracer_provider: Option<SdkTracerProvider>,
+}
+
+impl Drop for LoggingGuard {
+    fn drop(&mut self) {
+        if let Some(tracer_provider) = self.tracer_provider.take()
+            && let Err(err) = tracer_provider.shutdown()
+        {
+            eprintln!("Failed to flush traces, {err}.");
+        }
+    }
+}
+
+pub fn setup_logging(config: &Config) -> Result<LoggingGuard, String> {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let tracer_provider = config
+        .otlp_endpoint
+        .as_deref()
+        .map(tracer_provider)
+        .transpose()?;
+
+    let otel_layer = tracer_provider.as_ref().map(|tracer_provider| {
+        tracing_opentelemetry::layer()
+            .with_tracer(tracer_provider.tracer(SERVICE_NAME))
+    });
+
+    tracing_subscriber::registry()
+        .with(fmt_layer(config.log_format))
+        .with(otel_layer)
+        .with(EnvFilter::new(&config.log_level))
+        .try_init()
+        .map_err(|err| format!("Failed to set up logging, {err}"))?;
+
+    Ok(LoggingGuard { tracer_provider })
+}
+
+fn fmt_layer(format: LogFormat) -> Box<dyn Layer<Registry> + Send + Sync> {
+    match format {
+        LogFormat::Pretty => fmt::layer().pretty().boxed(),
+        LogFormat::Compact => fmt::layer().compact().boxed(),
+        LogFormat::Json => fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .boxed(),
+    }
+}
+
+fn tracer_provider(endpoint: &str) -> Result<SdkTracerProvider, String> {
+    let endpoint = traces_endpoint(endpoint);
+
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(&endpoint)
+        .build()
+        .map_err(|err| {
+            format!("Failed to create OTLP exporter for `{endpoint}`, {err}")
+        })?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder().with_service_name(SERVICE_NAME).build(),
+        )
+        .build())
+}
+
+fn traces_endpoint(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+
+    if endpoint.ends_with(OTLP_TRACES_PATH) {
+        endpoint.to_string()
+    } else {
+        format!("{endpoint}{OTLP_TRACES_PATH}")
+    }
 }
@@ -1,30 +1,151 @@
-use std::sync::Arc;
+use std::{env, process::exit, sync::Arc};
 
 use api_gen::{
     app,
     business::{
         app_state::AppState,
-        server::connection_establisher::TcpConnectionEstablisher,
+        batch_registration::{register_batch, restore_stored_registrations},
+        peer_sync::{PeerSync, pull_peer_state},
+        server::connection_establisher::{
+            TcpConnectionEstablisher, serve_with_drain,
+        },
     },
+    client::admin_client::AdminClient,
+    config::Config,
+    model::error::Error,
+    util::shutdown::shutdown_signal,
 };
-use axum::serve;
 use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
 
 use api_gen::logging::setup::setup_logging;
 
 #[tokio::main]
 async fn main() {
-    setup_logging();
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let config = match Config::parse(&args, |name| env::var(name).ok()) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}");
+            exit(1);
+        }
+    };
 
-    let port = "8080";
-    let connection_establisher = TcpConnectionEstablisher::default();
-    let app_state = Arc::new(AppState::new(connection_establisher));
+    let _logging_guard = match setup_logging(&config) {
+        Ok(logging_guard) => logging_guard,
+        Err(err) => {
+            eprintln!("{err}");
+            exit(1);
+        }
+    };
 
-    let app = app(port, app_state);
+    let pinned_registrations = match config.load_pinned_registrations() {
+        Ok(pinned_registrations) => pinned_registrations,
+        Err(err) => {
+            error!(%err, "{err}.");
+            exit(1);
+        }
+    };
 
-    let listener = TcpListener::bind(format!("0.0.0.0:{}", port))
+    let storage = match config.storage.open() {
+        Ok(storage) => storage,
+        Err(err) => {
+            error!(%err, "{err}.");
+            exit(1);
+        }
+    };
+
+    let peer_sync = (!config.peers.is_empty()).then(|| {
+        let instance_id = config
+            .instance_id
+            .clone()
+            .unwrap_or_else(PeerSync::random_instance_id);
+        let peers = config
+            .peers
+            .iter()
+            .map(|peer| {
+                AdminClient::new(peer).with_admin_auth(config.admin_auth.clone())
+            })
+            .collect::<Vec<_>>();
+
+        let count = peers.len();
+        info!(%instance_id, %count, "Syncing registrations with {count} peer(s) as `{instance_id}`.");
+        PeerSync::new(&instance_id, peers)
+    });
+
+    let port = config.port;
+    let connection_establisher =
+        TcpConnectionEstablisher::new(config.drain_timeout)
+            .with_bind_address(config.bind_address)
+            .with_port_range(config.port_range.clone())
+            .with_path_matching(config.path_matching);
+    let app_state = Arc::new(
+        AppState::new(connection_establisher)
+            .with_admin_auth(config.admin_auth.clone())
+            .with_storage(storage)
+            .with_peer_sync(peer_sync)
+            .with_pinned_registrations(pinned_registrations.clone()),
+    );
+
+    // Stored routes come first, so the pinned ones win where both overlap.
+    match restore_stored_registrations(&app_state).await {
+        Ok(restored) if !restored.is_empty() => {
+            let count = restored.len();
+            info!(%count, "Restored {count} stored registration(s).");
+        }
+        Ok(_) => {}
+        Err(err) => {
+            let message = err.failure_message();
+            warn!(%message, "Failed to restore stored registrations, {message}.");
+        }
+    }
+
+    if let Err(err) = register_batch(&app_state, pinned_registrations).await {
+        let message = err.failure_message();
+        error!(%message, "Failed to register pinned registrations, {message}.");
+
+        if let Error::Batch(_, failures) = &err {
+            for failure in failures {
+                let index = failure.index;
+                let failure_message = &failure.failure_message;
+                error!(%index, "Pinned registration #{index} failed, {failure_message}.");
+            }
+        }
+        exit(1);
+    }
+
+    // Changes the peers made while this instance was down were only
+    // broadcast to the running instances.
+    let pulled = pull_peer_state(&app_state).await;
+    if pulled > 0 {
+        info!(%pulled, "Pulled the routes of {pulled} port(s) from the peers.");
+    }
+
+    let app = app(&port.to_string(), app_state.clone());
+
+    let listener = match TcpListener::bind((config.bind_address, port.number()))
         .await
-        .unwrap();
+    {
+        Ok(listener) => listener,
+        Err(err) => {
+            error!(%port, %err, "Failed to bind the admin server to port {port}, {err}.");
+            exit(1);
+        }
+    };
+
+    // Stub servers are drained first, the admin server stays up until then.
+    let admin_shutdown = CancellationToken::new();
+    let admin_shutdown_trigger = admin_shutdown.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        if let Err(err) = app_state.shutdown_servers().await {
+            error!(%err, "Failed to shut down the stub servers, {err}.");
+        }
+        admin_shutdown_trigger.cancel();
+    });
+
+    serve_with_drain(listener, app, admin_shutdown, config.drain_timeout).await;
 
-    serve(listener, app).await.unwrap();
+    info!("Shut down.");
 }
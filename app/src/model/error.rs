@@ -4,28 +4,130 @@ use axum::{
 };
 use serde_json::{Value, json};
 
+use crate::model::response::{
+    batch_registration_failure::BatchRegistrationFailure,
+    field_error::FieldError,
+};
+
 pub enum Error {
     JsonParse(String),
     Connection(String),
+    Grpc(String),
+    Socket(String),
+    NotFound(String),
+    Unauthorized(String),
+    Forbidden(String),
+    Auth(String),
+    Oidc(String),
+    Cors(String),
+    RateLimit(String),
+    TooManyRequests(String),
+    Conflict(String),
+    Store(String),
+    Batch(String, Vec<BatchRegistrationFailure>),
+    Validation(String, Vec<FieldError>),
 }
 
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
-        let http_response = match self {
-            Self::JsonParse(error_message) => {
-                Json(Error::json("MalformedJson", &error_message))
-                    .into_response()
-            }
-            Self::Connection(error_message) => {
-                Json(Error::json("Connection", &error_message)).into_response()
-            }
-        };
+        let mut json = Error::json(self.failure_type(), self.failure_message());
+
+        if let Self::Batch(_, failures) = &self {
+            json["failures"] = json!(failures);
+        }
 
-        http_response.into_response()
+        if let Self::Validation(_, fields) = &self {
+            json["fields"] = json!(fields);
+        }
+
+        Json(json).into_response()
     }
 }
 
 impl Error {
+    pub fn failure_type(&self) -> &'static str {
+        match self {
+            Self::JsonParse(_) => "MalformedJson",
+            Self::Connection(_) => "Connection",
+            Self::Grpc(_) => "Grpc",
+            Self::Socket(_) => "Socket",
+            Self::NotFound(_) => "NotFound",
+            Self::Unauthorized(_) => "Unauthorized",
+            Self::Forbidden(_) => "Forbidden",
+            Self::Auth(_) => "Auth",
+            Self::Oidc(_) => "Oidc",
+            Self::Cors(_) => "Cors",
+            Self::RateLimit(_) => "RateLimit",
+            Self::TooManyRequests(_) => "TooManyRequests",
+            Self::Conflict(_) => "Conflict",
+            Self::Store(_) => "Store",
+            Self::Batch(..) => "Batch",
+            Self::Validation(..) => "Validation",
+        }
+    }
+
+    pub fn failure_message(&self) -> &str {
+        match self {
+            Self::JsonParse(error_message)
+            | Self::Connection(error_message)
+            | Self::Grpc(error_message)
+            | Self::Socket(error_message)
+            | Self::NotFound(error_message)
+            | Self::Unauthorized(error_message)
+            | Self::Forbidden(error_message)
+            | Self::Auth(error_message)
+            | Self::Oidc(error_message)
+            | Self::Cors(error_message)
+            | Self::RateLimit(error_message)
+            | Self::TooManyRequests(error_message)
+            | Self::Conflict(error_message)
+            | Self::Store(error_message)
+            | Self::Batch(error_message, _)
+            | Self::Validation(error_message, _) => error_message,
+        }
+    }
+
+    /// Appends `context` to the failure message, the failure type is kept.
+    pub fn with_context(self, context: &str) -> Self {
+        let extend = |message: String| format!("{message}, {context}");
+
+        match self {
+            Self::JsonParse(message) => Self::JsonParse(extend(message)),
+            Self::Connection(message) => Self::Connection(extend(message)),
+            Self::Grpc(message) => Self::Grpc(extend(message)),
+            Self::Socket(message) => Self::Socket(extend(message)),
+            Self::NotFound(message) => Self::NotFound(extend(message)),
+            Self::Unauthorized(message) => Self::Unauthorized(extend(message)),
+            Self::Forbidden(message) => Self::Forbidden(extend(message)),
+            Self::Auth(message) => Self::Auth(extend(message)),
+            Self::Oidc(message) => Self::Oidc(extend(message)),
+            Self::Cors(message) => Self::Cors(extend(message)),
+            Self::RateLimit(message) => Self::RateLimit(extend(message)),
+            Self::TooManyRequests(message) => {
+                Self::TooManyRequests(extend(message))
+            }
+            Self::Conflict(message) => Self::Conflict(extend(message)),
+            Self::Store(message) => Self::Store(extend(message)),
+            Self::Batch(message, failures) => {
+                Self::Batch(extend(message), failures)
+            }
+            Self::Validation(message, fields) => {
+                Self::Validation(extend(message), fields)
+            }
+        }
+    }
+
+    /// Lists every invalid field in a single error.
+    pub fn validation(fields: Vec<FieldError>) -> Self {
+        let names = fields
+            .iter()
+            .map(|field| format!("`{}`", field.field))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Self::Validation(format!("Invalid fields: {names}"), fields)
+    }
+
     fn json(failure_type: &str, failure_message: &str) -> Value {
         json!({
             "status": "FAILED",
@@ -0,0 +1,80 @@
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlOperation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variables: Option<Value>,
+}
+
+impl GraphQlOperation {
+    pub fn matches(&self, incoming: &GraphQlOperation) -> bool {
+        let operation_name_matches = match &self.operation_name {
+            Some(name) => incoming.operation_name.as_ref() == Some(name),
+            None => true,
+        };
+
+        let query_matches = match &self.query {
+            Some(query) => incoming
+                .query
+                .as_ref()
+                .is_some_and(|q| normalize_query(q) == normalize_query(query)),
+            None => true,
+        };
+
+        let variables_matches = match &self.variables {
+            Some(variables) => incoming.variables.as_ref() == Some(variables),
+            None => true,
+        };
+
+        operation_name_matches && query_matches && variables_matches
+    }
+
+    pub fn specificity(&self) -> usize {
+        [
+            self.operation_name.is_some(),
+            self.query.is_some(),
+            self.variables.is_some(),
+        ]
+        .into_iter()
+        .filter(|specified| *specified)
+        .count()
+    }
+
+    pub fn describe(&self) -> String {
+        match &self.operation_name {
+            Some(name) => format!("`{name}`"),
+            None => "<anonymous>".to_string(),
+        }
+    }
+}
+
+impl PartialEq for GraphQlOperation {
+    fn eq(&self, other: &Self) -> bool {
+        self.operation_name == other.operation_name
+            && self.query.as_deref().map(normalize_query)
+                == other.query.as_deref().map(normalize_query)
+            && self.variables == other.variables
+    }
+}
+
+impl Eq for GraphQlOperation {}
+
+impl Hash for GraphQlOperation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.operation_name.hash(state);
+        self.query.as_deref().map(normalize_query).hash(state);
+        self.variables.as_ref().map(Value::to_string).hash(state);
+    }
+}
+
+fn normalize_query(query: &str) -> String {
+    query.split_whitespace().collect::<Vec<_>>().join(" ")
+}
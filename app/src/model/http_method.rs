@@ -1,5 +1,7 @@
 use std::fmt::{self};
 
+use axum::routing::MethodFilter;
+use http::Method;
 use serde::{Deserialize, Serialize, de::Error};
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
@@ -52,20 +54,42 @@ impl fmt::Display for HttpMethod {
         &self,
         formatter: &mut fmt::Formatter<'_>,
     ) -> Result<(), fmt::Error> {
-        formatter.write_str(&self.to_string())
+        formatter.write_str(self.as_str())
     }
 }
 
 impl HttpMethod {
-    pub fn to_string(&self) -> String {
-        let slice = match self {
+    pub fn as_str(&self) -> &'static str {
+        match self {
             HttpMethod::Get => "GET",
             HttpMethod::Post => "POST",
             HttpMethod::Put => "PUT",
             HttpMethod::Patch => "PATCH",
             HttpMethod::Delete => "DELETE",
-        };
+        }
+    }
+}
+
+impl From<&HttpMethod> for MethodFilter {
+    fn from(method: &HttpMethod) -> Self {
+        match method {
+            HttpMethod::Get => MethodFilter::GET,
+            HttpMethod::Post => MethodFilter::POST,
+            HttpMethod::Put => MethodFilter::PUT,
+            HttpMethod::Patch => MethodFilter::PATCH,
+            HttpMethod::Delete => MethodFilter::DELETE,
+        }
+    }
+}
 
-        slice.to_string()
+impl From<&HttpMethod> for Method {
+    fn from(method: &HttpMethod) -> Self {
+        match method {
+            HttpMethod::Get => Method::GET,
+            HttpMethod::Post => Method::POST,
+            HttpMethod::Put => Method::PUT,
+            HttpMethod::Patch => Method::PATCH,
+            HttpMethod::Delete => Method::DELETE,
+        }
     }
 }
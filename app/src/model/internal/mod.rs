@@ -1,2 +1,6 @@
 pub mod request_json;
+pub mod request_path;
+pub mod request_query;
 pub mod server_registration;
+pub mod stored_port;
+pub mod validation;
@@ -1,20 +1,25 @@
 use axum::{
     Json,
-    extract::{FromRequest, Request, rejection::JsonRejection},
+    extract::{FromRequest, Request},
 };
 use http::StatusCode;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
 use tracing::error;
 
-use crate::model::error::Error;
+use crate::model::{
+    error::Error, internal::validation::Validate,
+    response::http_response::HttpResponse,
+};
 
 pub struct RequestJson<T>(pub T);
 
 impl<S, T> FromRequest<S> for RequestJson<T>
 where
-    Json<T>: FromRequest<S, Rejection = JsonRejection>,
+    T: DeserializeOwned + Validate,
     S: Send + Sync,
 {
-    type Rejection = (StatusCode, Error);
+    type Rejection = HttpResponse<()>;
 
     async fn from_request(
         request: Request,
@@ -23,16 +28,45 @@ where
         let method = request.method().to_string();
         let uri = request.uri().to_string();
 
-        match Json::<T>::from_request(request, state).await {
-            Ok(value) => Ok(Self(value.0)),
-            Err(rejection) => {
-                let error_message = rejection.body_text();
+        let body = Json::<Value>::from_request(request, state)
+            .await
+            .map(|Json(body)| body)
+            .map_err(|rejection| rejection.body_text());
+
+        let fields = body.as_ref().map(T::validate).unwrap_or_default();
+        if !fields.is_empty() {
+            let error = Error::validation(fields);
+            let error_message = error.failure_message();
+            error!(
+                %method, %uri, %error_message,
+                "Invalid request received for [{method}]({uri}), {error_message}",
+            );
+            return Err(HttpResponse::registration_failure(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                error,
+            ));
+        }
+
+        let request = body.and_then(|body| {
+            serde_json::from_value(body).map_err(|err| {
+                format!(
+                    "Failed to deserialize the JSON body into the target type: {err}"
+                )
+            })
+        });
+
+        match request {
+            Ok(value) => Ok(Self(value)),
+            Err(error_message) => {
                 error!(
                     %method, %uri, %error_message,
                     "Unexpected JSON received in body for [{method}]({uri}), {error_message}",
                 );
 
-                Err((StatusCode::BAD_REQUEST, Error::JsonParse(error_message)))
+                Err(HttpResponse::registration_failure(
+                    StatusCode::BAD_REQUEST,
+                    Error::JsonParse(error_message),
+                ))
             }
         }
     }
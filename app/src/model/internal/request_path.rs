@@ -0,0 +1,70 @@
+use axum::{
+    extract::{
+        FromRequestParts, Path, RawPathParams,
+        path::{ErrorKind, FailedToDeserializePathParams},
+        rejection::PathRejection,
+    },
+    http::request::Parts,
+};
+use http::StatusCode;
+use serde::de::DeserializeOwned;
+use tracing::error;
+
+use crate::model::{
+    error::Error, response::field_error::FieldError,
+    response::http_response::HttpResponse,
+};
+
+/// Path parameters, rejected with the same failure body as invalid request
+/// fields.
+pub struct RequestPath<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for RequestPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = HttpResponse<()>;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let rejection = match Path::<T>::from_request_parts(parts, state).await
+        {
+            Ok(Path(value)) => return Ok(Self(value)),
+            Err(rejection) => rejection,
+        };
+
+        let message = match &rejection {
+            PathRejection::FailedToDeserializePathParams(err) => message(err),
+            rejection => rejection.body_text(),
+        };
+
+        // Each route takes a single parameter, which names the field.
+        let field = match RawPathParams::from_request_parts(parts, state).await
+        {
+            Ok(params) => params
+                .iter()
+                .map(|(key, _)| key.to_string())
+                .next()
+                .unwrap_or("path".to_string()),
+            Err(_) => "path".to_string(),
+        };
+
+        let uri = &parts.uri;
+        error!(%uri, %message, "Invalid path received for {uri}, {message}");
+
+        Err(HttpResponse::failure(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Error::validation(vec![FieldError::new(field, message)]),
+        ))
+    }
+}
+
+fn message(err: &FailedToDeserializePathParams) -> String {
+    match err.kind() {
+        ErrorKind::Message(message) => message.clone(),
+        _ => err.body_text(),
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::request::Parts,
+};
+use http::StatusCode;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tracing::error;
+
+use crate::model::{
+    error::Error, internal::validation::Validate,
+    response::field_error::FieldError, response::http_response::HttpResponse,
+};
+
+/// Query parameters, rejected with the same failure body as invalid request
+/// fields.
+pub struct RequestQuery<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for RequestQuery<T>
+where
+    T: DeserializeOwned + Validate + Send,
+    S: Send + Sync,
+{
+    type Rejection = HttpResponse<()>;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let fields = match Query::<HashMap<String, String>>::from_request_parts(
+            parts, state,
+        )
+        .await
+        {
+            Ok(Query(query)) => T::validate(&Value::Object(
+                query
+                    .into_iter()
+                    .map(|(name, value)| (name, Value::String(value)))
+                    .collect(),
+            )),
+            Err(rejection) => {
+                vec![FieldError::new("query", rejection.body_text())]
+            }
+        };
+
+        let fields = if fields.is_empty() {
+            match Query::<T>::from_request_parts(parts, state).await {
+                Ok(Query(value)) => return Ok(Self(value)),
+                Err(rejection) => {
+                    vec![FieldError::new("query", rejection.body_text())]
+                }
+            }
+        } else {
+            fields
+        };
+
+        let error = Error::validation(fields);
+        let uri = &parts.uri;
+        let error_message = error.failure_message();
+        error!(
+            %uri, %error_message,
+            "Invalid query received for {uri}, {error_message}"
+        );
+
+        Err(HttpResponse::failure(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            error,
+        ))
+    }
+}
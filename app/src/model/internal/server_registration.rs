@@ -1,36 +1,247 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 
-use crate::model::http_method::HttpMethod;
+use crate::model::{
+    graphql_operation::GraphQlOperation,
+    http_method::HttpMethod,
+    port::Port,
+    rate_limit::RateLimit,
+    request::{
+        cors_registration_request::CorsRegistrationRequest,
+        grpc_registration_request::GrpcRegistrationRequest,
+        oidc_registration_request::{OidcClient, OidcUser},
+        registration_request::RegistrationRequest,
+        socket_registration_request::{
+            SocketFraming, SocketProtocol, SocketRegistrationRequest,
+            SocketRule,
+        },
+    },
+    route_path::RoutePath,
+    stub_auth::StubAuth,
+};
 
 #[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ServerRegistration {
-    pub port: String,
+    pub port: Port,
     pub registrations: Vec<Registration>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grpc: Vec<GrpcRegistration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub socket: Option<SocketRegistration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oidc: Option<OidcRegistration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cors: Option<CorsRegistration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
 }
 
 impl ServerRegistration {
-    pub fn new(port: String, registrations: Vec<Registration>) -> Self {
+    pub fn new(
+        port: Port,
+        registrations: Vec<Registration>,
+        grpc: Vec<GrpcRegistration>,
+        socket: Option<SocketRegistration>,
+        oidc: Option<OidcRegistration>,
+        cors: Option<CorsRegistration>,
+        rate_limit: Option<RateLimit>,
+    ) -> Self {
         Self {
             port,
             registrations,
+            grpc,
+            socket,
+            oidc,
+            cors,
+            rate_limit,
         }
     }
+
+    /// Every HTTP route as a registration that can be registered again.
+    pub fn into_registration_requests(self) -> Vec<RegistrationRequest> {
+        let port = self.port;
+
+        self.registrations
+            .into_iter()
+            .map(|registration| RegistrationRequest {
+                port,
+                path: registration.path,
+                method: registration.method,
+                graphql: registration.graphql,
+                auth: registration.auth,
+                rate_limit: registration.rate_limit,
+                response: registration.response,
+            })
+            .collect()
+    }
 }
 
 #[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Registration {
     pub method: HttpMethod,
-    pub path: String,
+    pub path: RoutePath,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graphql: Option<GraphQlOperation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<StubAuth>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
     pub response: Value,
 }
 
 impl Registration {
-    pub fn new(method: HttpMethod, path: String, response: Value) -> Self {
+    pub fn new(
+        method: HttpMethod,
+        path: RoutePath,
+        graphql: Option<GraphQlOperation>,
+        auth: Option<StubAuth>,
+        rate_limit: Option<RateLimit>,
+        response: Value,
+    ) -> Self {
         Self {
             method,
             path,
+            graphql,
+            auth,
+            rate_limit,
             response,
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GrpcRegistration {
+    pub service: String,
+    pub method: String,
+    pub status: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub trailers: BTreeMap<String, String>,
+    pub response: Value,
+}
+
+impl From<GrpcRegistrationRequest> for GrpcRegistration {
+    fn from(
+        GrpcRegistrationRequest {
+            service,
+            method,
+            status,
+            message,
+            trailers,
+            response,
+            ..
+        }: GrpcRegistrationRequest,
+    ) -> Self {
+        Self {
+            service,
+            method,
+            status,
+            message,
+            trailers,
+            response,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SocketRegistration {
+    pub protocol: SocketProtocol,
+    pub framing: SocketFraming,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub banner: Option<String>,
+    pub rules: Vec<SocketRule>,
+    pub echo: bool,
+    pub capture: bool,
+}
+
+impl From<SocketRegistrationRequest> for SocketRegistration {
+    fn from(
+        SocketRegistrationRequest {
+            protocol,
+            framing,
+            banner,
+            rules,
+            echo,
+            capture,
+            ..
+        }: SocketRegistrationRequest,
+    ) -> Self {
+        Self {
+            protocol,
+            framing,
+            banner,
+            rules,
+            echo,
+            capture,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcRegistration {
+    pub issuer: String,
+    pub key_id: String,
+    pub clients: Vec<OidcClient>,
+    pub users: Vec<OidcUser>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub claims: Map<String, Value>,
+    pub token_ttl: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CorsRegistration {
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<HttpMethod>,
+    pub allowed_headers: Vec<String>,
+    pub exposed_headers: Vec<String>,
+    pub allow_credentials: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<u64>,
+}
+
+impl CorsRegistration {
+    pub fn into_registration_request(
+        self,
+        port: Port,
+    ) -> CorsRegistrationRequest {
+        CorsRegistrationRequest {
+            port,
+            allowed_origins: self.allowed_origins,
+            allowed_methods: self.allowed_methods,
+            allowed_headers: self.allowed_headers,
+            exposed_headers: self.exposed_headers,
+            allow_credentials: self.allow_credentials,
+            max_age: self.max_age,
+        }
+    }
+}
+
+impl From<CorsRegistrationRequest> for CorsRegistration {
+    fn from(
+        CorsRegistrationRequest {
+            allowed_origins,
+            allowed_methods,
+            allowed_headers,
+            exposed_headers,
+            allow_credentials,
+            max_age,
+            ..
+        }: CorsRegistrationRequest,
+    ) -> Self {
+        Self {
+            allowed_origins,
+            allowed_methods,
+            allowed_headers,
+            exposed_headers,
+            allow_credentials,
+            max_age,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    internal::server_registration::{CorsRegistration, ServerRegistration},
+    port::Port,
+    rate_limit::RateLimit,
+    request::registration_request::RegistrationRequest,
+};
+
+/// What a storage keeps of one port, its HTTP routes and the CORS policy
+/// and rate limit in front of them.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredPort {
+    pub port: Port,
+    pub registrations: Vec<RegistrationRequest>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cors: Option<CorsRegistration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl StoredPort {
+    pub fn new(port: Port) -> Self {
+        Self {
+            port,
+            registrations: vec![],
+            cors: None,
+            rate_limit: None,
+        }
+    }
+}
+
+impl From<ServerRegistration> for StoredPort {
+    fn from(server_registration: ServerRegistration) -> Self {
+        let port = server_registration.port;
+        let cors = server_registration.cors.clone();
+        let rate_limit = server_registration.rate_limit.clone();
+
+        Self {
+            port,
+            registrations: server_registration.into_registration_requests(),
+            cors,
+            rate_limit,
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use serde_json::Value;
+
+use crate::model::{
+    http_method::HttpMethod,
+    port::Port,
+    request::{
+        batch_registration_request::BatchRegistrationRequest,
+        cors_registration_request::CorsRegistrationRequest,
+        grpc_descriptor_request::GrpcDescriptorRequest,
+        grpc_registration_request::GrpcRegistrationRequest,
+        oidc_registration_request::OidcRegistrationRequest,
+        page_request::{MAX_PAGE_LIMIT, PageRequest},
+        peer_update_request::PeerUpdateRequest,
+        rate_limit_registration_request::RateLimitRegistrationRequest,
+        registration_request::RegistrationRequest,
+        route_filter::RouteFilter,
+        socket_registration_request::SocketRegistrationRequest,
+    },
+    response::field_error::FieldError,
+    route_path::RoutePath,
+};
+
+/// Checks a raw request body before it is deserialized, so that every
+/// invalid field is reported at once instead of only the first one.
+pub trait Validate {
+    fn validate(body: &Value) -> Vec<FieldError>;
+}
+
+impl Validate for RegistrationRequest {
+    fn validate(body: &Value) -> Vec<FieldError> {
+        let mut errors = validate_port(body, "port");
+        errors.extend(validate_path(body, "path"));
+        errors
+    }
+}
+
+impl Validate for BatchRegistrationRequest {
+    fn validate(body: &Value) -> Vec<FieldError> {
+        let Some(registrations) =
+            body.get("registrations").and_then(Value::as_array)
+        else {
+            return vec![];
+        };
+
+        registrations
+            .iter()
+            .enumerate()
+            .flat_map(|(index, registration)| {
+                RegistrationRequest::validate(registration).into_iter().map(
+                    move |FieldError { field, message }| {
+                        FieldError::new(
+                            format!("registrations[{index}].{field}"),
+                            message,
+                        )
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+impl Validate for PeerUpdateRequest {
+    fn validate(body: &Value) -> Vec<FieldError> {
+        let Some(registrations) =
+            body.get("registrations").and_then(Value::as_array)
+        else {
+            return vec![];
+        };
+
+        // The port is taken from the URL, the one in the body is ignored.
+        registrations
+            .iter()
+            .enumerate()
+            .flat_map(|(index, registration)| {
+                validate_path(registration, "path").into_iter().map(
+                    move |FieldError { field, message }| {
+                        FieldError::new(
+                            format!("registrations[{index}].{field}"),
+                            message,
+                        )
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+impl Validate for CorsRegistrationRequest {
+    fn validate(body: &Value) -> Vec<FieldError> {
+        validate_port(body, "port")
+    }
+}
+
+impl Validate for GrpcDescriptorRequest {
+    fn validate(body: &Value) -> Vec<FieldError> {
+        validate_port(body, "port")
+    }
+}
+
+impl Validate for GrpcRegistrationRequest {
+    fn validate(body: &Value) -> Vec<FieldError> {
+        validate_port(body, "port")
+    }
+}
+
+impl Validate for OidcRegistrationRequest {
+    fn validate(body: &Value) -> Vec<FieldError> {
+        validate_port(body, "port")
+    }
+}
+
+impl Validate for RateLimitRegistrationRequest {
+    fn validate(body: &Value) -> Vec<FieldError> {
+        validate_port(body, "port")
+    }
+}
+
+impl Validate for SocketRegistrationRequest {
+    fn validate(body: &Value) -> Vec<FieldError> {
+        validate_port(body, "port")
+    }
+}
+
+impl Validate for PageRequest {
+    fn validate(query: &Value) -> Vec<FieldError> {
+        let mut errors = validate_count(query, "offset", usize::MAX);
+        errors.extend(validate_count(query, "limit", MAX_PAGE_LIMIT));
+        errors
+    }
+}
+
+impl Validate for RouteFilter {
+    fn validate(query: &Value) -> Vec<FieldError> {
+        query
+            .get("method")
+            .and_then(|method| {
+                serde_json::from_value::<HttpMethod>(method.clone()).err()
+            })
+            .map(|err| FieldError::new("method", err.to_string()))
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Missing fields are left to deserialization, which reports them.
+fn validate_port(body: &Value, field: &str) -> Vec<FieldError> {
+    body.get(field)
+        .and_then(|port| Port::from_json(port).err())
+        .map(|message| FieldError::new(field, message))
+        .into_iter()
+        .collect()
+}
+
+fn validate_path(body: &Value, field: &str) -> Vec<FieldError> {
+    let error = match body.get(field) {
+        Some(Value::String(path)) => path.parse::<RoutePath>().err(),
+        Some(path) => {
+            Some(format!("`{path}` is not a path, expected a string"))
+        }
+        None => None,
+    };
+
+    error
+        .map(|message| FieldError::new(field, message))
+        .into_iter()
+        .collect()
+}
+
+fn validate_count(query: &Value, field: &str, max: usize) -> Vec<FieldError> {
+    let error = match query.get(field) {
+        Some(Value::String(count)) => match count.parse::<usize>() {
+            Ok(count) if count > max => {
+                Some(format!("`{count}` exceeds the maximum of {max}"))
+            }
+            Ok(_) => None,
+            Err(_) => Some(format!(
+                "`{count}` is not a count, expected a non-negative number"
+            )),
+        },
+        Some(count) => Some(format!(
+            "`{count}` is not a count, expected a non-negative number"
+        )),
+        None => None,
+    };
+
+    error
+        .map(|message| FieldError::new(field, message))
+        .into_iter()
+        .collect()
+}
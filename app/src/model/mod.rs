@@ -1,5 +1,11 @@
 pub mod error;
+pub mod graphql_operation;
 pub mod http_method;
 pub mod internal;
+pub mod port;
+pub mod port_version;
+pub mod rate_limit;
 pub mod request;
 pub mod response;
+pub mod route_path;
+pub mod stub_auth;
@@ -0,0 +1,76 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize, de::Error};
+use serde_json::Value;
+
+/// A TCP/UDP port, zero asks for any free port.
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy, Debug, Default)]
+pub struct Port(u16);
+
+impl Port {
+    pub const EPHEMERAL: Port = Port(0);
+
+    pub const fn new(number: u16) -> Self {
+        Self(number)
+    }
+
+    pub fn number(&self) -> u16 {
+        self.0
+    }
+
+    pub fn is_ephemeral(&self) -> bool {
+        *self == Self::EPHEMERAL
+    }
+
+    /// Parses a port given either as a string or as a number.
+    pub fn from_json(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::String(port) => port.parse(),
+            Value::Number(number) => number.to_string().parse(),
+            _ => Err(format!(
+                "`{value}` is not a port, expected a number between 1 and 65535"
+            )),
+        }
+    }
+}
+
+impl FromStr for Port {
+    type Err = String;
+
+    fn from_str(port: &str) -> Result<Self, Self::Err> {
+        if port.is_empty() {
+            return Ok(Self::EPHEMERAL);
+        }
+
+        port.parse().map(Self).map_err(|_| {
+            format!(
+                "`{port}` is not a port, expected a number between 1 and 65535"
+            )
+        })
+    }
+}
+
+impl fmt::Display for Port {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.0)
+    }
+}
+
+impl Serialize for Port {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Port {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Port::from_json(&Value::deserialize(deserializer)?)
+            .map_err(Error::custom)
+    }
+}
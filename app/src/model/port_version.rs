@@ -0,0 +1,37 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Orders the changes peers make to the same port.
+///
+/// A change counts on from the highest version its instance has seen, two
+/// changes made at the same time are ordered by the instance they came from.
+#[derive(
+    Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub struct PortVersion {
+    pub counter: u64,
+    pub origin: String,
+}
+
+impl PortVersion {
+    pub fn new(counter: u64, origin: &str) -> Self {
+        Self {
+            counter,
+            origin: origin.to_string(),
+        }
+    }
+
+    /// The version of a change `origin` makes after seeing `current`.
+    pub fn next(current: Option<&PortVersion>, origin: &str) -> Self {
+        let counter = current.map_or(0, |current| current.counter);
+
+        Self::new(counter + 1, origin)
+    }
+}
+
+impl fmt::Display for PortVersion {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}@{}", self.counter, self.origin)
+    }
+}
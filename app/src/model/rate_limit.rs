@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimit {
+    #[serde(flatten)]
+    pub strategy: RateLimitStrategy,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_header: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(tag = "strategy", rename_all = "camelCase")]
+pub enum RateLimitStrategy {
+    #[serde(rename_all = "camelCase")]
+    TokenBucket {
+        capacity: u32,
+        refill_per_second: f64,
+    },
+    #[serde(rename_all = "camelCase")]
+    FixedWindow { limit: u32, window_seconds: u64 },
+}
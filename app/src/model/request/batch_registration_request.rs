@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::request::registration_request::RegistrationRequest;
+
+#[derive(Serialize, Deserialize)]
+pub struct BatchRegistrationRequest {
+    pub registrations: Vec<RegistrationRequest>,
+}
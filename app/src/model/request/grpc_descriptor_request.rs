@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::port::Port;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GrpcDescriptorRequest {
+    pub port: Port,
+    pub descriptor_set: String,
+}
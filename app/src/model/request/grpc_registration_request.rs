@@ -0,0 +1,21 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::model::port::Port;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GrpcRegistrationRequest {
+    pub port: Port,
+    pub service: String,
+    pub method: String,
+    #[serde(default)]
+    pub status: u32,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub trailers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub response: Value,
+}
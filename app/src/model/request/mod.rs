@@ -1 +1,12 @@
+pub mod batch_registration_request;
+pub mod cors_registration_request;
+pub mod grpc_descriptor_request;
+pub mod grpc_registration_request;
+pub mod oidc_registration_request;
+pub mod page_request;
+pub mod peer_update_request;
+pub mod rate_limit_registration_request;
 pub mod registration_request;
+pub mod reset_request;
+pub mod route_filter;
+pub mod socket_registration_request;
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::model::port::Port;
+
+const DEFAULT_TOKEN_TTL: u64 = 3600;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcRegistrationRequest {
+    pub port: Port,
+    #[serde(default)]
+    pub issuer: Option<String>,
+    #[serde(default)]
+    pub clients: Vec<OidcClient>,
+    #[serde(default)]
+    pub users: Vec<OidcUser>,
+    #[serde(default)]
+    pub claims: Map<String, Value>,
+    #[serde(default = "default_token_ttl")]
+    pub token_ttl: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcClient {
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OidcUser {
+    pub username: String,
+    pub password: String,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub claims: Map<String, Value>,
+}
+
+fn default_token_ttl() -> u64 {
+    DEFAULT_TOKEN_TTL
+}
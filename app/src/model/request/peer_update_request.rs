@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    port_version::PortVersion,
+    request::registration_request::RegistrationRequest,
+};
+
+/// Every HTTP route a peer serves on one port after a change.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PeerUpdateRequest {
+    pub version: PortVersion,
+    /// Missing once the peer removed the server on the port.
+    #[serde(default)]
+    pub registrations: Option<Vec<RegistrationRequest>>,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{port::Port, rate_limit::RateLimit};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RateLimitRegistrationRequest {
+    pub port: Port,
+    #[serde(flatten)]
+    pub rate_limit: RateLimit,
+}
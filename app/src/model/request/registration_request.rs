@@ -1,12 +1,23 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::model::http_method::HttpMethod;
+use crate::model::{
+    graphql_operation::GraphQlOperation, http_method::HttpMethod, port::Port,
+    rate_limit::RateLimit, route_path::RoutePath, stub_auth::StubAuth,
+};
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct RegistrationRequest {
-    pub port: String,
-    pub path: String,
+    #[serde(default)]
+    pub port: Port,
+    pub path: RoutePath,
     pub method: HttpMethod,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graphql: Option<GraphQlOperation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<StubAuth>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
     pub response: Value,
 }
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetRequest {
+    #[serde(default = "keep_pinned_by_default")]
+    pub keep_pinned: bool,
+}
+
+fn keep_pinned_by_default() -> bool {
+    true
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::http_method::HttpMethod;
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteFilter {
+    #[serde(default)]
+    pub method: Option<HttpMethod>,
+    /// Either a registered route or a concrete path it would answer.
+    #[serde(default)]
+    pub path: Option<String>,
+}
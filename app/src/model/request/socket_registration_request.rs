@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::port::Port;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SocketRegistrationRequest {
+    pub port: Port,
+    pub protocol: SocketProtocol,
+    #[serde(default)]
+    pub framing: SocketFraming,
+    #[serde(default)]
+    pub banner: Option<String>,
+    #[serde(default)]
+    pub rules: Vec<SocketRule>,
+    #[serde(default)]
+    pub echo: bool,
+    #[serde(default)]
+    pub capture: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SocketProtocol {
+    Tcp,
+    Udp,
+}
+
+#[derive(
+    Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default,
+)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SocketFraming {
+    #[default]
+    Line,
+    Raw,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SocketRule {
+    pub pattern: String,
+    pub reply: String,
+    #[serde(default)]
+    pub close: bool,
+}
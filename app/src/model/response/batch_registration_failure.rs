@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    error::Error, graphql_operation::GraphQlOperation, http_method::HttpMethod,
+    port::Port, request::registration_request::RegistrationRequest,
+    route_path::RoutePath,
+};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRegistrationFailure {
+    pub index: usize,
+    pub port: Port,
+    pub method: HttpMethod,
+    pub path: RoutePath,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graphql: Option<GraphQlOperation>,
+    pub failure_type: String,
+    pub failure_message: String,
+}
+
+impl BatchRegistrationFailure {
+    pub fn new(
+        index: usize,
+        registration_request: &RegistrationRequest,
+        error: &Error,
+    ) -> Self {
+        Self {
+            index,
+            port: registration_request.port,
+            method: registration_request.method.clone(),
+            path: registration_request.path.clone(),
+            graphql: registration_request.graphql.clone(),
+            failure_type: error.failure_type().to_string(),
+            failure_message: error.failure_message().to_string(),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::response::registration_response::RegistrationResponse;
+
+#[derive(Serialize, Deserialize)]
+pub struct BatchRegistrationResponse {
+    pub registrations: Vec<RegistrationResponse>,
+}
+
+impl BatchRegistrationResponse {
+    pub fn new(registrations: Vec<RegistrationResponse>) -> Self {
+        Self { registrations }
+    }
+}
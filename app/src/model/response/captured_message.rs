@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedMessage {
+    pub peer: String,
+    pub received_at: u128,
+    pub data: String,
+    pub base64: String,
+}
+
+impl CapturedMessage {
+    pub fn new(
+        peer: String,
+        received_at: u128,
+        data: String,
+        base64: String,
+    ) -> Self {
+        Self {
+            peer,
+            received_at,
+            data,
+            base64,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    internal::server_registration::CorsRegistration,
+    request::cors_registration_request::CorsRegistrationRequest,
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct CorsRegistrationResponse {
+    pub added: CorsRegistration,
+    pub removed: Option<CorsRegistration>,
+}
+
+impl CorsRegistrationResponse {
+    pub fn new(
+        registration_request: CorsRegistrationRequest,
+        removed_registration: Option<CorsRegistration>,
+    ) -> Self {
+        Self {
+            added: CorsRegistration::from(registration_request),
+            removed: removed_registration,
+        }
+    }
+}
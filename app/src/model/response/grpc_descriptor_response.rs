@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::port::Port;
+
+#[derive(Serialize, Deserialize)]
+pub struct GrpcDescriptorResponse {
+    pub port: Port,
+    pub services: Vec<GrpcService>,
+}
+
+impl GrpcDescriptorResponse {
+    pub fn new(port: Port, services: Vec<GrpcService>) -> Self {
+        Self { port, services }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GrpcService {
+    pub name: String,
+    pub methods: Vec<GrpcMethod>,
+}
+
+impl GrpcService {
+    pub fn new(name: String, methods: Vec<GrpcMethod>) -> Self {
+        Self { name, methods }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrpcMethod {
+    pub name: String,
+    pub input_type: String,
+    pub output_type: String,
+    pub unary: bool,
+}
+
+impl GrpcMethod {
+    pub fn new(
+        name: String,
+        input_type: String,
+        output_type: String,
+        unary: bool,
+    ) -> Self {
+        Self {
+            name,
+            input_type,
+            output_type,
+            unary,
+        }
+    }
+}
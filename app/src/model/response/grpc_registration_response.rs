@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    internal::server_registration::GrpcRegistration,
+    request::grpc_registration_request::GrpcRegistrationRequest,
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct GrpcRegistrationResponse {
+    pub added: GrpcRegistration,
+    pub removed: Option<GrpcRegistration>,
+}
+
+impl GrpcRegistrationResponse {
+    pub fn new(
+        registration_request: GrpcRegistrationRequest,
+        removed_registration: Option<GrpcRegistration>,
+    ) -> Self {
+        Self {
+            added: GrpcRegistration::from(registration_request),
+            removed: removed_registration,
+        }
+    }
+}
@@ -5,11 +5,13 @@ use axum::{
 use http::StatusCode;
 use serde::Serialize;
 
-use crate::model::error::Error;
+use crate::{logging::metrics::FailedRegistration, model::error::Error};
 
 pub enum HttpResponse<T: Serialize> {
     Success(StatusCode, Json<T>),
     Failure(StatusCode, Error),
+    /// A failure that is counted as a rejected registration.
+    RegistrationFailure(StatusCode, Error),
 }
 
 impl<T: Serialize> HttpResponse<T> {
@@ -20,6 +22,10 @@ impl<T: Serialize> HttpResponse<T> {
     pub fn failure(status_code: StatusCode, error: Error) -> Self {
         Self::Failure(status_code, error)
     }
+
+    pub fn registration_failure(status_code: StatusCode, error: Error) -> Self {
+        Self::RegistrationFailure(status_code, error)
+    }
 }
 
 impl<T: Serialize> IntoResponse for HttpResponse<T> {
@@ -31,6 +37,15 @@ impl<T: Serialize> IntoResponse for HttpResponse<T> {
             Self::Failure(status_code, error) => {
                 (status_code, error).into_response()
             }
+            Self::RegistrationFailure(status_code, error) => {
+                let failure_type = error.failure_type();
+
+                let mut response = (status_code, error).into_response();
+                response
+                    .extensions_mut()
+                    .insert(FailedRegistration(failure_type));
+                response
+            }
         }
     }
 }
@@ -1,2 +1,18 @@
+pub mod batch_registration_failure;
+pub mod batch_registration_response;
+pub mod captured_message;
+pub mod cors_registration_response;
+pub mod field_error;
+pub mod grpc_descriptor_response;
+pub mod grpc_registration_response;
 pub mod http_response;
+pub mod oidc_registration_response;
+pub mod page;
+pub mod peer_state_response;
+pub mod peer_update_response;
+pub mod rate_limit_counter;
+pub mod rate_limit_registration_response;
 pub mod registration_response;
+pub mod reset_response;
+pub mod server_summary;
+pub mod socket_registration_response;
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::internal::server_registration::OidcRegistration;
+
+#[derive(Serialize, Deserialize)]
+pub struct OidcRegistrationResponse {
+    pub added: OidcRegistration,
+    pub removed: Option<OidcRegistration>,
+}
+
+impl OidcRegistrationResponse {
+    pub fn new(
+        added_registration: OidcRegistration,
+        removed_registration: Option<OidcRegistration>,
+    ) -> Self {
+        Self {
+            added: added_registration,
+            removed: removed_registration,
+        }
+    }
+}
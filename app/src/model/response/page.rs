@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::request::page_request::PageRequest;
+
+#[derive(Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl<T> Page<T> {
+    pub fn new(
+        items: Vec<T>,
+        PageRequest { offset, limit }: PageRequest,
+    ) -> Self {
+        let total = items.len();
+        let items = items.into_iter().skip(offset).take(limit).collect();
+
+        Self {
+            items,
+            total,
+            offset,
+            limit,
+        }
+    }
+}
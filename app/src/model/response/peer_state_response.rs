@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    port::Port, request::peer_update_request::PeerUpdateRequest,
+};
+
+/// The latest known change to one port, pulled by a peer that joins the
+/// cluster after the change was broadcast.
+#[derive(Serialize, Deserialize)]
+pub struct PeerStateResponse {
+    pub port: Port,
+    #[serde(flatten)]
+    pub update: PeerUpdateRequest,
+}
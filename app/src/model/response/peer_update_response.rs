@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{port::Port, port_version::PortVersion};
+
+#[derive(Serialize, Deserialize)]
+pub struct PeerUpdateResponse {
+    pub port: Port,
+    pub version: PortVersion,
+}
+
+impl PeerUpdateResponse {
+    pub fn new(port: Port, version: PortVersion) -> Self {
+        Self { port, version }
+    }
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    graphql_operation::GraphQlOperation, http_method::HttpMethod,
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitCounter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<HttpMethod>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graphql: Option<GraphQlOperation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset_after: u64,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::rate_limit::RateLimit;
+
+#[derive(Serialize, Deserialize)]
+pub struct RateLimitRegistrationResponse {
+    pub added: RateLimit,
+    pub removed: Option<RateLimit>,
+}
+
+impl RateLimitRegistrationResponse {
+    pub fn new(added: RateLimit, removed: Option<RateLimit>) -> Self {
+        Self { added, removed }
+    }
+}
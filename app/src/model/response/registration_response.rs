@@ -1,25 +1,31 @@
 use serde::{Deserialize, Serialize};
 
 use crate::model::{
-    internal::server_registration::Registration,
+    internal::server_registration::Registration, port::Port,
     request::registration_request::RegistrationRequest,
 };
 
 #[derive(Serialize, Deserialize)]
 pub struct RegistrationResponse {
+    pub port: Port,
     pub added: Registration,
     pub removed: Option<Registration>,
 }
 
 impl RegistrationResponse {
     pub fn new(
+        port: Port,
         registration_request: RegistrationRequest,
         removed_registration: Option<Registration>,
     ) -> Self {
         Self {
+            port,
             added: Registration::new(
                 registration_request.method,
                 registration_request.path,
+                registration_request.graphql,
+                registration_request.auth,
+                registration_request.rate_limit,
                 registration_request.response,
             ),
             removed: removed_registration,
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::internal::server_registration::{
+    Registration, ServerRegistration,
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct ResetResponse {
+    pub removed: Vec<ServerRegistration>,
+    pub pinned: Vec<Registration>,
+}
+
+impl ResetResponse {
+    pub fn new(
+        removed: Vec<ServerRegistration>,
+        pinned: Vec<Registration>,
+    ) -> Self {
+        Self { removed, pinned }
+    }
+}
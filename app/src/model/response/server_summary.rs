@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    internal::server_registration::ServerRegistration, port::Port,
+};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerSummary {
+    pub port: Port,
+    pub routes: usize,
+    pub grpc: usize,
+    pub socket: bool,
+    pub oidc: bool,
+    pub cors: bool,
+    pub rate_limit: bool,
+}
+
+impl From<&ServerRegistration> for ServerSummary {
+    fn from(server_registration: &ServerRegistration) -> Self {
+        Self {
+            port: server_registration.port,
+            routes: server_registration.registrations.len(),
+            grpc: server_registration.grpc.len(),
+            socket: server_registration.socket.is_some(),
+            oidc: server_registration.oidc.is_some(),
+            cors: server_registration.cors.is_some(),
+            rate_limit: server_registration.rate_limit.is_some(),
+        }
+    }
+}
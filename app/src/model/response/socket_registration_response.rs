@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    internal::server_registration::SocketRegistration,
+    request::socket_registration_request::SocketRegistrationRequest,
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct SocketRegistrationResponse {
+    pub added: SocketRegistration,
+    pub removed: Option<SocketRegistration>,
+}
+
+impl SocketRegistrationResponse {
+    pub fn new(
+        registration_request: SocketRegistrationRequest,
+        removed_registration: Option<SocketRegistration>,
+    ) -> Self {
+        Self {
+            added: SocketRegistration::from(registration_request),
+            removed: removed_registration,
+        }
+    }
+}
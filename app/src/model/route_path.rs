@@ -0,0 +1,116 @@
+use std::{fmt, ops::Deref, str::FromStr};
+
+use serde::{Deserialize, Serialize, de::Error};
+
+/// A normalized stub route path that axum accepts without panicking.
+///
+/// Duplicate and trailing slashes are dropped and percent-encodings are
+/// canonicalized, so `/users//{id}/` and `/users/{id}` are the same route.
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Debug)]
+pub struct RoutePath(String);
+
+impl RoutePath {
+    pub fn to_lowercase(&self) -> Self {
+        Self(self.0.to_lowercase())
+    }
+}
+
+impl FromStr for RoutePath {
+    type Err = String;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        if !path.starts_with('/') {
+            return Err(format!("`{path}` must start with `/`"));
+        }
+
+        if path.chars().any(char::is_whitespace) {
+            return Err(format!("`{path}` must not contain whitespace"));
+        }
+
+        let normalized = normalize_path(path)
+            .ok_or(format!("`{path}` contains an invalid percent-encoding"))?;
+
+        if normalized
+            .split('/')
+            .any(|segment| segment.starts_with(':') || segment.starts_with('*'))
+        {
+            return Err(format!(
+                "`{path}` uses `:name` or `*name` captures, use `{{name}}` or `{{*name}}` instead"
+            ));
+        }
+
+        matchit::Router::new()
+            .insert(&normalized, ())
+            .map_err(|err| format!("`{path}` is not a valid route, {err}"))?;
+
+        Ok(Self(normalized))
+    }
+}
+
+/// Collapses duplicate slashes, drops a trailing slash, decodes
+/// percent-encoded unreserved characters and upper-cases the remaining
+/// escapes. Returns `None` for a malformed percent-encoding.
+pub fn normalize_path(path: &str) -> Option<String> {
+    let mut decoded = String::with_capacity(path.len());
+    let mut chars = path.chars();
+
+    while let Some(char) = chars.next() {
+        if char != '%' {
+            decoded.push(char);
+            continue;
+        }
+
+        let escape = chars.by_ref().take(2).collect::<String>();
+        if escape.len() != 2 || !escape.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let byte = u8::from_str_radix(&escape, 16).ok()?;
+
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            decoded.push(char::from(byte));
+        } else {
+            decoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    let segments = decoded
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+
+    Some(format!("/{}", segments.join("/")))
+}
+
+impl Deref for RoutePath {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for RoutePath {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.0)
+    }
+}
+
+impl Serialize for RoutePath {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RoutePath {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(Error::custom)
+    }
+}
@@ -0,0 +1,148 @@
+use std::{fmt, sync::Arc};
+
+use axum::{
+    Router,
+    extract::Request,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use base64::{Engine, prelude::BASE64_STANDARD};
+use http::{
+    HeaderValue, StatusCode,
+    header::{AUTHORIZATION, WWW_AUTHENTICATE},
+};
+use tracing::warn;
+
+use crate::{
+    model::{error::Error, response::http_response::HttpResponse},
+    security::credentials::{basic_matches, bearer_matches},
+};
+
+const REDACTED: &str = "<redacted>";
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum AdminAuth {
+    Bearer(String),
+    Basic { username: String, password: String },
+}
+
+impl fmt::Debug for AdminAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdminAuth::Bearer(_) => {
+                f.debug_tuple("Bearer").field(&REDACTED).finish()
+            }
+            AdminAuth::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .field("password", &REDACTED)
+                .finish(),
+        }
+    }
+}
+
+impl AdminAuth {
+    pub fn bearer(token: &str) -> Result<Self, String> {
+        if token.trim().is_empty() {
+            return Err("The admin token must not be empty".to_string());
+        }
+
+        Ok(AdminAuth::Bearer(token.to_string()))
+    }
+
+    pub fn basic(credentials: &str) -> Result<Self, String> {
+        match credentials.split_once(':') {
+            Some((username, password))
+                if !username.is_empty() && !password.is_empty() =>
+            {
+                Ok(AdminAuth::Basic {
+                    username: username.to_string(),
+                    password: password.to_string(),
+                })
+            }
+            _ => Err(
+                "Basic auth credentials must be of the form `username:password`"
+                    .to_string(),
+            ),
+        }
+    }
+
+    /// The `Authorization` header value a client sends to pass this check.
+    pub fn authorization(&self) -> String {
+        match self {
+            AdminAuth::Bearer(token) => format!("Bearer {token}"),
+            AdminAuth::Basic { username, password } => {
+                let credentials =
+                    BASE64_STANDARD.encode(format!("{username}:{password}"));
+                format!("Basic {credentials}")
+            }
+        }
+    }
+
+    fn authorize(&self, authorization: Option<&HeaderValue>) -> bool {
+        match self {
+            AdminAuth::Bearer(token) => bearer_matches(authorization, token),
+            AdminAuth::Basic { username, password } => {
+                basic_matches(authorization, username, password)
+            }
+        }
+    }
+
+    fn challenge(&self) -> HeaderValue {
+        match self {
+            AdminAuth::Bearer(_) => HeaderValue::from_static("Bearer"),
+            AdminAuth::Basic { .. } => {
+                HeaderValue::from_static("Basic realm=\"api-gen\"")
+            }
+        }
+    }
+}
+
+pub trait AdminAuthMiddleware<S> {
+    fn with_admin_auth(self, admin_auth: Option<AdminAuth>) -> Router<S>;
+}
+
+impl<S: Clone + Send + Sync + 'static> AdminAuthMiddleware<S> for Router<S> {
+    fn with_admin_auth(self, admin_auth: Option<AdminAuth>) -> Router<S> {
+        match admin_auth {
+            Some(admin_auth) => {
+                let admin_auth = Arc::new(admin_auth);
+
+                self.route_layer(middleware::from_fn(
+                    move |request: Request, next: Next| {
+                        let admin_auth = admin_auth.clone();
+                        async move { authenticate(&admin_auth, request, next).await }
+                    },
+                ))
+            }
+            None => self,
+        }
+    }
+}
+
+async fn authenticate(
+    admin_auth: &AdminAuth,
+    request: Request,
+    next: Next,
+) -> Response {
+    if admin_auth.authorize(request.headers().get(AUTHORIZATION)) {
+        return next.run(request).await;
+    }
+
+    let method = request.method().to_string();
+    let uri = request.uri().to_string();
+    warn!(%method, %uri, "Rejected unauthenticated admin request [{method}]({uri}).");
+
+    let mut response = HttpResponse::<()>::failure(
+        StatusCode::UNAUTHORIZED,
+        Error::Unauthorized(
+            "Missing or invalid credentials for the admin API".to_string(),
+        ),
+    )
+    .into_response();
+    response
+        .headers_mut()
+        .insert(WWW_AUTHENTICATE, admin_auth.challenge());
+
+    response
+}
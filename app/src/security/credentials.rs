@@ -0,0 +1,58 @@
+use base64::{Engine, prelude::BASE64_STANDARD};
+use http::HeaderValue;
+
+pub(super) fn bearer_token(
+    authorization: Option<&HeaderValue>,
+) -> Option<&str> {
+    authorization
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+pub(super) fn bearer_matches(
+    authorization: Option<&HeaderValue>,
+    token: &str,
+) -> bool {
+    bearer_token(authorization)
+        .is_some_and(|provided| constant_time_eq(provided, token))
+}
+
+pub(super) fn basic_matches(
+    authorization: Option<&HeaderValue>,
+    username: &str,
+    password: &str,
+) -> bool {
+    basic_credentials(authorization).is_some_and(
+        |(provided_username, provided_password)| {
+            constant_time_eq(&provided_username, username)
+                & constant_time_eq(&provided_password, password)
+        },
+    )
+}
+
+pub(crate) fn basic_credentials(
+    authorization: Option<&HeaderValue>,
+) -> Option<(String, String)> {
+    authorization
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Basic "))
+        .and_then(|encoded| BASE64_STANDARD.decode(encoded).ok())
+        .and_then(|decoded| String::from_utf8(decoded).ok())
+        .and_then(|decoded| {
+            decoded.split_once(':').map(|(username, password)| {
+                (username.to_string(), password.to_string())
+            })
+        })
+}
+
+pub(crate) fn constant_time_eq(provided: &str, expected: &str) -> bool {
+    let provided = provided.as_bytes();
+    let expected = expected.as_bytes();
+
+    provided.len() == expected.len()
+        && provided
+            .iter()
+            .zip(expected)
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
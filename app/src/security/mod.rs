@@ -0,0 +1,3 @@
+pub mod admin_auth;
+pub(crate) mod credentials;
+pub mod stub_auth;
@@ -0,0 +1,268 @@
+use std::{fs, sync::Arc};
+
+use axum::response::{IntoResponse, Response};
+use http::{
+    HeaderMap, HeaderName, HeaderValue, StatusCode,
+    header::{AUTHORIZATION, WWW_AUTHENTICATE},
+};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, Validation, decode, decode_header, jwk::JwkSet,
+};
+use serde_json::{Map, Value};
+use tracing::warn;
+
+use crate::{
+    model::{
+        error::Error, response::http_response::HttpResponse,
+        stub_auth::StubAuth,
+    },
+    security::credentials::{basic_matches, bearer_matches, bearer_token},
+};
+
+const HMAC_ALGORITHMS: [Algorithm; 3] =
+    [Algorithm::HS256, Algorithm::HS384, Algorithm::HS512];
+
+pub enum AuthFailure {
+    Unauthenticated(&'static str, String),
+    Forbidden(String),
+}
+
+impl IntoResponse for AuthFailure {
+    fn into_response(self) -> Response {
+        match self {
+            AuthFailure::Unauthenticated(challenge, message) => {
+                warn!(%message, "Rejected unauthenticated stub request, {message}.");
+
+                let mut response = HttpResponse::<()>::failure(
+                    StatusCode::UNAUTHORIZED,
+                    Error::Unauthorized(message),
+                )
+                .into_response();
+                response.headers_mut().insert(
+                    WWW_AUTHENTICATE,
+                    HeaderValue::from_static(challenge),
+                );
+
+                response
+            }
+            AuthFailure::Forbidden(message) => {
+                warn!(%message, "Rejected unauthorized stub request, {message}.");
+
+                HttpResponse::<()>::failure(
+                    StatusCode::FORBIDDEN,
+                    Error::Forbidden(message),
+                )
+                .into_response()
+            }
+        }
+    }
+}
+
+/// Checks requests against a stub's `auth` block.
+///
+/// Everything a check needs is loaded when the route is registered, so a bad
+/// JWKS file fails the registration instead of every request.
+#[derive(Clone)]
+pub struct StubAuthenticator {
+    auth: StubAuth,
+    jwks: Arc<[(Option<String>, DecodingKey)]>,
+}
+
+impl StubAuthenticator {
+    pub fn new(auth: StubAuth) -> Result<Self, Error> {
+        let jwks = match &auth {
+            StubAuth::Bearer { .. } | StubAuth::Basic { .. } => vec![],
+            StubAuth::ApiKey { header, .. } => {
+                HeaderName::try_from(header).map_err(|err| {
+                    Error::Auth(format!(
+                        "Invalid API key header `{header}`, {err}"
+                    ))
+                })?;
+                vec![]
+            }
+            StubAuth::Jwt {
+                secret, jwks_file, ..
+            } => match (secret, jwks_file) {
+                (Some(_), None) => vec![],
+                (None, Some(jwks_file)) => load_jwks(jwks_file)?,
+                _ => {
+                    return Err(Error::Auth(
+                        "Exactly one of `secret` and `jwksFile` must be configured for JWT auth"
+                            .to_string(),
+                    ));
+                }
+            },
+        };
+
+        Ok(Self {
+            auth,
+            jwks: jwks.into(),
+        })
+    }
+
+    pub fn validate(auth: &StubAuth) -> Result<(), Error> {
+        Self::new(auth.clone()).map(|_| ())
+    }
+
+    pub fn get_auth(&self) -> &StubAuth {
+        &self.auth
+    }
+
+    pub fn verify(&self, headers: &HeaderMap) -> Result<(), AuthFailure> {
+        let authorization = headers.get(AUTHORIZATION);
+
+        match &self.auth {
+            StubAuth::Bearer { token } => {
+                if bearer_matches(authorization, token) {
+                    Ok(())
+                } else {
+                    Err(AuthFailure::Unauthenticated(
+                        "Bearer",
+                        "Missing or invalid bearer token".to_string(),
+                    ))
+                }
+            }
+            StubAuth::Basic { username, password } => {
+                if basic_matches(authorization, username, password) {
+                    Ok(())
+                } else {
+                    Err(AuthFailure::Unauthenticated(
+                        "Basic realm=\"api-gen\"",
+                        "Missing or invalid basic auth credentials".to_string(),
+                    ))
+                }
+            }
+            StubAuth::ApiKey { header, value } => {
+                let provided = headers
+                    .get(header.as_str())
+                    .and_then(|provided| provided.to_str().ok());
+
+                match provided {
+                    Some(provided) if provided == value => Ok(()),
+                    _ => Err(AuthFailure::Unauthenticated(
+                        "ApiKey",
+                        format!("Missing or invalid API key in `{header}`"),
+                    )),
+                }
+            }
+            StubAuth::Jwt { secret, claims, .. } => {
+                let token = bearer_token(authorization).ok_or_else(|| {
+                    AuthFailure::Unauthenticated(
+                        "Bearer",
+                        "Missing bearer token".to_string(),
+                    )
+                })?;
+
+                let token_claims =
+                    decode_jwt(token, secret.as_deref(), &self.jwks).map_err(
+                        |message| {
+                            AuthFailure::Unauthenticated("Bearer", message)
+                        },
+                    )?;
+
+                verify_claims(claims, &token_claims)
+            }
+        }
+    }
+}
+
+/// Reads the keys of `jwks_file` along with their key ids.
+fn load_jwks(
+    jwks_file: &str,
+) -> Result<Vec<(Option<String>, DecodingKey)>, Error> {
+    let jwks = fs::read_to_string(jwks_file).map_err(|err| {
+        Error::Auth(format!("Failed to read JWKS file `{jwks_file}`, {err}"))
+    })?;
+
+    let jwks = serde_json::from_str::<JwkSet>(&jwks).map_err(|err| {
+        Error::Auth(format!("Failed to parse JWKS file `{jwks_file}`, {err}"))
+    })?;
+
+    if jwks.keys.is_empty() {
+        return Err(Error::Auth(format!(
+            "JWKS file `{jwks_file}` does not contain any keys"
+        )));
+    }
+
+    jwks.keys
+        .iter()
+        .map(|jwk| {
+            let kid = jwk.common.key_id.clone();
+            DecodingKey::from_jwk(jwk)
+                .map(|key| (kid, key))
+                .map_err(|err| {
+                    Error::Auth(format!(
+                        "Unusable key in JWKS file `{jwks_file}`, {err}"
+                    ))
+                })
+        })
+        .collect()
+}
+
+fn decode_jwt(
+    token: &str,
+    secret: Option<&str>,
+    jwks: &[(Option<String>, DecodingKey)],
+) -> Result<Map<String, Value>, String> {
+    let header =
+        decode_header(token).map_err(|err| format!("Invalid token, {err}"))?;
+
+    let key = match secret {
+        Some(secret) => {
+            if !HMAC_ALGORITHMS.contains(&header.alg) {
+                return Err(format!(
+                    "Token algorithm `{:?}` is not an HMAC algorithm",
+                    header.alg
+                ));
+            }
+
+            DecodingKey::from_secret(secret.as_bytes())
+        }
+        None => {
+            let key = match &header.kid {
+                Some(kid) => {
+                    jwks.iter().find(|(key_id, _)| key_id.as_ref() == Some(kid))
+                }
+                None => jwks.first(),
+            };
+
+            key.map(|(_, key)| key.clone())
+                .ok_or_else(|| "No matching key found in JWKS".to_string())?
+        }
+    };
+
+    let mut validation = Validation::new(header.alg);
+    validation.required_spec_claims.clear();
+    validation.validate_aud = false;
+
+    decode::<Map<String, Value>>(token, &key, &validation)
+        .map(|token_data| token_data.claims)
+        .map_err(|err| format!("Invalid token, {err}"))
+}
+
+fn verify_claims(
+    expected: &Map<String, Value>,
+    actual: &Map<String, Value>,
+) -> Result<(), AuthFailure> {
+    for (name, expected_value) in expected {
+        let satisfied = match (actual.get(name), expected_value) {
+            (Some(Value::Array(values)), _) => values.contains(expected_value),
+            (Some(Value::String(values)), Value::String(expected_values)) => {
+                let values = values.split_whitespace().collect::<Vec<_>>();
+                expected_values
+                    .split_whitespace()
+                    .all(|expected_value| values.contains(&expected_value))
+            }
+            (Some(value), _) => value == expected_value,
+            (None, _) => false,
+        };
+
+        if !satisfied {
+            return Err(AuthFailure::Forbidden(format!(
+                "Token claim `{name}` does not satisfy `{expected_value}`"
+            )));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use std::collections::{HashMap, HashSet};
+
+/// Command line arguments, shared by `api-gen` and `api-gen-ctl` so both
+/// read them the same way.
+pub struct Arguments {
+    pub positionals: Vec<String>,
+    pub options: HashMap<String, String>,
+    pub flags: HashSet<String>,
+}
+
+impl Arguments {
+    /// Splits `args` into `--option value` pairs, `--flag`s and positional
+    /// arguments.
+    ///
+    /// Only the names in `flags` go without a value. Any other name has to be
+    /// one of `options` and is followed by its value.
+    pub fn parse(
+        args: &[String],
+        options: &[&str],
+        flags: &[&str],
+    ) -> Result<Self, String> {
+        let mut arguments = Self {
+            positionals: vec![],
+            options: HashMap::new(),
+            flags: HashSet::new(),
+        };
+
+        let mut args = args.iter().peekable();
+
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(flag) if flags.contains(&flag) => {
+                    arguments.flags.insert(flag.to_string());
+                }
+                Some(option) if options.contains(&option) => {
+                    let value =
+                        args.next_if(|value| !value.starts_with("--")).ok_or(
+                            format!("Missing value for option `--{option}`"),
+                        )?;
+                    arguments.options.insert(option.to_string(), value.clone());
+                }
+                Some(option) => {
+                    return Err(format!("Unknown option `--{option}`"));
+                }
+                None => arguments.positionals.push(arg.clone()),
+            }
+        }
+
+        Ok(arguments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Arguments;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn should_split_options_flags_and_positionals() {
+        let arguments = Arguments::parse(
+            &args(&["--port", "8080", "remove", "--force", "3000"]),
+            &["port"],
+            &["force"],
+        )
+        .unwrap();
+
+        assert_eq!(vec!["remove", "3000"], arguments.positionals);
+        assert_eq!("8080", arguments.options["port"]);
+        assert!(arguments.flags.contains("force"));
+    }
+
+    #[test]
+    fn should_fail_for_unknown_option() {
+        let error =
+            Arguments::parse(&args(&["--prot", "8080"]), &["port"], &[]).err();
+
+        assert_eq!(Some("Unknown option `--prot`".to_string()), error);
+    }
+
+    #[test]
+    fn should_fail_for_option_without_value() {
+        for arguments in [vec!["--port"], vec!["--port", "--force"]] {
+            let error =
+                Arguments::parse(&args(&arguments), &["port"], &["force"])
+                    .err();
+
+            assert_eq!(
+                Some("Missing value for option `--port`".to_string()),
+                error
+            );
+        }
+    }
+}
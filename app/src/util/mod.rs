@@ -1,2 +1,3 @@
-pub mod lock;
+pub mod arguments;
 pub mod notifier;
+pub mod shutdown;
@@ -2,8 +2,6 @@ use std::sync::RwLock;
 
 use tokio::sync::Notify;
 
-use crate::util::lock::{safe_read, safe_write};
-
 #[derive(Clone)]
 pub enum NotifierState {
     Pending,
@@ -24,6 +22,12 @@ pub struct Notifier<T> {
     state: RwLock<NotifierState>,
 }
 
+impl<T> Default for Notifier<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> Notifier<T> {
     pub fn new() -> Self {
         Self {
@@ -38,15 +42,17 @@ impl<T> Notifier<T> {
             return Err(NotificationError::AlreadyFired);
         }
 
-        let result = safe_write(&self.data, |mut guard| {
-            *guard = Some(data);
+        // A poisoned lock leaves the notifier corrupted rather than pending,
+        // so waiters are released either way.
+        let stored = self.data.write().map(|mut data_guard| {
+            *data_guard = Some(data);
         });
-        safe_write(&self.state, |mut guard| {
-            match result {
-                Some(_) => *guard = NotifierState::Notified,
-                None => *guard = NotifierState::Corrupted,
+        if let Ok(mut state_guard) = self.state.write() {
+            *state_guard = match stored {
+                Ok(()) => NotifierState::Notified,
+                Err(_) => NotifierState::Corrupted,
             };
-        });
+        }
 
         self.notify.notify_waiters();
 
@@ -58,7 +64,9 @@ impl<T> Notifier<T> {
     }
 
     fn get_state(&self) -> NotifierState {
-        safe_read(&self.state, |guard| guard.clone())
+        self.state
+            .read()
+            .map(|guard| guard.clone())
             .unwrap_or(NotifierState::Corrupted)
     }
 
@@ -69,9 +77,10 @@ impl<T> Notifier<T> {
                 Box::pin(self.await_notification()).await
             }
             NotifierState::Notified => {
-                let data = safe_write(&self.data, |mut guard| guard.take());
+                let data =
+                    self.data.write().ok().and_then(|mut guard| guard.take());
 
-                if let Some(Some(data)) = data {
+                if let Some(data) = data {
                     Ok(data)
                 } else {
                     Err(NotificationError::NoAvailableData)
@@ -86,10 +95,7 @@ impl<T> Notifier<T> {
 mod tests {
     use std::sync::Arc;
 
-    use crate::util::{
-        lock::{safe_read, safe_write},
-        notifier::{NotificationError, NotifierState},
-    };
+    use crate::util::notifier::{NotificationError, NotifierState};
 
     use super::Notifier;
 
@@ -145,9 +151,10 @@ mod tests {
         poison_data_lock(notifier.clone()).await;
         let _ = notifier.notify(data);
 
-        safe_read(&notifier.state, |guard| {
-            assert!(matches!(guard.clone(), NotifierState::Corrupted));
-        });
+        assert!(matches!(
+            notifier.state.read().unwrap().clone(),
+            NotifierState::Corrupted
+        ));
     }
 
     #[tokio::test]
@@ -167,9 +174,8 @@ mod tests {
         notifier: Arc<Notifier<T>>,
     ) {
         let _ = tokio::spawn(async move {
-            safe_write(&notifier.data, |_| {
-                panic!("Poisoned the RwLock explicitly.");
-            });
+            let _guard = notifier.data.write();
+            panic!("Poisoned the RwLock explicitly.");
         })
         .await;
     }
@@ -0,0 +1,161 @@
+use api_gen::{
+    model::http_method::HttpMethod, security::admin_auth::AdminAuth,
+};
+use axum::{Router, body::Body, extract::Request};
+use http::{HeaderMap, StatusCode, header::WWW_AUTHENTICATE};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use crate::http::{
+    register::registrar::Registrar, request_sender::RequestSender,
+    util::app_with_admin_auth,
+};
+
+fn bearer_auth() -> Option<AdminAuth> {
+    Some(AdminAuth::Bearer("secret-token".to_string()))
+}
+
+fn registration() -> Value {
+    json!({
+        "port": "3000",
+        "method": "GET",
+        "path": "/hello",
+        "response": "Hello World!",
+    })
+}
+
+async fn send_with_authorization(
+    router: Router,
+    uri: &str,
+    method: &str,
+    authorization: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, HeaderMap) {
+    let mut request = Request::builder()
+        .uri(uri)
+        .method(method)
+        .header("Content-Type", "application/json");
+
+    if let Some(authorization) = authorization {
+        request = request.header("Authorization", authorization);
+    }
+
+    let body = match body {
+        Some(body) => Body::from(body.to_string()),
+        None => Body::empty(),
+    };
+
+    let response = router
+        .oneshot(request.body(body).unwrap())
+        .await
+        .expect("Couldn't make the request!");
+
+    (response.status(), response.headers().clone())
+}
+
+#[tokio::test]
+async fn should_reject_admin_requests_without_credentials() {
+    let (mut router, _) = app_with_admin_auth(bearer_auth());
+
+    router
+        .register(registration(), |status_code, response_body| {
+            assert_eq!(StatusCode::UNAUTHORIZED, status_code);
+            assert_eq!(
+                json!({
+                    "status": "FAILED",
+                    "failureType": "Unauthorized",
+                    "failureMessage": "Missing or invalid credentials for the admin API",
+                }),
+                response_body
+            );
+        })
+        .await;
+
+    let (status_code, headers) =
+        send_with_authorization(router, "/info", "GET", None, None).await;
+
+    assert_eq!(StatusCode::UNAUTHORIZED, status_code);
+    assert_eq!("Bearer", headers.get(WWW_AUTHENTICATE).unwrap());
+}
+
+#[tokio::test]
+async fn should_reject_admin_requests_with_invalid_credentials() {
+    let (router, _) = app_with_admin_auth(bearer_auth());
+
+    let (status_code, _) = send_with_authorization(
+        router,
+        "/register",
+        "POST",
+        Some("Bearer wrong-token"),
+        Some(registration()),
+    )
+    .await;
+
+    assert_eq!(StatusCode::UNAUTHORIZED, status_code);
+}
+
+#[tokio::test]
+async fn should_accept_admin_requests_with_bearer_token() {
+    let (router, connection_establisher) = app_with_admin_auth(bearer_auth());
+
+    let (status_code, _) = send_with_authorization(
+        router,
+        "/register",
+        "POST",
+        Some("Bearer secret-token"),
+        Some(registration()),
+    )
+    .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+
+    let (status_code, response_body) = connection_establisher
+        .get_router("3000")
+        .send("/hello".to_string(), HttpMethod::Get, None)
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!("Hello World!"), response_body);
+}
+
+#[tokio::test]
+async fn should_accept_admin_requests_with_basic_auth() {
+    let (router, _) =
+        app_with_admin_auth(Some(AdminAuth::basic("admin:secret").unwrap()));
+
+    let (status_code, _) = send_with_authorization(
+        router.clone(),
+        "/info",
+        "GET",
+        Some("Basic YWRtaW46c2VjcmV0"),
+        None,
+    )
+    .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+
+    let (status_code, headers) = send_with_authorization(
+        router,
+        "/info",
+        "GET",
+        Some("Basic YWRtaW46d3Jvbmc="),
+        None,
+    )
+    .await;
+
+    assert_eq!(StatusCode::UNAUTHORIZED, status_code);
+    assert_eq!(
+        "Basic realm=\"api-gen\"",
+        headers.get(WWW_AUTHENTICATE).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn should_keep_health_check_unauthenticated() {
+    let (router, _) = app_with_admin_auth(bearer_auth());
+
+    let (status_code, _) =
+        send_with_authorization(router, "/health", "GET", None, None).await;
+
+    assert_eq!(StatusCode::OK, status_code);
+}
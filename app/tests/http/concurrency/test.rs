@@ -0,0 +1,147 @@
+use api_gen::model::http_method::HttpMethod;
+use axum::{Router, body::Body, extract::Request};
+use http::StatusCode;
+use serde_json::{Value, json};
+use tokio::task::JoinSet;
+use tower::ServiceExt;
+
+use crate::http::{request_sender::RequestSender, util::app};
+
+const PORT: &str = "3900";
+const REGISTRATIONS: usize = 50;
+
+/// Unlike `RequestSender`, the returned future can be spawned.
+async fn post(router: Router, uri: &str, body: Value) -> StatusCode {
+    let request = Request::builder()
+        .uri(uri)
+        .method("POST")
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    router.oneshot(request).await.unwrap().status()
+}
+
+async fn registered_paths(router: &mut Router) -> Vec<String> {
+    let (_, response_body) = router
+        .send(
+            format!("/servers/{PORT}/routes?limit=500"),
+            HttpMethod::Get,
+            None,
+        )
+        .await;
+
+    response_body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|route| route["path"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn should_keep_every_route_of_concurrent_registrations() {
+    let (mut router, _) = app();
+
+    let mut registrations = JoinSet::new();
+    for index in 0..REGISTRATIONS {
+        let body = json!({
+            "port": PORT,
+            "method": "GET",
+            "path": format!("/route-{index:02}"),
+            "response": index,
+        });
+        registrations.spawn(post(router.clone(), "/register", body));
+    }
+
+    for status_code in registrations.join_all().await {
+        assert_eq!(StatusCode::OK, status_code);
+    }
+
+    let expected = (0..REGISTRATIONS)
+        .map(|index| format!("/route-{index:02}"))
+        .collect::<Vec<_>>();
+    assert_eq!(expected, registered_paths(&mut router).await);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn should_keep_every_route_of_concurrent_batches_and_updates() {
+    let (mut router, _) = app();
+
+    let mut registrations = JoinSet::new();
+    for index in 0..REGISTRATIONS {
+        let route = |suffix: &str| {
+            json!({
+                "port": PORT,
+                "method": "GET",
+                "path": format!("/batch-{index:02}-{suffix}"),
+                "response": index,
+            })
+        };
+        registrations.spawn(post(
+            router.clone(),
+            "/register/batch",
+            json!({ "registrations": [route("a"), route("b")] }),
+        ));
+        registrations.spawn(post(
+            router.clone(),
+            "/register/rate-limit",
+            json!({
+                "port": PORT,
+                "strategy": "fixedWindow",
+                "limit": 1000 + index,
+                "windowSeconds": 60,
+            }),
+        ));
+    }
+
+    for status_code in registrations.join_all().await {
+        assert_eq!(StatusCode::OK, status_code);
+    }
+
+    assert_eq!(2 * REGISTRATIONS, registered_paths(&mut router).await.len());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn should_not_keep_routes_registered_before_concurrent_resets() {
+    let (mut router, _) = app();
+    let route = |path: String| {
+        json!({
+            "port": PORT,
+            "method": "GET",
+            "path": path,
+            "response": [],
+        })
+    };
+
+    for index in 0..REGISTRATIONS {
+        let before = format!("/before-{index:02}");
+        let status_code =
+            post(router.clone(), "/register", route(before)).await;
+        assert_eq!(StatusCode::OK, status_code);
+
+        let mut requests = JoinSet::new();
+        requests.spawn(post(router.clone(), "/reset", json!({})));
+        requests.spawn(post(
+            router.clone(),
+            "/register",
+            route(format!("/during-{index:02}")),
+        ));
+
+        for status_code in requests.join_all().await {
+            assert_eq!(StatusCode::OK, status_code);
+        }
+
+        let (_, response_body) = router
+            .send(format!("/servers/{PORT}/routes"), HttpMethod::Get, None)
+            .await;
+        let paths = response_body["items"].as_array().cloned();
+        assert!(
+            paths
+                .unwrap_or_default()
+                .iter()
+                .all(|route| route["path"]
+                    != json!(format!("/before-{index:02}")))
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use api_gen::model::http_method::HttpMethod;
+use axum::{Router, body::Body, extract::Request};
+use http::{
+    HeaderMap, StatusCode,
+    header::{
+        ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS,
+        ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN,
+        ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE,
+        ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN,
+    },
+};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use crate::http::{
+    register::registrar::Registrar, request_sender::RequestSender, util::app,
+};
+
+const REGISTER_CORS_ENDPOINT: &str = "/register/cors";
+const DEV_SERVER_ORIGIN: &str = "http://localhost:5173";
+
+fn cors_registration() -> Value {
+    json!({
+        "port": "3000",
+        "allowedOrigins": [DEV_SERVER_ORIGIN],
+        "exposedHeaders": ["x-request-id"],
+        "allowCredentials": true,
+        "maxAge": 600,
+    })
+}
+
+async fn register_with_cors() -> (Router, Router) {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .register(
+            json!({
+                "port": "3000",
+                "method": "GET",
+                "path": "/hello",
+                "response": "Hello World!",
+            }),
+            |status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+
+    let (status_code, _) = router
+        .send(
+            REGISTER_CORS_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(cors_registration()),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+
+    (router, connection_establisher.get_router("3000"))
+}
+
+async fn send(
+    router: &Router,
+    method: &str,
+    headers: &[(&str, &str)],
+) -> (StatusCode, HeaderMap) {
+    let mut request = Request::builder().uri("/hello").method(method);
+
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+
+    let response = router
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .expect("Couldn't make the request!");
+
+    (response.status(), response.headers().clone())
+}
+
+#[tokio::test]
+async fn should_answer_preflight_without_options_stub() {
+    let (_, stub) = register_with_cors().await;
+
+    let (status_code, headers) = send(
+        &stub,
+        "OPTIONS",
+        &[
+            (ORIGIN.as_str(), DEV_SERVER_ORIGIN),
+            (ACCESS_CONTROL_REQUEST_METHOD.as_str(), "GET"),
+            (ACCESS_CONTROL_REQUEST_HEADERS.as_str(), "x-trace-id"),
+        ],
+    )
+    .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(
+        DEV_SERVER_ORIGIN,
+        headers.get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap()
+    );
+    assert_eq!(
+        "true",
+        headers.get(ACCESS_CONTROL_ALLOW_CREDENTIALS).unwrap()
+    );
+    assert_eq!("GET", headers.get(ACCESS_CONTROL_ALLOW_METHODS).unwrap());
+    assert_eq!(
+        "x-trace-id",
+        headers.get(ACCESS_CONTROL_ALLOW_HEADERS).unwrap()
+    );
+    assert_eq!("600", headers.get(ACCESS_CONTROL_MAX_AGE).unwrap());
+}
+
+#[tokio::test]
+async fn should_add_cors_headers_to_stub_responses() {
+    let (_, stub) = register_with_cors().await;
+
+    let (status_code, headers) =
+        send(&stub, "GET", &[(ORIGIN.as_str(), DEV_SERVER_ORIGIN)]).await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(
+        DEV_SERVER_ORIGIN,
+        headers.get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap()
+    );
+    assert_eq!(
+        "x-request-id",
+        headers.get(ACCESS_CONTROL_EXPOSE_HEADERS).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn should_not_allow_unlisted_origin() {
+    let (_, stub) = register_with_cors().await;
+
+    let (_, headers) = send(
+        &stub,
+        "OPTIONS",
+        &[
+            (ORIGIN.as_str(), "http://evil.example"),
+            (ACCESS_CONTROL_REQUEST_METHOD.as_str(), "GET"),
+        ],
+    )
+    .await;
+
+    assert!(headers.get(ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+}
+
+#[tokio::test]
+async fn should_keep_cors_policy_across_route_registrations() {
+    let (mut router, connection_establisher) = app();
+
+    let (status_code, response_body) = router
+        .send(
+            REGISTER_CORS_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(cors_registration()),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(Value::Null, response_body["removed"]);
+
+    router
+        .register(
+            json!({
+                "port": "3000",
+                "method": "GET",
+                "path": "/hello",
+                "response": "Hello World!",
+            }),
+            |status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+
+    let (_, headers) = send(
+        &connection_establisher.get_router("3000"),
+        "GET",
+        &[(ORIGIN.as_str(), DEV_SERVER_ORIGIN)],
+    )
+    .await;
+    assert_eq!(
+        DEV_SERVER_ORIGIN,
+        headers.get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap()
+    );
+
+    let (_, registrations) = router
+        .send("/info".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(
+        json!({
+            "allowedOrigins": [DEV_SERVER_ORIGIN],
+            "allowedMethods": [],
+            "allowedHeaders": [],
+            "exposedHeaders": ["x-request-id"],
+            "allowCredentials": true,
+            "maxAge": 600,
+        }),
+        registrations[0]["cors"]
+    );
+}
+
+#[tokio::test]
+async fn should_reject_wildcard_origin_with_credentials() {
+    let (mut router, _) = app();
+
+    let (status_code, response_body) = router
+        .send(
+            REGISTER_CORS_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "3000",
+                "allowedOrigins": ["*"],
+                "allowCredentials": true,
+            })),
+        )
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    assert_eq!(
+        json!({
+            "status": "FAILED",
+            "failureType": "Cors",
+            "failureMessage": "`*` in `allowedOrigins` cannot be combined with `allowCredentials`",
+        }),
+        response_body
+    );
+}
@@ -0,0 +1,215 @@
+use std::{env, fs, process};
+
+use api_gen::{ctl::Invocation, security::admin_auth::AdminAuth};
+use axum::{Router, serve};
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+
+use crate::http::util::{app, app_with_admin_auth};
+
+async fn start(router: Router) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { serve(listener, router).await.unwrap() });
+
+    format!("http://{address}")
+}
+
+async fn run(url: &str, args: &[&str]) -> Result<String, String> {
+    let args = ["--url", url]
+        .iter()
+        .chain(args)
+        .map(|arg| arg.to_string())
+        .collect::<Vec<_>>();
+
+    Invocation::parse(&args, |_| None)?.run().await
+}
+
+async fn run_json(url: &str, args: &[&str]) -> Value {
+    let args = [&["--output", "json"], args].concat();
+    let output = run(url, &args).await.unwrap();
+
+    serde_json::from_str(&output).unwrap()
+}
+
+async fn register(url: &str, port: &str, path: &str, response: &str) {
+    run(
+        url,
+        &[
+            "register",
+            "--port",
+            port,
+            "--method",
+            "GET",
+            "--path",
+            path,
+            "--response",
+            response,
+        ],
+    )
+    .await
+    .unwrap();
+}
+
+fn temp_file(name: &str) -> String {
+    env::temp_dir()
+        .join(format!("api-gen-ctl-{}-{name}", process::id()))
+        .to_string_lossy()
+        .to_string()
+}
+
+#[tokio::test]
+async fn should_register_and_list_routes() {
+    let url = start(app().0).await;
+
+    let output = run(
+        &url,
+        &[
+            "register",
+            "--port",
+            "3000",
+            "--method",
+            "GET",
+            "--path",
+            "/orders",
+            "--response",
+            "[1, 2]",
+        ],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        "PORT  METHOD  PATH     RESPONSE  STATUS\n\
+         3000  GET     /orders  [1,2]     added",
+        output
+    );
+
+    assert_eq!(
+        json!([{
+            "port": "3000",
+            "registrations": [{
+                "method": "GET",
+                "path": "/orders",
+                "response": [1, 2],
+            }],
+        }]),
+        run_json(&url, &["list"]).await
+    );
+    assert_eq!(
+        "PORT  KIND  METHOD  PATH     RESPONSE\n\
+         3000  http  GET     /orders  [1,2]",
+        run(&url, &["list", "--port", "3000"]).await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn should_remove_server() {
+    let url = start(app().0).await;
+    register(&url, "3000", "/orders", "orders").await;
+    register(&url, "3001", "/users", "users").await;
+
+    let removed = run_json(&url, &["remove", "3000"]).await;
+
+    assert_eq!(json!("3000"), removed["port"]);
+    let servers = run_json(&url, &["list"]).await;
+    assert_eq!(1, servers.as_array().unwrap().len());
+    assert_eq!(json!("3001"), servers[0]["port"]);
+    assert_eq!(
+        "NotFound (404): No server is running on port 3000",
+        run(&url, &["remove", "3000"]).await.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn should_reset_all_servers() {
+    let url = start(app().0).await;
+    register(&url, "3000", "/orders", "orders").await;
+    register(&url, "3001", "/users", "users").await;
+
+    assert_eq!(
+        "Removed 2 server(s), restored 0 pinned registration(s).",
+        run(&url, &["reset"]).await.unwrap()
+    );
+    assert_eq!(json!([]), run_json(&url, &["list"]).await);
+}
+
+#[tokio::test]
+async fn should_round_trip_export_and_import() {
+    let exporting = start(app().0).await;
+    register(&exporting, "3000", "/orders", "orders").await;
+    register(&exporting, "3001", "/users/{id}", r#"{"id": 1}"#).await;
+
+    let file = temp_file("export.json");
+    run(&exporting, &["export", &file]).await.unwrap();
+
+    let importing = start(app().0).await;
+    let imported = run_json(&importing, &["import", &file]).await;
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(2, imported["registrations"].as_array().unwrap().len());
+    let mut exported = run_json(&exporting, &["list"]).await;
+    let mut listed = run_json(&importing, &["list"]).await;
+    for servers in [&mut exported, &mut listed] {
+        servers
+            .as_array_mut()
+            .unwrap()
+            .sort_by_key(|server| server["port"].to_string());
+    }
+    assert_eq!(exported, listed);
+}
+
+#[tokio::test]
+async fn should_report_validation_failures() {
+    let url = start(app().0).await;
+
+    let error = run(
+        &url,
+        &["import", "/nonexistent/api-gen-ctl/registrations.json"],
+    )
+    .await
+    .unwrap_err();
+    assert!(error.starts_with("Failed to read"));
+
+    let file = temp_file("invalid.json");
+    fs::write(
+        &file,
+        json!({
+            "registrations": [{
+                "port": "3000",
+                "method": "GET",
+                "path": "/orders",
+                "rateLimit": {
+                    "strategy": "fixedWindow",
+                    "limit": 0,
+                    "windowSeconds": 1,
+                },
+                "response": [],
+            }],
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let error = run(&url, &["import", &file]).await.unwrap_err();
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(
+        "Batch (400): 1 of 1 registrations failed, no changes were applied",
+        error
+    );
+}
+
+#[tokio::test]
+async fn should_authenticate_against_admin_api() {
+    let (router, _) =
+        app_with_admin_auth(Some(AdminAuth::Bearer("secret".to_string())));
+    let url = start(router).await;
+
+    assert_eq!(
+        "Unauthorized (401): Missing or invalid credentials for the admin API",
+        run(&url, &["list"]).await.unwrap_err()
+    );
+    assert_eq!(
+        json!([]),
+        run_json(&url, &["--admin-token", "secret", "list"]).await
+    );
+}
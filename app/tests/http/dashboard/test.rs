@@ -0,0 +1,68 @@
+use api_gen::security::admin_auth::AdminAuth;
+use axum::{Router, body::Body, extract::Request};
+use http::{StatusCode, header::CONTENT_TYPE};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+use crate::http::util::{app, app_with_admin_auth};
+
+async fn fetch(router: Router, uri: &str) -> (StatusCode, String, String) {
+    let response = router
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .expect("Couldn't make the request!");
+
+    let status_code = response.status();
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .map(|content_type| content_type.to_str().unwrap().to_string())
+        .unwrap_or_default();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+
+    (
+        status_code,
+        content_type,
+        String::from_utf8(body.to_vec()).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn should_serve_dashboard_page() {
+    let (router, _) = app();
+
+    let (status_code, content_type, body) = fetch(router, "/dashboard").await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert!(content_type.starts_with("text/html"));
+    assert!(body.contains(r#"<script src="/dashboard/dashboard.js">"#));
+}
+
+#[tokio::test]
+async fn should_serve_embedded_assets() {
+    let (router, _) = app();
+
+    let (status_code, content_type, body) =
+        fetch(router.clone(), "/dashboard/dashboard.js").await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert!(content_type.starts_with("text/javascript"));
+    assert!(body.contains("/servers"));
+
+    let (status_code, content_type, _) =
+        fetch(router, "/dashboard/dashboard.css").await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert!(content_type.starts_with("text/css"));
+}
+
+#[tokio::test]
+async fn should_serve_dashboard_without_admin_credentials() {
+    let (router, _) = app_with_admin_auth(Some(AdminAuth::Bearer(
+        "secret-token".to_string(),
+    )));
+
+    let (status_code, _, _) = fetch(router.clone(), "/dashboard").await;
+    assert_eq!(StatusCode::OK, status_code);
+
+    let (status_code, _, _) = fetch(router, "/servers").await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status_code);
+}
@@ -0,0 +1,251 @@
+use api_gen::model::http_method::HttpMethod;
+use http::StatusCode;
+use serde_json::json;
+
+use crate::http::register::{registrar::Registrar, util::app};
+
+#[tokio::test]
+async fn should_respond_per_operation_name() {
+    let (mut router, registration_verifier_builder) = app();
+
+    router
+        .register_many(
+            json!([
+                {
+                    "port": "3000",
+                    "method": "POST",
+                    "path": "/graphql",
+                    "graphql": { "operationName": "GetUser" },
+                    "response": { "data": { "user": { "id": "1" } } },
+                },
+                {
+                    "port": "3000",
+                    "method": "POST",
+                    "path": "/graphql",
+                    "graphql": { "operationName": "GetOrders" },
+                    "response": { "data": { "orders": [] } },
+                },
+            ]),
+            |_, status_code, _| {
+                assert_eq!(StatusCode::OK, status_code);
+            },
+        )
+        .await;
+
+    let registration_verifier_builder = registration_verifier_builder
+        .port("3000")
+        .method(HttpMethod::Post)
+        .path("/graphql");
+
+    let registration_verifier_builder =
+        registration_verifier_builder.body(json!({
+            "operationName": "GetUser",
+            "query": "query GetUser { user { id } }",
+        }));
+    registration_verifier_builder
+        .build()
+        .request(|status_code, response_body| {
+            assert_eq!(StatusCode::OK, status_code);
+            assert_eq!(
+                json!({ "data": { "user": { "id": "1" } } }),
+                response_body
+            );
+        })
+        .await;
+
+    let registration_verifier_builder =
+        registration_verifier_builder.body(json!({
+            "operationName": "GetOrders",
+            "query": "query GetOrders { orders { id } }",
+        }));
+    registration_verifier_builder
+        .build()
+        .request(|status_code, response_body| {
+            assert_eq!(StatusCode::OK, status_code);
+            assert_eq!(json!({ "data": { "orders": [] } }), response_body);
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn should_prefer_most_specific_operation() {
+    let (mut router, registration_verifier_builder) = app();
+
+    router
+        .register_many(
+            json!([
+                {
+                    "port": "3000",
+                    "method": "POST",
+                    "path": "/graphql",
+                    "graphql": { "operationName": "GetUser" },
+                    "response": { "data": { "user": null } },
+                },
+                {
+                    "port": "3000",
+                    "method": "POST",
+                    "path": "/graphql",
+                    "graphql": {
+                        "operationName": "GetUser",
+                        "query": "query GetUser($id: ID!) { user(id: $id) { id } }",
+                        "variables": { "id": "1" },
+                    },
+                    "response": { "data": { "user": { "id": "1" } } },
+                },
+            ]),
+            |_, status_code, _| {
+                assert_eq!(StatusCode::OK, status_code);
+            },
+        )
+        .await;
+
+    registration_verifier_builder
+        .port("3000")
+        .method(HttpMethod::Post)
+        .path("/graphql")
+        .body(json!({
+            "operationName": "GetUser",
+            "query": "query GetUser($id: ID!) {\n  user(id: $id) {\n    id\n  }\n}",
+            "variables": { "id": "1" },
+        }))
+        .build()
+        .request(|status_code, response_body| {
+            assert_eq!(StatusCode::OK, status_code);
+            assert_eq!(json!({ "data": { "user": { "id": "1" } } }), response_body);
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn should_return_registered_errors() {
+    let (mut router, registration_verifier_builder) = app();
+
+    router
+        .register(
+            json!({
+                "port": "3000",
+                "method": "POST",
+                "path": "/graphql",
+                "graphql": { "operationName": "GetUser" },
+                "response": {
+                    "data": null,
+                    "errors": [{ "message": "User not found." }],
+                },
+            }),
+            |status_code, _| {
+                assert_eq!(StatusCode::OK, status_code);
+            },
+        )
+        .await;
+
+    registration_verifier_builder
+        .port("3000")
+        .method(HttpMethod::Post)
+        .path("/graphql")
+        .body(json!({ "operationName": "GetUser" }))
+        .build()
+        .request(|status_code, response_body| {
+            assert_eq!(StatusCode::OK, status_code);
+            assert_eq!(
+                json!({
+                    "data": null,
+                    "errors": [{ "message": "User not found." }],
+                }),
+                response_body
+            );
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn should_return_graphql_error_for_unmatched_operation() {
+    let (mut router, registration_verifier_builder) = app();
+
+    router
+        .register(
+            json!({
+                "port": "3000",
+                "method": "POST",
+                "path": "/graphql",
+                "graphql": { "operationName": "GetUser" },
+                "response": { "data": { "user": null } },
+            }),
+            |status_code, _| {
+                assert_eq!(StatusCode::OK, status_code);
+            },
+        )
+        .await;
+
+    registration_verifier_builder
+        .port("3000")
+        .method(HttpMethod::Post)
+        .path("/graphql")
+        .body(json!({ "operationName": "GetOrders" }))
+        .build()
+        .request(|status_code, response_body| {
+            assert_eq!(StatusCode::OK, status_code);
+            assert_eq!(
+                json!({
+                    "data": null,
+                    "errors": [
+                        {
+                            "message": "No stub registered for operation `GetOrders`.",
+                            "extensions": { "code": "OPERATION_NOT_STUBBED" },
+                        }
+                    ],
+                }),
+                response_body
+            );
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn should_echo_graphql_operation_in_registration_response() {
+    let (mut router, _) = app();
+
+    router
+        .register_many(
+            json!([
+                {
+                    "port": "3000",
+                    "method": "POST",
+                    "path": "/graphql",
+                    "graphql": { "operationName": "GetUser" },
+                    "response": { "data": { "user": null } },
+                },
+                {
+                    "port": "3000",
+                    "method": "POST",
+                    "path": "/graphql",
+                    "graphql": { "operationName": "GetUser" },
+                    "response": { "data": { "user": { "id": "1" } } },
+                },
+            ]),
+            |idx, status_code, registration_response| {
+                assert_eq!(StatusCode::OK, status_code);
+
+                if idx == 1 {
+                    assert_eq!(
+                        json!({
+                            "port": "3000",
+                            "added": {
+                                "method": "POST",
+                                "path": "/graphql",
+                                "graphql": { "operationName": "GetUser" },
+                                "response": { "data": { "user": { "id": "1" } } },
+                            },
+                            "removed": {
+                                "method": "POST",
+                                "path": "/graphql",
+                                "graphql": { "operationName": "GetUser" },
+                                "response": { "data": { "user": null } },
+                            },
+                        }),
+                        registration_response
+                    );
+                }
+            },
+        )
+        .await;
+}
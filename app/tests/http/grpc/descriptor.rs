@@ -0,0 +1,73 @@
+use base64::{Engine, prelude::BASE64_STANDARD};
+use prost_reflect::{
+    DescriptorPool, MessageDescriptor,
+    prost::Message,
+    prost_types::{
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto,
+        FileDescriptorSet, MethodDescriptorProto, ServiceDescriptorProto,
+        field_descriptor_proto::{Label, Type},
+    },
+};
+
+pub(super) const SAY_HELLO_PATH: &str = "/helloworld.Greeter/SayHello";
+pub(super) const SAY_GOODBYE_PATH: &str = "/helloworld.Greeter/SayGoodbye";
+
+pub(super) fn encoded_descriptor_set() -> String {
+    BASE64_STANDARD.encode(descriptor_set().encode_to_vec())
+}
+
+pub(super) fn message_descriptor(name: &str) -> MessageDescriptor {
+    DescriptorPool::from_file_descriptor_set(descriptor_set())
+        .expect("Failed to build descriptor pool!")
+        .get_message_by_name(name)
+        .expect("Message is missing from the descriptor pool!")
+}
+
+fn descriptor_set() -> FileDescriptorSet {
+    FileDescriptorSet {
+        file: vec![FileDescriptorProto {
+            name: Some("helloworld.proto".to_string()),
+            package: Some("helloworld".to_string()),
+            syntax: Some("proto3".to_string()),
+            message_type: vec![
+                message("HelloRequest", "name"),
+                message("HelloReply", "message"),
+            ],
+            service: vec![ServiceDescriptorProto {
+                name: Some("Greeter".to_string()),
+                method: vec![
+                    method("SayHello", false),
+                    method("SayGoodbye", false),
+                    method("SayHelloStream", true),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    }
+}
+
+fn message(name: &str, field: &str) -> DescriptorProto {
+    DescriptorProto {
+        name: Some(name.to_string()),
+        field: vec![FieldDescriptorProto {
+            name: Some(field.to_string()),
+            json_name: Some(field.to_string()),
+            number: Some(1),
+            label: Some(Label::Optional as i32),
+            r#type: Some(Type::String as i32),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+fn method(name: &str, server_streaming: bool) -> MethodDescriptorProto {
+    MethodDescriptorProto {
+        name: Some(name.to_string()),
+        input_type: Some(".helloworld.HelloRequest".to_string()),
+        output_type: Some(".helloworld.HelloReply".to_string()),
+        server_streaming: Some(server_streaming),
+        ..Default::default()
+    }
+}
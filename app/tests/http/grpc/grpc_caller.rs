@@ -0,0 +1,83 @@
+use axum::{Router, body::Body, extract::Request};
+use http::HeaderMap;
+use http_body_util::BodyExt;
+use prost_reflect::{
+    DynamicMessage, MessageDescriptor,
+    prost::{
+        Message,
+        bytes::{Buf, BufMut, Bytes, BytesMut},
+    },
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+pub(super) struct GrpcReply {
+    pub(super) message: Option<Value>,
+    pub(super) trailers: HeaderMap,
+}
+
+pub(super) trait GrpcCaller {
+    fn call(
+        self,
+        path: &str,
+        request: DynamicMessage,
+        reply_descriptor: MessageDescriptor,
+    ) -> impl Future<Output = GrpcReply>;
+}
+
+impl GrpcCaller for Router {
+    async fn call(
+        self,
+        path: &str,
+        request: DynamicMessage,
+        reply_descriptor: MessageDescriptor,
+    ) -> GrpcReply {
+        let encoded = request.encode_to_vec();
+        let mut frame = BytesMut::new();
+        frame.put_u8(0);
+        frame.put_u32(encoded.len() as u32);
+        frame.put_slice(&encoded);
+
+        let request = Request::builder()
+            .uri(path)
+            .method("POST")
+            .header("Content-Type", "application/grpc")
+            .body(Body::from(frame.freeze()))
+            .expect("Failed to build request!");
+
+        let response = self
+            .oneshot(request)
+            .await
+            .expect("Couldn't make the request!");
+
+        assert_eq!(
+            "application/grpc",
+            response.headers().get("Content-Type").unwrap()
+        );
+
+        let collected = response
+            .into_body()
+            .collect()
+            .await
+            .expect("Failed to collect the body from response!");
+        let trailers = collected.trailers().cloned().unwrap_or_default();
+        let mut bytes: Bytes = collected.to_bytes();
+
+        let message = if bytes.is_empty() {
+            None
+        } else {
+            let _compressed = bytes.get_u8();
+            let length = bytes.get_u32() as usize;
+            let message =
+                DynamicMessage::decode(reply_descriptor, &bytes[..length])
+                    .expect("Failed to decode gRPC reply!");
+
+            Some(
+                serde_json::to_value(&message)
+                    .expect("Failed to serialize gRPC reply!"),
+            )
+        };
+
+        GrpcReply { message, trailers }
+    }
+}
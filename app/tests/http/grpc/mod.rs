@@ -0,0 +1,3 @@
+mod descriptor;
+mod grpc_caller;
+mod test;
@@ -0,0 +1,399 @@
+use api_gen::model::http_method::HttpMethod;
+use http::StatusCode;
+use prost_reflect::{DynamicMessage, Value};
+use serde_json::json;
+
+use crate::http::{
+    grpc::{
+        descriptor::{
+            SAY_GOODBYE_PATH, SAY_HELLO_PATH, encoded_descriptor_set,
+            message_descriptor,
+        },
+        grpc_caller::GrpcCaller,
+    },
+    request_sender::RequestSender,
+    util::app,
+};
+
+const REGISTER_DESCRIPTOR_ENDPOINT: &str = "/register/grpc/descriptor";
+const REGISTER_GRPC_ENDPOINT: &str = "/register/grpc";
+
+fn hello_request() -> DynamicMessage {
+    let mut request =
+        DynamicMessage::new(message_descriptor("helloworld.HelloRequest"));
+    request.set_field_by_name("name", Value::String("api-gen".to_string()));
+    request
+}
+
+#[tokio::test]
+async fn should_list_services_from_descriptor_set() {
+    let (mut router, _) = app();
+
+    let (status_code, response_body) = router
+        .send(
+            REGISTER_DESCRIPTOR_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "descriptorSet": encoded_descriptor_set(),
+            })),
+        )
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(
+        json!({
+            "port": "5000",
+            "services": [
+                {
+                    "name": "helloworld.Greeter",
+                    "methods": [
+                        {
+                            "name": "SayHello",
+                            "inputType": "helloworld.HelloRequest",
+                            "outputType": "helloworld.HelloReply",
+                            "unary": true,
+                        },
+                        {
+                            "name": "SayGoodbye",
+                            "inputType": "helloworld.HelloRequest",
+                            "outputType": "helloworld.HelloReply",
+                            "unary": true,
+                        },
+                        {
+                            "name": "SayHelloStream",
+                            "inputType": "helloworld.HelloRequest",
+                            "outputType": "helloworld.HelloReply",
+                            "unary": false,
+                        },
+                    ],
+                }
+            ],
+        }),
+        response_body
+    );
+}
+
+#[tokio::test]
+async fn should_fail_for_invalid_descriptor_set() {
+    let (mut router, _) = app();
+
+    let (status_code, response_body) = router
+        .send(
+            REGISTER_DESCRIPTOR_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "descriptorSet": "not base64!",
+            })),
+        )
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    assert_eq!(json!("Grpc"), response_body["failureType"]);
+}
+
+#[tokio::test]
+async fn should_respond_with_transcoded_message_and_trailers() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .send(
+            REGISTER_DESCRIPTOR_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "descriptorSet": encoded_descriptor_set(),
+            })),
+        )
+        .await;
+
+    let (status_code, response_body) = router
+        .send(
+            REGISTER_GRPC_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "service": "helloworld.Greeter",
+                "method": "SayHello",
+                "trailers": { "x-stub": "api-gen" },
+                "response": { "message": "Hello World!" },
+            })),
+        )
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(
+        json!({
+            "added": {
+                "service": "helloworld.Greeter",
+                "method": "SayHello",
+                "status": 0,
+                "trailers": { "x-stub": "api-gen" },
+                "response": { "message": "Hello World!" },
+            },
+            "removed": null,
+        }),
+        response_body
+    );
+
+    let reply = connection_establisher
+        .get_router("5000")
+        .call(
+            SAY_HELLO_PATH,
+            hello_request(),
+            message_descriptor("helloworld.HelloReply"),
+        )
+        .await;
+
+    assert_eq!(Some(json!({ "message": "Hello World!" })), reply.message);
+    assert_eq!("0", reply.trailers.get("grpc-status").unwrap());
+    assert_eq!("api-gen", reply.trailers.get("x-stub").unwrap());
+}
+
+#[tokio::test]
+async fn should_respond_with_registered_status() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .send(
+            REGISTER_DESCRIPTOR_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "descriptorSet": encoded_descriptor_set(),
+            })),
+        )
+        .await;
+
+    let (status_code, _) = router
+        .send(
+            REGISTER_GRPC_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "service": "helloworld.Greeter",
+                "method": "SayHello",
+                "status": 5,
+                "message": "User not found",
+            })),
+        )
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+
+    let reply = connection_establisher
+        .get_router("5000")
+        .call(
+            SAY_HELLO_PATH,
+            hello_request(),
+            message_descriptor("helloworld.HelloReply"),
+        )
+        .await;
+
+    assert_eq!(None, reply.message);
+    assert_eq!("5", reply.trailers.get("grpc-status").unwrap());
+    assert_eq!(
+        "User not found",
+        reply.trailers.get("grpc-message").unwrap()
+    );
+}
+
+#[tokio::test]
+async fn should_respond_unimplemented_for_unstubbed_method() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .send(
+            REGISTER_DESCRIPTOR_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "descriptorSet": encoded_descriptor_set(),
+            })),
+        )
+        .await;
+
+    let reply = connection_establisher
+        .get_router("5000")
+        .call(
+            SAY_GOODBYE_PATH,
+            hello_request(),
+            message_descriptor("helloworld.HelloReply"),
+        )
+        .await;
+
+    assert_eq!(None, reply.message);
+    assert_eq!("12", reply.trailers.get("grpc-status").unwrap());
+}
+
+#[tokio::test]
+async fn should_fail_for_invalid_grpc_stubs() {
+    let (mut router, _) = app();
+
+    router
+        .send(
+            REGISTER_DESCRIPTOR_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "descriptorSet": encoded_descriptor_set(),
+            })),
+        )
+        .await;
+
+    let invalid_stubs = [
+        json!({
+            "port": "5000",
+            "service": "helloworld.Unknown",
+            "method": "SayHello",
+        }),
+        json!({
+            "port": "5000",
+            "service": "helloworld.Greeter",
+            "method": "SayHelloStream",
+        }),
+        json!({
+            "port": "5000",
+            "service": "helloworld.Greeter",
+            "method": "SayHello",
+            "response": { "unknownField": true },
+        }),
+        json!({
+            "port": "5000",
+            "service": "helloworld.Greeter",
+            "method": "SayHello",
+            "status": 42,
+        }),
+        json!({
+            "port": "5000",
+            "service": "helloworld.Greeter",
+            "method": "SayHello",
+            "trailers": { "Grpc-Status": "0" },
+        }),
+    ];
+
+    for invalid_stub in invalid_stubs {
+        let (status_code, response_body) = router
+            .send(
+                REGISTER_GRPC_ENDPOINT.to_string(),
+                HttpMethod::Post,
+                Some(invalid_stub),
+            )
+            .await;
+
+        assert_eq!(StatusCode::BAD_REQUEST, status_code);
+        assert_eq!(json!("Grpc"), response_body["failureType"]);
+    }
+}
+
+#[tokio::test]
+async fn should_keep_http_routes_alongside_grpc_services() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .send(
+            "/register".to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "method": "GET",
+                "path": "/hello",
+                "response": "Hello World!",
+            })),
+        )
+        .await;
+
+    router
+        .send(
+            REGISTER_DESCRIPTOR_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "descriptorSet": encoded_descriptor_set(),
+            })),
+        )
+        .await;
+
+    let (status_code, response_body) = connection_establisher
+        .get_router("5000")
+        .send("/hello".to_string(), HttpMethod::Get, None)
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!("Hello World!"), response_body);
+}
+
+#[tokio::test]
+async fn should_reject_grpc_services_shadowing_http_routes() {
+    let (mut router, _) = app();
+
+    router
+        .send(
+            "/register".to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "method": "POST",
+                "path": SAY_HELLO_PATH,
+                "response": "Hello World!",
+            })),
+        )
+        .await;
+
+    let (status_code, response_body) = router
+        .send(
+            REGISTER_DESCRIPTOR_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "descriptorSet": encoded_descriptor_set(),
+            })),
+        )
+        .await;
+
+    assert_eq!(StatusCode::CONFLICT, status_code);
+    assert_eq!(
+        json!(format!(
+            "`{SAY_HELLO_PATH}` conflicts with the registered route `{SAY_HELLO_PATH}` on port 5000, the previous server on port 5000 keeps serving"
+        )),
+        response_body["failureMessage"]
+    );
+}
+
+#[tokio::test]
+async fn should_reject_http_routes_shadowed_by_grpc_services() {
+    let (mut router, _) = app();
+
+    router
+        .send(
+            REGISTER_DESCRIPTOR_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "descriptorSet": encoded_descriptor_set(),
+            })),
+        )
+        .await;
+
+    let (status_code, response_body) = router
+        .send(
+            "/register".to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "5000",
+                "method": "GET",
+                "path": SAY_HELLO_PATH,
+                "response": "Hello World!",
+            })),
+        )
+        .await;
+
+    assert_eq!(StatusCode::CONFLICT, status_code);
+    assert_eq!(
+        json!(format!(
+            "`{SAY_HELLO_PATH}` conflicts with the registered route `{SAY_HELLO_PATH}`"
+        )),
+        response_body["failureMessage"]
+    );
+}
@@ -0,0 +1,170 @@
+use api_gen::client::{
+    api_gen::ApiGen, registration_builder::RegistrationBuilder,
+};
+use http::StatusCode;
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+
+async fn get(url: &str) -> (StatusCode, Value) {
+    let response = reqwest::get(url).await.unwrap();
+    let status_code = response.status();
+
+    (status_code, response.json().await.unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn should_serve_registered_stub() {
+    let api_gen = ApiGen::start().await.unwrap();
+
+    let stub = api_gen
+        .register(
+            RegistrationBuilder::get("/orders/{id}")
+                .with_response(json!({ "id": 1 }))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert!(!stub.port().is_ephemeral());
+    assert_eq!("/orders/{id}", &**stub.path());
+    assert_eq!(
+        (StatusCode::OK, json!({ "id": 1 })),
+        get(&format!("{}/orders/1", stub.base_url())).await
+    );
+    assert!(stub.is_registered().await.unwrap());
+
+    api_gen.shutdown().await;
+}
+
+#[tokio::test]
+async fn should_register_routes_on_same_port() {
+    let api_gen = ApiGen::start().await.unwrap();
+    let orders = api_gen
+        .register(
+            RegistrationBuilder::get("/orders")
+                .with_response(json!(["order"]))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let users = api_gen
+        .register(
+            RegistrationBuilder::post("/users")
+                .with_port(orders.port())
+                .with_response(json!("created"))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(orders.port(), users.port());
+    assert_eq!((StatusCode::OK, json!(["order"])), get(&orders.url()).await);
+    assert!(orders.is_registered().await.unwrap());
+    assert!(users.is_registered().await.unwrap());
+
+    api_gen.shutdown().await;
+}
+
+#[tokio::test]
+async fn should_answer_requests_while_routes_are_added() {
+    let api_gen = ApiGen::start().await.unwrap();
+    let orders = api_gen
+        .register(
+            RegistrationBuilder::get("/orders")
+                .with_response(json!(["order"]))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let url = orders.url();
+    let requests = tokio::spawn(async move {
+        let mut failures = 0;
+        for _ in 0..50 {
+            if reqwest::get(&url).await.is_err() {
+                failures += 1;
+            }
+        }
+        failures
+    });
+
+    for index in 0..10 {
+        api_gen
+            .register(
+                RegistrationBuilder::get(&format!("/users/{index}"))
+                    .with_port(orders.port())
+                    .with_response(json!("user"))
+                    .build()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(0, requests.await.unwrap());
+
+    api_gen.shutdown().await;
+}
+
+#[tokio::test]
+async fn should_tear_down_stubs() {
+    let api_gen = ApiGen::start().await.unwrap();
+    let registration = || {
+        RegistrationBuilder::get("/health")
+            .with_response(json!("UP"))
+            .build()
+            .unwrap()
+    };
+
+    let removed = api_gen.register(registration()).await.unwrap();
+    let port = removed.port();
+    let reset = api_gen.register(registration()).await.unwrap();
+
+    assert_eq!(port, removed.remove().await.unwrap().port);
+    assert!(reset.is_registered().await.unwrap());
+
+    assert_eq!(1, api_gen.reset().await.unwrap().removed.len());
+    assert!(!reset.is_registered().await.unwrap());
+    assert!(api_gen.client().list().await.unwrap().is_empty());
+
+    api_gen.shutdown().await;
+}
+
+#[tokio::test]
+async fn should_release_ports_on_shutdown() {
+    let api_gen = ApiGen::start().await.unwrap();
+    let stub = api_gen
+        .register(
+            RegistrationBuilder::get("/")
+                .with_response(json!("UP"))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let url = api_gen.url().to_string();
+
+    api_gen.shutdown().await;
+
+    assert!(
+        TcpListener::bind(format!("0.0.0.0:{}", stub.port()))
+            .await
+            .is_ok()
+    );
+    assert!(reqwest::get(&url).await.is_err());
+}
+
+#[tokio::test]
+async fn should_reject_invalid_route_path() {
+    let error = RegistrationBuilder::get("orders")
+        .with_response(json!([]))
+        .build()
+        .unwrap_err();
+
+    assert_eq!("`orders` must start with `/`", error);
+}
@@ -0,0 +1,121 @@
+use api_gen::model::http_method::HttpMethod;
+use axum::{Router, body::Body, extract::Request};
+use http::{StatusCode, header::CONTENT_TYPE};
+use http_body_util::BodyExt;
+use serde_json::json;
+use tower::ServiceExt;
+
+use crate::http::{
+    register::registrar::Registrar, request_sender::RequestSender, util::app,
+};
+
+const METRICS_PORT: &str = "3400";
+
+async fn scrape(router: &Router) -> String {
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/metrics")
+                .method("GET")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Couldn't make the request!");
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        "text/plain; version=0.0.4",
+        response.headers().get(CONTENT_TYPE).unwrap()
+    );
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn should_expose_stub_request_metrics() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .register(
+            json!({
+                "port": METRICS_PORT,
+                "method": "GET",
+                "path": "/orders/{id}",
+                "response": { "id": "1" },
+            }),
+            |status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+
+    let mut stub = connection_establisher.get_router(METRICS_PORT);
+    for path in ["/orders/1", "/orders/2", "/missing"] {
+        stub.send(path.to_string(), HttpMethod::Get, None).await;
+    }
+
+    let metrics = scrape(&router).await;
+
+    assert!(metrics.contains(
+        "api_gen_http_requests_total{method=\"GET\",port=\"3400\",route=\"/orders/{id}\",status=\"200\"} 2"
+    ));
+    assert!(metrics.contains(
+        "api_gen_http_requests_total{method=\"GET\",port=\"3400\",route=\"<unmatched>\",status=\"404\"} 1"
+    ));
+    assert!(metrics.contains(
+        "api_gen_http_request_duration_seconds_count{method=\"GET\",port=\"3400\",route=\"/orders/{id}\"} 2"
+    ));
+    assert!(
+        metrics
+            .contains("api_gen_registrations{kind=\"http\",port=\"3400\"} 1")
+    );
+    assert!(metrics.contains("api_gen_active_ports 1"));
+}
+
+#[tokio::test]
+async fn should_count_registration_failures_by_type() {
+    let (mut router, _) = app();
+
+    router
+        .send(
+            "/register/cors".to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": METRICS_PORT,
+                "allowedOrigins": ["*"],
+                "allowCredentials": true,
+            })),
+        )
+        .await;
+
+    let metrics = scrape(&router).await;
+
+    assert!(metrics.contains(
+        "api_gen_registration_failures_total{endpoint=\"/register/cors\",failure_type=\"Cors\"} 1"
+    ));
+}
+
+#[tokio::test]
+async fn should_count_rejected_request_bodies_by_endpoint() {
+    let (mut router, _) = app();
+
+    for endpoint in ["/register", "/sync/3000"] {
+        router
+            .send(
+                endpoint.to_string(),
+                HttpMethod::Post,
+                Some(json!({ "unexpected": true })),
+            )
+            .await;
+    }
+
+    let metrics = scrape(&router).await;
+
+    assert!(metrics.contains(
+        "api_gen_registration_failures_total{endpoint=\"/register\",failure_type=\"MalformedJson\"} 1"
+    ));
+    assert!(metrics.contains(
+        "api_gen_registration_failures_total{endpoint=\"/sync/{port}\",failure_type=\"MalformedJson\"} 1"
+    ));
+}
@@ -1,4 +1,25 @@
+mod admin_auth;
+mod concurrency;
+mod cors;
+mod ctl;
+mod dashboard;
+mod graphql;
+mod grpc;
+mod harness;
+mod metrics;
+mod oidc;
+mod peer_sync;
+mod rate_limit;
 mod register;
+mod register_batch;
 mod registrations;
 mod request_sender;
+mod reset;
+mod rollback;
+mod route_rules;
+mod servers;
+mod socket;
+mod storage;
+mod store;
+mod stub_auth;
 mod util;
@@ -0,0 +1,340 @@
+use api_gen::model::http_method::HttpMethod;
+use axum::{Router, body::Body, extract::Request};
+use http::{StatusCode, header::CONTENT_TYPE};
+use http_body_util::BodyExt;
+use jsonwebtoken::{
+    DecodingKey, Validation, decode, decode_header, jwk::JwkSet,
+};
+use serde_json::{Map, Value, json};
+use tower::ServiceExt;
+
+use crate::http::{request_sender::RequestSender, util::app};
+
+const REGISTER_OIDC_ENDPOINT: &str = "/register/oidc";
+const CLIENT_CREDENTIALS: &str = "Basic b3JkZXJzOm9yZGVycy1zZWNyZXQ=";
+
+fn oidc_registration() -> Value {
+    json!({
+        "port": "4000",
+        "issuer": "http://auth.local/",
+        "clients": [
+            { "clientId": "orders", "clientSecret": "orders-secret" },
+        ],
+        "users": [
+            {
+                "username": "alice",
+                "password": "wonderland",
+                "claims": { "email": "alice@example.com" },
+            },
+        ],
+        "claims": { "tenant": "acme" },
+        "tokenTtl": 600,
+    })
+}
+
+async fn register_provider() -> (Router, Router) {
+    let (mut router, connection_establisher) = app();
+
+    let (status_code, _) = router
+        .send(
+            REGISTER_OIDC_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(oidc_registration()),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+
+    (router, connection_establisher.get_router("4000"))
+}
+
+async fn request_token(
+    provider: &Router,
+    authorization: Option<&str>,
+    form: &str,
+) -> (StatusCode, Value) {
+    let mut request = Request::builder()
+        .uri("/token")
+        .method("POST")
+        .header(CONTENT_TYPE, "application/x-www-form-urlencoded");
+
+    if let Some(authorization) = authorization {
+        request = request.header("Authorization", authorization);
+    }
+
+    let response = provider
+        .clone()
+        .oneshot(request.body(Body::from(form.to_string())).unwrap())
+        .await
+        .expect("Couldn't make the request!");
+
+    let status_code = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+
+    (status_code, serde_json::from_slice(&body).unwrap())
+}
+
+async fn verify(provider: &mut Router, token: &Value) -> Map<String, Value> {
+    let (_, jwks) = provider
+        .send("/.well-known/jwks.json".to_string(), HttpMethod::Get, None)
+        .await;
+    let jwks = serde_json::from_value::<JwkSet>(jwks).unwrap();
+
+    let token = token.as_str().unwrap();
+    let header = decode_header(token).unwrap();
+    let jwk = jwks.find(&header.kid.unwrap()).unwrap();
+
+    let mut validation = Validation::new(header.alg);
+    validation.set_audience(&["orders"]);
+    validation.set_issuer(&["http://auth.local"]);
+
+    decode::<Map<String, Value>>(
+        token,
+        &DecodingKey::from_jwk(jwk).unwrap(),
+        &validation,
+    )
+    .unwrap()
+    .claims
+}
+
+#[tokio::test]
+async fn should_register_oidc_provider() {
+    let (mut router, _) = app();
+
+    let (status_code, response_body) = router
+        .send(
+            REGISTER_OIDC_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(oidc_registration()),
+        )
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!("http://auth.local"), response_body["added"]["issuer"]);
+    assert!(response_body["added"]["keyId"].is_string());
+    assert_eq!(json!(600), response_body["added"]["tokenTtl"]);
+    assert_eq!(Value::Null, response_body["removed"]);
+
+    let (_, registrations) = router
+        .send("/info".to_string(), HttpMethod::Get, None)
+        .await;
+
+    assert_eq!(json!("4000"), registrations[0]["port"]);
+    assert_eq!(response_body["added"], registrations[0]["oidc"],);
+}
+
+#[tokio::test]
+async fn should_serve_discovery_document() {
+    let (_, mut provider) = register_provider().await;
+
+    let (status_code, discovery) = provider
+        .send(
+            "/.well-known/openid-configuration".to_string(),
+            HttpMethod::Get,
+            None,
+        )
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!("http://auth.local"), discovery["issuer"]);
+    assert_eq!(
+        json!("http://auth.local/.well-known/jwks.json"),
+        discovery["jwks_uri"]
+    );
+    assert_eq!(
+        json!("http://auth.local/token"),
+        discovery["token_endpoint"]
+    );
+    assert_eq!(
+        json!("http://auth.local/authorize"),
+        discovery["authorization_endpoint"]
+    );
+}
+
+#[tokio::test]
+async fn should_reject_authorization_requests() {
+    let (_, mut provider) = register_provider().await;
+
+    let (status_code, error) = provider
+        .send(
+            "/authorize?response_type=code&client_id=orders".to_string(),
+            HttpMethod::Get,
+            None,
+        )
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    assert_eq!(json!("unsupported_response_type"), error["error"]);
+}
+
+#[tokio::test]
+async fn should_issue_client_credentials_token() {
+    let (_, mut provider) = register_provider().await;
+
+    let (status_code, token) = request_token(
+        &provider,
+        Some(CLIENT_CREDENTIALS),
+        "grant_type=client_credentials&scope=orders%3Aread",
+    )
+    .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!("Bearer"), token["token_type"]);
+    assert_eq!(json!(600), token["expires_in"]);
+    assert_eq!(Value::Null, token["refresh_token"]);
+
+    let claims = verify(&mut provider, &token["access_token"]).await;
+    assert_eq!(json!("orders"), claims["sub"]);
+    assert_eq!(json!("orders:read"), claims["scope"]);
+    assert_eq!(json!("acme"), claims["tenant"]);
+}
+
+#[tokio::test]
+async fn should_reject_unknown_client() {
+    let (_, provider) = register_provider().await;
+
+    let (status_code, error) = request_token(
+        &provider,
+        None,
+        "grant_type=client_credentials&client_id=orders&client_secret=wrong",
+    )
+    .await;
+
+    assert_eq!(StatusCode::UNAUTHORIZED, status_code);
+    assert_eq!(
+        json!({
+            "error": "invalid_client",
+            "error_description": "Client authentication failed",
+        }),
+        error
+    );
+}
+
+#[tokio::test]
+async fn should_issue_password_token_with_id_token() {
+    let (_, mut provider) = register_provider().await;
+
+    let (status_code, token) = request_token(
+        &provider,
+        None,
+        "grant_type=password&client_id=orders&client_secret=orders-secret\
+            &username=alice&password=wonderland&scope=openid",
+    )
+    .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert!(token["refresh_token"].is_string());
+
+    let claims = verify(&mut provider, &token["id_token"]).await;
+    assert_eq!(json!("alice"), claims["sub"]);
+    assert_eq!(json!("alice@example.com"), claims["email"]);
+
+    let (status_code, error) = request_token(
+        &provider,
+        Some(CLIENT_CREDENTIALS),
+        "grant_type=password&username=alice&password=wrong",
+    )
+    .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    assert_eq!(json!("invalid_grant"), error["error"]);
+}
+
+#[tokio::test]
+async fn should_rotate_refresh_tokens() {
+    let (_, mut provider) = register_provider().await;
+
+    let (_, token) = request_token(
+        &provider,
+        Some(CLIENT_CREDENTIALS),
+        "grant_type=password&username=alice&password=wonderland",
+    )
+    .await;
+    let refresh_token = token["refresh_token"].as_str().unwrap();
+    let refresh_form =
+        format!("grant_type=refresh_token&refresh_token={refresh_token}");
+
+    let (status_code, refreshed) =
+        request_token(&provider, Some(CLIENT_CREDENTIALS), &refresh_form).await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_ne!(token["refresh_token"], refreshed["refresh_token"]);
+
+    let claims = verify(&mut provider, &refreshed["access_token"]).await;
+    assert_eq!(json!("alice"), claims["sub"]);
+
+    let (status_code, error) =
+        request_token(&provider, Some(CLIENT_CREDENTIALS), &refresh_form).await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    assert_eq!(json!("invalid_grant"), error["error"]);
+}
+
+#[tokio::test]
+async fn should_reject_unsupported_grant_type() {
+    let (_, provider) = register_provider().await;
+
+    let (status_code, error) = request_token(
+        &provider,
+        Some(CLIENT_CREDENTIALS),
+        "grant_type=authorization_code",
+    )
+    .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    assert_eq!(json!("unsupported_grant_type"), error["error"]);
+}
+
+#[tokio::test]
+async fn should_reject_invalid_token_ttl() {
+    let (mut router, _) = app();
+
+    let (status_code, response_body) = router
+        .send(
+            REGISTER_OIDC_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({ "port": "4000", "tokenTtl": 0 })),
+        )
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    assert_eq!(
+        json!({
+            "status": "FAILED",
+            "failureType": "Oidc",
+            "failureMessage": "`tokenTtl` must be greater than zero",
+        }),
+        response_body
+    );
+}
+
+#[tokio::test]
+async fn should_reject_reserved_claims() {
+    let (mut router, _) = app();
+
+    let (status_code, response_body) = router
+        .send(
+            REGISTER_OIDC_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "4000",
+                "users": [
+                    {
+                        "username": "alice",
+                        "password": "wonderland",
+                        "claims": { "sub": "admin" },
+                    },
+                ],
+            })),
+        )
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    assert_eq!(
+        json!({
+            "status": "FAILED",
+            "failureType": "Oidc",
+            "failureMessage": "Claim `sub` is reserved and cannot be configured",
+        }),
+        response_body
+    );
+}
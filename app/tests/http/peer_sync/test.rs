@@ -0,0 +1,363 @@
+use std::time::Duration;
+
+use api_gen::{
+    business::{
+        app_state::AppState,
+        peer_sync::{PeerSync, pull_peer_state},
+    },
+    client::admin_client::AdminClient,
+    model::{
+        http_method::HttpMethod,
+        internal::server_registration::ServerRegistration, port::Port,
+        request::registration_request::RegistrationRequest,
+    },
+};
+use axum::serve;
+use http::StatusCode;
+use serde_json::{Value, json};
+use tokio::{net::TcpListener, time::sleep};
+
+use crate::{
+    http::{
+        request_sender::RequestSender,
+        util::{app, app_with_peer_sync},
+    },
+    test_double::fake_connection_establisher::FakeConnectionEstablisher,
+};
+
+const PORT: &str = "4200";
+const OTHER_PORT: &str = "4201";
+
+struct Instance {
+    client: AdminClient,
+    connection_establisher: FakeConnectionEstablisher,
+}
+
+/// Starts one instance per id, each syncing with all of the others.
+async fn cluster(instance_ids: &[&str]) -> Vec<Instance> {
+    let mut listeners = vec![];
+    for _ in instance_ids {
+        listeners.push(TcpListener::bind("127.0.0.1:0").await.unwrap());
+    }
+    let urls = listeners
+        .iter()
+        .map(|listener| format!("http://{}", listener.local_addr().unwrap()))
+        .collect::<Vec<_>>();
+
+    let mut instances = vec![];
+    for (index, (instance_id, listener)) in
+        instance_ids.iter().zip(listeners).enumerate()
+    {
+        let peers = urls
+            .iter()
+            .enumerate()
+            .filter(|(peer_index, _)| *peer_index != index)
+            .map(|(_, url)| AdminClient::new(url))
+            .collect();
+        let (router, connection_establisher) =
+            app_with_peer_sync(PeerSync::new(instance_id, peers));
+        tokio::spawn(async move { serve(listener, router).await.unwrap() });
+
+        instances.push(Instance {
+            client: AdminClient::new(&urls[index]),
+            connection_establisher,
+        });
+    }
+
+    instances
+}
+
+fn registration(
+    port: &str,
+    path: &str,
+    response: Value,
+) -> RegistrationRequest {
+    serde_json::from_value(json!({
+        "port": port,
+        "method": "GET",
+        "path": path,
+        "response": response,
+    }))
+    .unwrap()
+}
+
+async fn routes(instance: &Instance) -> Value {
+    json!(instance.client.list().await.unwrap())
+}
+
+/// Waits until `instance` lists `expected`, peers are updated in the
+/// background.
+async fn eventually_lists(instance: &Instance, expected: &Value) {
+    for _ in 0..100 {
+        if routes(instance).await == *expected {
+            return;
+        }
+        sleep(Duration::from_millis(20)).await;
+    }
+
+    assert_eq!(*expected, routes(instance).await);
+}
+
+fn peer_update(counter: u64, origin: &str, path: Option<&str>) -> Value {
+    json!({
+        "version": { "counter": counter, "origin": origin },
+        "registrations": path.map(|path| json!([{
+            "method": "GET",
+            "path": path,
+            "response": [path],
+        }])),
+    })
+}
+
+#[tokio::test]
+async fn should_replicate_registrations_to_peers() {
+    let instances = cluster(&["a", "b", "c"]).await;
+
+    instances[0]
+        .client
+        .register(&registration(PORT, "/orders", json!(["order-1"])))
+        .await
+        .unwrap();
+    let expected = routes(&instances[0]).await;
+
+    for instance in &instances[1..] {
+        eventually_lists(instance, &expected).await;
+
+        let (status_code, response_body) = instance
+            .connection_establisher
+            .get_router(PORT)
+            .send("/orders".to_string(), HttpMethod::Get, None)
+            .await;
+        assert_eq!(StatusCode::OK, status_code);
+        assert_eq!(json!(["order-1"]), response_body);
+    }
+}
+
+#[tokio::test]
+async fn should_replicate_removals_and_resets_to_peers() {
+    let instances = cluster(&["a", "b"]).await;
+
+    instances[0]
+        .client
+        .register_batch(vec![
+            registration(PORT, "/orders", json!(["order-1"])),
+            registration(OTHER_PORT, "/users", json!(["user-1"])),
+        ])
+        .await
+        .unwrap();
+    eventually_lists(&instances[1], &routes(&instances[0]).await).await;
+
+    instances[1].client.remove(Port::new(4201)).await.unwrap();
+    let expected = routes(&instances[1]).await;
+    assert_eq!(1, expected.as_array().unwrap().len());
+    eventually_lists(&instances[0], &expected).await;
+
+    instances[0].client.reset(true).await.unwrap();
+    eventually_lists(&instances[1], &json!([])).await;
+}
+
+#[tokio::test]
+async fn should_settle_concurrent_changes_on_the_same_port() {
+    let instances = cluster(&["a", "b"]).await;
+
+    let orders = registration(PORT, "/orders", json!(["order-1"]));
+    let users = registration(PORT, "/users", json!(["user-1"]));
+    let (orders, users) = tokio::join!(
+        instances[0].client.register(&orders),
+        instances[1].client.register(&users),
+    );
+    orders.unwrap();
+    users.unwrap();
+
+    // Either change may win, but both instances must end up with it.
+    for _ in 0..100 {
+        let (first, second) =
+            (routes(&instances[0]).await, routes(&instances[1]).await);
+        if first == second {
+            let servers =
+                serde_json::from_value::<Vec<ServerRegistration>>(first)
+                    .unwrap();
+            assert_eq!(1, servers.len());
+            return;
+        }
+        sleep(Duration::from_millis(20)).await;
+    }
+
+    assert_eq!(routes(&instances[0]).await, routes(&instances[1]).await);
+}
+
+#[tokio::test]
+async fn should_only_apply_newer_versions() {
+    let (mut router, connection_establisher) =
+        app_with_peer_sync(PeerSync::new("a", vec![]));
+    let uri = format!("/sync/{PORT}");
+
+    let (status_code, response_body) = router
+        .send(
+            uri.clone(),
+            HttpMethod::Post,
+            Some(peer_update(2, "b", Some("/orders"))),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(
+        json!({
+            "port": "4200",
+            "version": { "counter": 2, "origin": "b" },
+        }),
+        response_body
+    );
+
+    for (counter, origin) in [(1, "c"), (2, "a"), (2, "b")] {
+        let (status_code, response_body) = router
+            .send(
+                uri.clone(),
+                HttpMethod::Post,
+                Some(peer_update(counter, origin, Some("/users"))),
+            )
+            .await;
+
+        assert_eq!(StatusCode::CONFLICT, status_code);
+        assert_eq!(
+            json!({
+                "status": "FAILED",
+                "failureType": "Conflict",
+                "failureMessage": format!("Version {counter}@{origin} of port 4200 is not newer than 2@b"),
+            }),
+            response_body
+        );
+    }
+
+    // Same counter, but a greater origin wins the tie.
+    let (status_code, _) = router
+        .send(
+            uri.clone(),
+            HttpMethod::Post,
+            Some(peer_update(2, "c", Some("/users"))),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+
+    let mut stub = connection_establisher.get_router(PORT);
+    let (status_code, _) = stub
+        .send("/orders".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(StatusCode::NOT_FOUND, status_code);
+    let (status_code, response_body) =
+        stub.send("/users".to_string(), HttpMethod::Get, None).await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!(["/users"]), response_body);
+
+    let (status_code, _) = router
+        .send(uri, HttpMethod::Post, Some(peer_update(3, "b", None)))
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    let (_, registrations) = router
+        .send("/info".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(json!([]), registrations);
+}
+
+#[tokio::test]
+async fn should_reject_updates_when_peer_sync_is_disabled() {
+    let (mut router, _) = app();
+
+    let (status_code, response_body) = router
+        .send(
+            format!("/sync/{PORT}"),
+            HttpMethod::Post,
+            Some(peer_update(1, "b", Some("/orders"))),
+        )
+        .await;
+
+    assert_eq!(StatusCode::NOT_FOUND, status_code);
+    assert_eq!(
+        json!({
+            "status": "FAILED",
+            "failureType": "NotFound",
+            "failureMessage": "Peer sync is not enabled on this instance",
+        }),
+        response_body
+    );
+}
+
+#[tokio::test]
+async fn should_reject_updates_for_ephemeral_port() {
+    let (mut router, _) = app_with_peer_sync(PeerSync::new("a", vec![]));
+
+    let (status_code, response_body) = router
+        .send(
+            "/sync/0".to_string(),
+            HttpMethod::Post,
+            Some(peer_update(1, "b", Some("/orders"))),
+        )
+        .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status_code);
+    assert_eq!(json!("Validation"), response_body["failureType"]);
+    assert_eq!(
+        json!([{
+            "field": "port",
+            "message": "Only a fixed port can be synced between peers",
+        }]),
+        response_body["fields"]
+    );
+}
+
+#[tokio::test]
+async fn should_pull_routes_from_peers_on_start() {
+    let instances = cluster(&["a", "b"]).await;
+    instances[0]
+        .client
+        .register(&registration(PORT, "/orders", json!(["order-1"])))
+        .await
+        .unwrap();
+    eventually_lists(&instances[1], &routes(&instances[0]).await).await;
+
+    let connection_establisher = FakeConnectionEstablisher::new();
+    let peers = instances
+        .iter()
+        .map(|instance| AdminClient::new(instance.client.base_url()))
+        .collect();
+    let app_state = AppState::new(connection_establisher.clone())
+        .with_peer_sync(Some(PeerSync::new("c", peers)));
+
+    assert_eq!(1, pull_peer_state(&app_state).await);
+
+    let (status_code, response_body) = connection_establisher
+        .get_router(PORT)
+        .send("/orders".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!(["order-1"]), response_body);
+}
+
+#[tokio::test]
+async fn should_retry_peers_that_are_down() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    drop(listener);
+
+    let peers = vec![AdminClient::new(&format!("http://{address}"))];
+    let (router, _) = app_with_peer_sync(PeerSync::new("a", peers));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let client =
+        AdminClient::new(&format!("http://{}", listener.local_addr().unwrap()));
+    tokio::spawn(async move { serve(listener, router).await.unwrap() });
+
+    client
+        .register(&registration(PORT, "/orders", json!(["order-1"])))
+        .await
+        .unwrap();
+    sleep(Duration::from_millis(300)).await;
+
+    let (router, _) = app_with_peer_sync(PeerSync::new("b", vec![]));
+    let listener = TcpListener::bind(address).await.unwrap();
+    tokio::spawn(async move { serve(listener, router).await.unwrap() });
+
+    let peer = Instance {
+        client: AdminClient::new(&format!("http://{address}")),
+        connection_establisher: FakeConnectionEstablisher::new(),
+    };
+    eventually_lists(&peer, &json!(client.list().await.unwrap())).await;
+}
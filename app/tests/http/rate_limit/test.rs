@@ -0,0 +1,311 @@
+use std::time::Duration;
+
+use api_gen::model::http_method::HttpMethod;
+use axum::{Router, body::Body, extract::Request};
+use http::{HeaderMap, StatusCode, header::RETRY_AFTER};
+use http_body_util::BodyExt;
+use serde_json::{Value, json};
+use tokio::time::sleep;
+use tower::ServiceExt;
+
+use crate::http::{
+    register::registrar::Registrar, request_sender::RequestSender, util::app,
+};
+
+fn registration(path: &str, rate_limit: Option<Value>) -> Value {
+    let mut registration = json!({
+        "port": "3000",
+        "method": "GET",
+        "path": path,
+        "response": "Hello World!",
+    });
+
+    if let Some(rate_limit) = rate_limit {
+        registration["rateLimit"] = rate_limit;
+    }
+
+    registration
+}
+
+async fn send(
+    router: &Router,
+    path: &str,
+    headers: &[(&str, &str)],
+) -> (StatusCode, HeaderMap, Value) {
+    let mut request = Request::builder().uri(path).method("GET");
+
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+
+    let response = router
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .expect("Couldn't make the request!");
+
+    let status_code = response.status();
+    let headers = response.headers().clone();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+
+    (status_code, headers, serde_json::from_slice(&body).unwrap())
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> &'a str {
+    headers.get(name).unwrap().to_str().unwrap()
+}
+
+#[tokio::test]
+async fn should_limit_route_with_fixed_window() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .register(
+            registration(
+                "/hello",
+                Some(json!({
+                    "strategy": "fixedWindow",
+                    "limit": 2,
+                    "windowSeconds": 60,
+                })),
+            ),
+            |status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+    let stub = connection_establisher.get_router("3000");
+
+    for remaining in ["1", "0"] {
+        let (status_code, headers, _) = send(&stub, "/hello", &[]).await;
+
+        assert_eq!(StatusCode::OK, status_code);
+        assert_eq!("2", header(&headers, "x-ratelimit-limit"));
+        assert_eq!(remaining, header(&headers, "x-ratelimit-remaining"));
+    }
+
+    let (status_code, headers, response_body) =
+        send(&stub, "/hello", &[]).await;
+
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, status_code);
+    assert_eq!("0", header(&headers, "x-ratelimit-remaining"));
+    let retry_after = header(&headers, RETRY_AFTER.as_str())
+        .parse::<u64>()
+        .unwrap();
+    assert!((1..=60).contains(&retry_after));
+    assert_eq!(
+        json!({
+            "status": "FAILED",
+            "failureType": "TooManyRequests",
+            "failureMessage": "Rate limit exceeded",
+        }),
+        response_body
+    );
+}
+
+#[tokio::test]
+async fn should_limit_separately_per_key_header() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .register(
+            registration(
+                "/hello",
+                Some(json!({
+                    "strategy": "fixedWindow",
+                    "limit": 1,
+                    "windowSeconds": 60,
+                    "keyHeader": "X-Api-Key",
+                })),
+            ),
+            |status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+    let stub = connection_establisher.get_router("3000");
+
+    let (status_code, _, _) =
+        send(&stub, "/hello", &[("X-Api-Key", "first")]).await;
+    assert_eq!(StatusCode::OK, status_code);
+
+    let (status_code, _, _) =
+        send(&stub, "/hello", &[("X-Api-Key", "first")]).await;
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, status_code);
+
+    let (status_code, _, _) =
+        send(&stub, "/hello", &[("X-Api-Key", "second")]).await;
+    assert_eq!(StatusCode::OK, status_code);
+}
+
+#[tokio::test]
+async fn should_limit_whole_port_with_token_bucket() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .register_many(
+            json!([registration("/hello", None), registration("/bye", None)]),
+            |_, status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+
+    let (status_code, response_body) = router
+        .send(
+            "/register/rate-limit".to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "3000",
+                "strategy": "tokenBucket",
+                "capacity": 1,
+                "refillPerSecond": 0.001,
+            })),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(
+        json!({
+            "added": {
+                "strategy": "tokenBucket",
+                "capacity": 1,
+                "refillPerSecond": 0.001,
+            },
+            "removed": null,
+        }),
+        response_body
+    );
+
+    let stub = connection_establisher.get_router("3000");
+
+    let (status_code, _, _) = send(&stub, "/hello", &[]).await;
+    assert_eq!(StatusCode::OK, status_code);
+
+    let (status_code, headers, _) = send(&stub, "/bye", &[]).await;
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, status_code);
+    assert_eq!("1000", header(&headers, RETRY_AFTER.as_str()));
+}
+
+#[tokio::test]
+async fn should_inspect_and_reset_counters() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .register(
+            registration(
+                "/hello",
+                Some(json!({
+                    "strategy": "fixedWindow",
+                    "limit": 1,
+                    "windowSeconds": 60,
+                    "keyHeader": "X-Api-Key",
+                })),
+            ),
+            |status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+    let stub = connection_establisher.get_router("3000");
+
+    send(&stub, "/hello", &[("X-Api-Key", "first")]).await;
+
+    let (status_code, counters) = router
+        .send("/rate-limits/3000".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(1, counters.as_array().unwrap().len());
+    assert_eq!(json!("GET"), counters[0]["method"]);
+    assert_eq!(json!("/hello"), counters[0]["path"]);
+    assert_eq!(json!("first"), counters[0]["key"]);
+    assert_eq!(json!(1), counters[0]["limit"]);
+    assert_eq!(json!(0), counters[0]["remaining"]);
+
+    let (status_code, counters) = router
+        .send("/rate-limits/3000".to_string(), HttpMethod::Delete, None)
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(1, counters.as_array().unwrap().len());
+
+    let (status_code, counters) = router
+        .send("/rate-limits/3000".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!([]), counters);
+
+    let (status_code, _, _) =
+        send(&stub, "/hello", &[("X-Api-Key", "first")]).await;
+    assert_eq!(StatusCode::OK, status_code);
+}
+
+#[tokio::test]
+async fn should_evict_expired_counters() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .register(
+            registration(
+                "/hello",
+                Some(json!({
+                    "strategy": "fixedWindow",
+                    "limit": 1,
+                    "windowSeconds": 1,
+                    "keyHeader": "X-Api-Key",
+                })),
+            ),
+            |status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+    let stub = connection_establisher.get_router("3000");
+
+    send(&stub, "/hello", &[("X-Api-Key", "first")]).await;
+    sleep(Duration::from_millis(1100)).await;
+    send(&stub, "/hello", &[("X-Api-Key", "second")]).await;
+
+    let (status_code, counters) = router
+        .send("/rate-limits/3000".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(1, counters.as_array().unwrap().len());
+    assert_eq!(json!("second"), counters[0]["key"]);
+}
+
+#[tokio::test]
+async fn should_return_not_found_for_unknown_port() {
+    let (mut router, _) = app();
+
+    let (status_code, response_body) = router
+        .send("/rate-limits/3000".to_string(), HttpMethod::Get, None)
+        .await;
+
+    assert_eq!(StatusCode::NOT_FOUND, status_code);
+    assert_eq!(
+        json!({
+            "status": "FAILED",
+            "failureType": "NotFound",
+            "failureMessage": "No server is running on port 3000",
+        }),
+        response_body
+    );
+}
+
+#[tokio::test]
+async fn should_reject_invalid_rate_limit() {
+    let (mut router, _) = app();
+
+    router
+        .register(
+            registration(
+                "/hello",
+                Some(json!({
+                    "strategy": "fixedWindow",
+                    "limit": 0,
+                    "windowSeconds": 60,
+                })),
+            ),
+            |status_code, response_body| {
+                assert_eq!(StatusCode::BAD_REQUEST, status_code);
+                assert_eq!(
+                    json!({
+                        "status": "FAILED",
+                        "failureType": "RateLimit",
+                        "failureMessage": "`limit` must be greater than zero",
+                    }),
+                    response_body
+                );
+            },
+        )
+        .await;
+}
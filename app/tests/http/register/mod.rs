@@ -1,4 +1,4 @@
 pub(crate) mod registrar;
-mod registration_verifier;
+pub(crate) mod registration_verifier;
 mod test;
-mod util;
+pub(crate) mod util;
@@ -5,7 +5,7 @@ use serde_json::Value;
 
 use crate::http::request_sender::RequestSender;
 
-const REGISTER_ENDPOINT: &'static str = "/register";
+const REGISTER_ENDPOINT: &str = "/register";
 
 pub(crate) trait Registrar {
     fn register<F>(
@@ -14,7 +14,7 @@ pub(crate) trait Registrar {
         assertion: F,
     ) -> impl Future<Output = ()>
     where
-        F: Fn(StatusCode, Value) -> ();
+        F: Fn(StatusCode, Value);
 
     fn register_many<F>(
         &mut self,
@@ -22,13 +22,13 @@ pub(crate) trait Registrar {
         assertion: F,
     ) -> impl Future<Output = ()>
     where
-        F: Fn(usize, StatusCode, Value) -> ();
+        F: Fn(usize, StatusCode, Value);
 }
 
 impl Registrar for Router {
     async fn register<F>(&mut self, registration_request: Value, assertion: F)
     where
-        F: Fn(StatusCode, Value) -> (),
+        F: Fn(StatusCode, Value),
     {
         let (status_code, response_body) = self
             .send(
@@ -46,7 +46,7 @@ impl Registrar for Router {
         registration_request: Value,
         assertion: F,
     ) where
-        F: Fn(usize, StatusCode, Value) -> (),
+        F: Fn(usize, StatusCode, Value),
     {
         if let Value::Array(requests) = registration_request {
             for (idx, request) in requests.into_iter().enumerate() {
@@ -7,7 +7,7 @@ use crate::{
     test_double::fake_connection_establisher::FakeConnectionEstablisher,
 };
 
-pub(super) struct RegistrationVerifier {
+pub(crate) struct RegistrationVerifier {
     connection_establisher: FakeConnectionEstablisher,
     port: String,
     method: HttpMethod,
@@ -16,7 +16,7 @@ pub(super) struct RegistrationVerifier {
 }
 
 impl RegistrationVerifier {
-    pub(super) async fn request<F>(&self, assertion: F)
+    pub(crate) async fn request<F>(&self, assertion: F)
     where
         F: Fn(StatusCode, Value),
     {
@@ -33,14 +33,15 @@ impl RegistrationVerifier {
         assertion(status_code, response_body);
     }
 
-    pub(super) fn builder(
+    pub(crate) fn builder(
         connection_establisher: FakeConnectionEstablisher,
     ) -> RegistrationVerifierBuilder {
         RegistrationVerifierBuilder::new(connection_establisher)
     }
 }
 
-pub(super) struct RegistrationVerifierBuilder {
+#[derive(Clone)]
+pub(crate) struct RegistrationVerifierBuilder {
     connection_establisher: FakeConnectionEstablisher,
     port: Option<String>,
     method: Option<HttpMethod>,
@@ -59,27 +60,27 @@ impl RegistrationVerifierBuilder {
         }
     }
 
-    pub(super) fn port(mut self, port: &str) -> Self {
+    pub(crate) fn port(mut self, port: &str) -> Self {
         self.port = Some(port.to_string());
         self
     }
 
-    pub(super) fn method(mut self, method: HttpMethod) -> Self {
+    pub(crate) fn method(mut self, method: HttpMethod) -> Self {
         self.method = Some(method);
         self
     }
 
-    pub(super) fn path(mut self, path: &str) -> Self {
+    pub(crate) fn path(mut self, path: &str) -> Self {
         self.path = Some(path.to_string());
         self
     }
 
-    pub(super) fn body(mut self, body: Value) -> Self {
+    pub(crate) fn body(mut self, body: Value) -> Self {
         self.body = Some(body);
         self
     }
 
-    pub(super) fn build(&self) -> RegistrationVerifier {
+    pub(crate) fn build(&self) -> RegistrationVerifier {
         self.validate();
 
         RegistrationVerifier {
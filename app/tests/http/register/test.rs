@@ -1,3 +1,5 @@
+use std::sync::{Arc, Mutex};
+
 use api_gen::model::http_method::HttpMethod;
 use http::StatusCode;
 use serde_json::json;
@@ -20,6 +22,7 @@ async fn should_succeed_for_valid_payload() {
                 assert_eq!(StatusCode::OK, status_code);
                 assert_eq!(
                     json!({
+                        "port": "3000",
                         "added": {
                             "method": "GET",
                             "path": "/hello",
@@ -50,6 +53,7 @@ async fn should_accept_http_method_in_case_insensitive_fashion() {
                 assert_eq!(StatusCode::OK, status_code);
                 assert_eq!(
                     json!({
+                        "port": "3000",
                         "added": {
                             "method": "POST",
                             "path": "/hello",
@@ -90,7 +94,7 @@ async fn should_fail_for_missing_attributes() {
     router
         .register(
             json!({
-                "method": "GET",
+                "port": "3000",
                 "path": "/hello",
                 "response": "Hello World!",
             }),
@@ -104,7 +108,7 @@ async fn should_fail_for_missing_attributes() {
         .register(
             json!({
                 "port": "3000",
-                "path": "/hello",
+                "method": "GET",
                 "response": "Hello World!",
             }),
             |status_code, _| {
@@ -117,24 +121,114 @@ async fn should_fail_for_missing_attributes() {
         .register(
             json!({
                 "port": "3000",
+                "path": "/hello",
                 "method": "GET",
-                "response": "Hello World!",
             }),
             |status_code, _| {
                 assert_eq!(StatusCode::BAD_REQUEST, status_code);
             },
         )
         .await;
+}
+
+#[tokio::test]
+async fn should_list_every_invalid_field() {
+    let (mut router, _) = app();
+
+    router
+        .register(
+            json!({
+                "port": "99999",
+                "method": "GET",
+                "path": "hello",
+                "response": "Hello World!",
+            }),
+            |status_code, response_body| {
+                assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status_code);
+                assert_eq!(
+                    json!({
+                        "status": "FAILED",
+                        "failureType": "Validation",
+                        "failureMessage": "Invalid fields: `port`, `path`",
+                        "fields": [
+                            {
+                                "field": "port",
+                                "message": "`99999` is not a port, expected a number between 1 and 65535",
+                            },
+                            {
+                                "field": "path",
+                                "message": "`hello` must start with `/`",
+                            },
+                        ],
+                    }),
+                    response_body
+                );
+            },
+        )
+        .await;
+}
+
+#[tokio::test]
+async fn should_reject_paths_axum_cannot_route() {
+    let (mut router, _) = app();
+
+    for (path, message) in [
+        (
+            "/users/:id",
+            "`/users/:id` uses `:name` or `*name` captures, use `{name}` or `{*name}` instead",
+        ),
+        ("/hello world", "`/hello world` must not contain whitespace"),
+    ] {
+        router
+            .register(
+                json!({
+                    "port": "3000",
+                    "method": "GET",
+                    "path": path,
+                    "response": "Hello World!",
+                }),
+                |status_code, response_body| {
+                    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status_code);
+                    assert_eq!(
+                        json!([{ "field": "path", "message": message }]),
+                        response_body["fields"]
+                    );
+                },
+            )
+            .await;
+    }
 
     router
         .register(
             json!({
                 "port": "3000",
-                "path": "/hello",
                 "method": "GET",
+                "path": "/users/{id",
+                "response": "Hello World!",
             }),
-            |status_code, _| {
-                assert_eq!(StatusCode::BAD_REQUEST, status_code);
+            |status_code, response_body| {
+                assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status_code);
+                assert_eq!(json!("path"), response_body["fields"][0]["field"]);
+            },
+        )
+        .await;
+}
+
+#[tokio::test]
+async fn should_accept_numeric_port() {
+    let (mut router, _) = app();
+
+    router
+        .register(
+            json!({
+                "port": 3000,
+                "method": "GET",
+                "path": "/hello",
+                "response": "Hello World!",
+            }),
+            |status_code, registration_response| {
+                assert_eq!(StatusCode::OK, status_code);
+                assert_eq!(json!("3000"), registration_response["port"]);
             },
         )
         .await;
@@ -156,6 +250,7 @@ async fn should_respond_at_registered_endpoint() {
                 assert_eq!(StatusCode::OK, status_code);
                 assert_eq!(
                     json!({
+                        "port": "3000",
                         "added": {
                             "method": "GET",
                             "path": "/hello",
@@ -209,6 +304,7 @@ async fn should_override_registered_response_with_new_one() {
                 if idx == 0 {
                     assert_eq!(
                         json!({
+                            "port": "3000",
                             "added": {
                                 "method": "GET",
                                 "path": "/hello",
@@ -221,6 +317,7 @@ async fn should_override_registered_response_with_new_one() {
                 } else if idx == 1 {
                     assert_eq!(
                         json!({
+                            "port": "3000",
                             "added": {
                                 "method": "GET",
                                 "path": "/hello",
@@ -518,3 +615,49 @@ async fn should_handle_different_paths_differently() {
         })
         .await;
 }
+
+#[tokio::test]
+async fn should_allocate_ephemeral_port_when_port_is_omitted() {
+    let (mut router, registration_verifier_builder) = app();
+    let mut allocated_ports = vec![];
+
+    for (path, port) in [("/first", None), ("/second", Some("0"))] {
+        let mut registration_request = json!({
+            "method": "GET",
+            "path": path,
+            "response": path,
+        });
+        if let Some(port) = port {
+            registration_request["port"] = json!(port);
+        }
+
+        let allocated_port = Arc::new(Mutex::new(String::new()));
+        router
+            .register(registration_request, |status_code, response_body| {
+                assert_eq!(StatusCode::OK, status_code);
+                assert_eq!(json!(null), response_body["removed"]);
+                *allocated_port.lock().unwrap() =
+                    response_body["port"].as_str().unwrap().to_string();
+            })
+            .await;
+
+        let allocated_port = allocated_port.lock().unwrap().clone();
+        assert_ne!("0", allocated_port);
+
+        registration_verifier_builder
+            .clone()
+            .port(&allocated_port)
+            .method(HttpMethod::Get)
+            .path(path)
+            .build()
+            .request(|status_code, response_body| {
+                assert_eq!(StatusCode::OK, status_code);
+                assert_eq!(json!(path), response_body);
+            })
+            .await;
+
+        allocated_ports.push(allocated_port);
+    }
+
+    assert_ne!(allocated_ports[0], allocated_ports[1]);
+}
@@ -7,7 +7,7 @@ use crate::http::{
     util,
 };
 
-pub(super) fn app() -> (Router, RegistrationVerifierBuilder) {
+pub(crate) fn app() -> (Router, RegistrationVerifierBuilder) {
     let (router, connection_establisher) = util::app();
     let registration_verifier_builder =
         RegistrationVerifier::builder(connection_establisher);
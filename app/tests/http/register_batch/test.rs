@@ -0,0 +1,396 @@
+use api_gen::model::http_method::HttpMethod;
+use axum::{Router, body::Body, extract::Request};
+use http::StatusCode;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use crate::http::{
+    register::registrar::Registrar, request_sender::RequestSender, util::app,
+};
+
+const REGISTER_BATCH_ENDPOINT: &str = "/register/batch";
+const LIST_REGISTRATIONS_ENDPOINT: &str = "/info";
+
+async fn register_batch(
+    router: &mut Router,
+    registrations: Value,
+) -> (StatusCode, Value) {
+    router
+        .send(
+            REGISTER_BATCH_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({ "registrations": registrations })),
+        )
+        .await
+}
+
+async fn list_registrations(router: &mut Router) -> Value {
+    let (status_code, registrations) = router
+        .send(
+            LIST_REGISTRATIONS_ENDPOINT.to_string(),
+            HttpMethod::Get,
+            None,
+        )
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+
+    registrations
+}
+
+async fn status_of(router: &Router, path: &str) -> StatusCode {
+    router
+        .clone()
+        .oneshot(Request::get(path).body(Body::empty()).unwrap())
+        .await
+        .expect("Couldn't make the request!")
+        .status()
+}
+
+#[tokio::test]
+async fn should_register_routes_across_ports() {
+    let (mut router, connection_establisher) = app();
+
+    let (status_code, response_body) = register_batch(
+        &mut router,
+        json!([
+            {
+                "port": "3000",
+                "method": "GET",
+                "path": "/orders",
+                "response": ["order-1"],
+            },
+            {
+                "port": "3001",
+                "method": "GET",
+                "path": "/users",
+                "response": ["user-1"],
+            },
+            {
+                "port": "3000",
+                "method": "POST",
+                "path": "/orders",
+                "response": "created",
+            },
+        ]),
+    )
+    .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(
+        json!({
+            "registrations": [
+                {
+                    "port": "3000",
+                    "added": {
+                        "method": "GET",
+                        "path": "/orders",
+                        "response": ["order-1"],
+                    },
+                    "removed": null,
+                },
+                {
+                    "port": "3001",
+                    "added": {
+                        "method": "GET",
+                        "path": "/users",
+                        "response": ["user-1"],
+                    },
+                    "removed": null,
+                },
+                {
+                    "port": "3000",
+                    "added": {
+                        "method": "POST",
+                        "path": "/orders",
+                        "response": "created",
+                    },
+                    "removed": null,
+                },
+            ],
+        }),
+        response_body
+    );
+
+    let orders = connection_establisher.get_router("3000");
+    assert_eq!(StatusCode::OK, status_of(&orders, "/orders").await);
+
+    let users = connection_establisher.get_router("3001");
+    assert_eq!(StatusCode::OK, status_of(&users, "/users").await);
+}
+
+#[tokio::test]
+async fn should_report_removed_registrations() {
+    let (mut router, _) = app();
+
+    router
+        .register(
+            json!({
+                "port": "3000",
+                "method": "GET",
+                "path": "/orders",
+                "response": "old",
+            }),
+            |status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+
+    let (status_code, response_body) = register_batch(
+        &mut router,
+        json!([{
+            "port": "3000",
+            "method": "GET",
+            "path": "/orders",
+            "response": "new",
+        }]),
+    )
+    .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(
+        json!({
+            "method": "GET",
+            "path": "/orders",
+            "response": "old",
+        }),
+        response_body["registrations"][0]["removed"]
+    );
+}
+
+#[tokio::test]
+async fn should_reject_whole_batch_for_invalid_entries() {
+    let (mut router, _) = app();
+
+    let (status_code, response_body) = register_batch(
+        &mut router,
+        json!([
+            {
+                "port": "3000",
+                "method": "GET",
+                "path": "/orders",
+                "response": ["order-1"],
+            },
+            {
+                "port": "3001",
+                "method": "GET",
+                "path": "/users",
+                "rateLimit": {
+                    "strategy": "fixedWindow",
+                    "limit": 0,
+                    "windowSeconds": 1,
+                },
+                "response": ["user-1"],
+            },
+        ]),
+    )
+    .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    assert_eq!(
+        json!({
+            "status": "FAILED",
+            "failureType": "Batch",
+            "failureMessage": "1 of 2 registrations failed, no changes were applied",
+            "failures": [{
+                "index": 1,
+                "port": "3001",
+                "method": "GET",
+                "path": "/users",
+                "failureType": "RateLimit",
+                "failureMessage": "`limit` must be greater than zero",
+            }],
+        }),
+        response_body
+    );
+
+    assert_eq!(json!([]), list_registrations(&mut router).await);
+}
+
+#[tokio::test]
+async fn should_list_invalid_fields_of_every_entry() {
+    let (mut router, _) = app();
+
+    let (status_code, response_body) = register_batch(
+        &mut router,
+        json!([
+            {
+                "port": "3000",
+                "method": "GET",
+                "path": "/orders",
+                "response": ["order-1"],
+            },
+            {
+                "port": "abc",
+                "method": "GET",
+                "path": "users",
+                "response": ["user-1"],
+            },
+        ]),
+    )
+    .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status_code);
+    assert_eq!(json!("Validation"), response_body["failureType"]);
+    assert_eq!(
+        json!([
+            {
+                "field": "registrations[1].port",
+                "message": "`abc` is not a port, expected a number between 1 and 65535",
+            },
+            {
+                "field": "registrations[1].path",
+                "message": "`users` must start with `/`",
+            },
+        ]),
+        response_body["fields"]
+    );
+
+    assert_eq!(json!([]), list_registrations(&mut router).await);
+}
+
+#[tokio::test]
+async fn should_restore_applied_ports_when_a_port_fails() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .register(
+            json!({
+                "port": "3000",
+                "method": "GET",
+                "path": "/orders",
+                "response": ["order-1"],
+            }),
+            |status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+    connection_establisher.make_unavailable("3001");
+
+    let (status_code, response_body) = register_batch(
+        &mut router,
+        json!([
+            {
+                "port": "3000",
+                "method": "GET",
+                "path": "/invoices",
+                "response": ["invoice-1"],
+            },
+            {
+                "port": "3001",
+                "method": "GET",
+                "path": "/users",
+                "response": ["user-1"],
+            },
+        ]),
+    )
+    .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    assert_eq!(json!("Batch"), response_body["failureType"]);
+    assert_eq!(
+        json!([{
+            "index": 1,
+            "port": "3001",
+            "method": "GET",
+            "path": "/users",
+            "failureType": "Connection",
+            "failureMessage": "Failed to establish connection, port 3001 is unavailable",
+        }]),
+        response_body["failures"]
+    );
+
+    assert_eq!(
+        json!([{
+            "port": "3000",
+            "registrations": [{
+                "method": "GET",
+                "path": "/orders",
+                "response": ["order-1"],
+            }],
+        }]),
+        list_registrations(&mut router).await
+    );
+
+    let orders = connection_establisher.get_router("3000");
+    assert_eq!(StatusCode::OK, status_of(&orders, "/orders").await);
+    assert_eq!(StatusCode::NOT_FOUND, status_of(&orders, "/invoices").await);
+}
+
+#[tokio::test]
+async fn should_keep_previous_server_when_its_port_is_unavailable() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .register(
+            json!({
+                "port": "3000",
+                "method": "GET",
+                "path": "/orders",
+                "response": ["order-1"],
+            }),
+            |status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+    connection_establisher.make_unavailable("3000");
+
+    let (status_code, response_body) = register_batch(
+        &mut router,
+        json!([{
+            "port": "3000",
+            "method": "GET",
+            "path": "/invoices",
+            "response": ["invoice-1"],
+        }]),
+    )
+    .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    assert_eq!(
+        json!("1 of 1 registrations failed, no changes were applied"),
+        response_body["failureMessage"]
+    );
+    assert_eq!(
+        json!([{
+            "port": "3000",
+            "registrations": [{
+                "method": "GET",
+                "path": "/orders",
+                "response": ["order-1"],
+            }],
+        }]),
+        list_registrations(&mut router).await
+    );
+
+    let orders = connection_establisher.get_router("3000");
+    assert_eq!(StatusCode::OK, status_of(&orders, "/orders").await);
+}
+
+#[tokio::test]
+async fn should_share_one_ephemeral_port_between_entries_without_port() {
+    let (mut router, connection_establisher) = app();
+
+    let (status_code, response_body) = register_batch(
+        &mut router,
+        json!([
+            {
+                "method": "GET",
+                "path": "/orders",
+                "response": ["order-1"],
+            },
+            {
+                "port": "0",
+                "method": "GET",
+                "path": "/users",
+                "response": ["user-1"],
+            },
+        ]),
+    )
+    .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    let port = response_body["registrations"][0]["port"].as_str().unwrap();
+    assert_ne!("0", port);
+    assert_eq!(json!(port), response_body["registrations"][1]["port"]);
+
+    let stub = connection_establisher.get_router(port);
+    assert_eq!(StatusCode::OK, status_of(&stub, "/orders").await);
+    assert_eq!(StatusCode::OK, status_of(&stub, "/users").await);
+}
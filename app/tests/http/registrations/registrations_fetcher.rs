@@ -6,7 +6,7 @@ use http::StatusCode;
 
 use crate::http::request_sender::RequestSender;
 
-const LIST_REGISTRATIONS_ENDPOINT: &'static str = "/info";
+const LIST_REGISTRATIONS_ENDPOINT: &str = "/info";
 
 pub(super) trait RegistrationsFetcher {
     fn fetch_registrations<F>(
@@ -24,16 +24,15 @@ impl ServerRegistrationExtensions for Vec<ServerRegistration> {
     ) -> Option<&Registration> {
         self.iter()
             .filter_map(|sr| {
-                if sr.port == port {
+                if sr.port.to_string() == port {
                     Some(&sr.registrations)
                 } else {
                     None
                 }
             })
             .flatten()
-            .filter(|r| {
-                r.method == method && r.path == path && r.response == response
+            .rfind(|r| {
+                r.method == method && &*r.path == path && r.response == response
             })
-            .last()
     }
 }
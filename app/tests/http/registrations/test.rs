@@ -47,33 +47,36 @@ async fn should_list_all_registrations() {
 
             assert_eq!(2, registrations.len());
 
-            assert!(matches!(
-                registrations.find(
-                    "3000",
-                    HttpMethod::Get,
-                    "/hello",
-                    json!("Hello World!"),
-                ),
-                Some(_),
-            ));
-            assert!(matches!(
-                registrations.find(
-                    "3000",
-                    HttpMethod::Post,
-                    "/hello",
-                    json!("Hello World!!!"),
-                ),
-                Some(_),
-            ));
-            assert!(matches!(
-                registrations.find(
-                    "3001",
-                    HttpMethod::Get,
-                    "/hello",
-                    json!("Hello World!"),
-                ),
-                Some(_),
-            ));
+            assert!(
+                registrations
+                    .find(
+                        "3000",
+                        HttpMethod::Get,
+                        "/hello",
+                        json!("Hello World!"),
+                    )
+                    .is_some()
+            );
+            assert!(
+                registrations
+                    .find(
+                        "3000",
+                        HttpMethod::Post,
+                        "/hello",
+                        json!("Hello World!!!"),
+                    )
+                    .is_some()
+            );
+            assert!(
+                registrations
+                    .find(
+                        "3001",
+                        HttpMethod::Get,
+                        "/hello",
+                        json!("Hello World!"),
+                    )
+                    .is_some()
+            );
         })
         .await;
 }
@@ -114,33 +117,36 @@ async fn should_not_list_overriden_registrations() {
 
             assert_eq!(2, registrations.len());
 
-            assert!(matches!(
-                registrations.find(
-                    "3000",
-                    HttpMethod::Get,
-                    "/hello",
-                    json!("Hello World!"),
-                ),
-                Some(_),
-            ));
-            assert!(matches!(
-                registrations.find(
-                    "3000",
-                    HttpMethod::Post,
-                    "/hello",
-                    json!("Hello World!!!"),
-                ),
-                Some(_),
-            ));
-            assert!(matches!(
-                registrations.find(
-                    "3001",
-                    HttpMethod::Get,
-                    "/hello",
-                    json!("Hello World!"),
-                ),
-                Some(_),
-            ));
+            assert!(
+                registrations
+                    .find(
+                        "3000",
+                        HttpMethod::Get,
+                        "/hello",
+                        json!("Hello World!"),
+                    )
+                    .is_some()
+            );
+            assert!(
+                registrations
+                    .find(
+                        "3000",
+                        HttpMethod::Post,
+                        "/hello",
+                        json!("Hello World!!!"),
+                    )
+                    .is_some()
+            );
+            assert!(
+                registrations
+                    .find(
+                        "3001",
+                        HttpMethod::Get,
+                        "/hello",
+                        json!("Hello World!"),
+                    )
+                    .is_some()
+            );
         })
         .await;
 
@@ -176,42 +182,46 @@ async fn should_not_list_overriden_registrations() {
 
             assert_eq!(2, registrations.len());
 
-            assert!(matches!(
-                registrations.find(
-                    "3000",
-                    HttpMethod::Get,
-                    "/hello",
-                    json!("Hello World! (UPDATED)"),
-                ),
-                Some(_),
-            ));
-            assert!(matches!(
-                registrations.find(
-                    "3000",
-                    HttpMethod::Post,
-                    "/hello",
-                    json!("Hello World!!!"),
-                ),
-                Some(_),
-            ));
-            assert!(matches!(
-                registrations.find(
-                    "3000",
-                    HttpMethod::Patch,
-                    "/hello",
-                    json!("Hello World!!!"),
-                ),
-                Some(_),
-            ));
-            assert!(matches!(
-                registrations.find(
-                    "3001",
-                    HttpMethod::Get,
-                    "/hello",
-                    json!("Hello World! (UPDATED)"),
-                ),
-                Some(_),
-            ));
+            assert!(
+                registrations
+                    .find(
+                        "3000",
+                        HttpMethod::Get,
+                        "/hello",
+                        json!("Hello World! (UPDATED)"),
+                    )
+                    .is_some()
+            );
+            assert!(
+                registrations
+                    .find(
+                        "3000",
+                        HttpMethod::Post,
+                        "/hello",
+                        json!("Hello World!!!"),
+                    )
+                    .is_some()
+            );
+            assert!(
+                registrations
+                    .find(
+                        "3000",
+                        HttpMethod::Patch,
+                        "/hello",
+                        json!("Hello World!!!"),
+                    )
+                    .is_some()
+            );
+            assert!(
+                registrations
+                    .find(
+                        "3001",
+                        HttpMethod::Get,
+                        "/hello",
+                        json!("Hello World! (UPDATED)"),
+                    )
+                    .is_some()
+            );
         })
         .await;
 }
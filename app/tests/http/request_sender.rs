@@ -76,7 +76,7 @@ impl ResponseExtractor for Response<Body> {
             .expect("Failed to collect the body from response!");
         let bytes = collection.to_bytes();
 
-        if bytes.len() == 0 {
+        if bytes.is_empty() {
             Value::Null
         } else {
             serde_json::from_slice::<Value>(&bytes)
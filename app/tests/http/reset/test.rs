@@ -0,0 +1,148 @@
+use api_gen::model::http_method::HttpMethod;
+use axum::Router;
+use http::StatusCode;
+use serde_json::{Value, json};
+
+use crate::http::{
+    register::registrar::Registrar,
+    request_sender::RequestSender,
+    util::{app, app_with_pinned_registrations},
+};
+
+const LIST_REGISTRATIONS_ENDPOINT: &str = "/info";
+
+fn pinned_registration() -> Value {
+    json!({
+        "port": "3000",
+        "method": "GET",
+        "path": "/health",
+        "response": "UP",
+    })
+}
+
+async fn register_stubs(router: &mut Router) {
+    router
+        .register_many(
+            json!([
+                {
+                    "port": "3000",
+                    "method": "GET",
+                    "path": "/orders",
+                    "response": ["order-1"],
+                },
+                {
+                    "port": "3001",
+                    "method": "GET",
+                    "path": "/users",
+                    "response": ["user-1"],
+                },
+            ]),
+            |_, status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+}
+
+async fn reset(router: &mut Router, uri: &str) -> (StatusCode, Value) {
+    router.send(uri.to_string(), HttpMethod::Post, None).await
+}
+
+async fn list_registrations(router: &mut Router) -> Value {
+    let (_, registrations) = router
+        .send(
+            LIST_REGISTRATIONS_ENDPOINT.to_string(),
+            HttpMethod::Get,
+            None,
+        )
+        .await;
+
+    registrations
+}
+
+#[tokio::test]
+async fn should_remove_all_servers() {
+    let (mut router, _) = app();
+    register_stubs(&mut router).await;
+
+    let (status_code, response_body) = reset(&mut router, "/reset").await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(2, response_body["removed"].as_array().unwrap().len());
+    assert_eq!(json!([]), response_body["pinned"]);
+    assert_eq!(json!([]), list_registrations(&mut router).await);
+}
+
+#[tokio::test]
+async fn should_restore_pinned_registrations() {
+    let (mut router, _) = app_with_pinned_registrations(vec![
+        serde_json::from_value(pinned_registration()).unwrap(),
+    ]);
+    register_stubs(&mut router).await;
+
+    let (status_code, response_body) = reset(&mut router, "/reset").await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(
+        json!([{
+            "method": "GET",
+            "path": "/health",
+            "response": "UP",
+        }]),
+        response_body["pinned"]
+    );
+    assert_eq!(
+        json!([{
+            "port": "3000",
+            "registrations": [{
+                "method": "GET",
+                "path": "/health",
+                "response": "UP",
+            }],
+        }]),
+        list_registrations(&mut router).await
+    );
+}
+
+#[tokio::test]
+async fn should_drop_pinned_registrations_on_request() {
+    let (mut router, _) = app_with_pinned_registrations(vec![
+        serde_json::from_value(pinned_registration()).unwrap(),
+    ]);
+    register_stubs(&mut router).await;
+
+    let (status_code, response_body) =
+        reset(&mut router, "/reset?keepPinned=false").await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!([]), response_body["pinned"]);
+    assert_eq!(json!([]), list_registrations(&mut router).await);
+}
+
+#[tokio::test]
+async fn should_allow_registering_again_after_reset() {
+    let (mut router, connection_establisher) = app();
+    register_stubs(&mut router).await;
+
+    reset(&mut router, "/reset").await;
+
+    router
+        .register(
+            json!({
+                "port": "3001",
+                "method": "GET",
+                "path": "/users",
+                "response": ["user-2"],
+            }),
+            |status_code, response_body| {
+                assert_eq!(StatusCode::OK, status_code);
+                assert_eq!(json!(null), response_body["removed"]);
+            },
+        )
+        .await;
+
+    let (status_code, response_body) = connection_establisher
+        .get_router("3001")
+        .send("/users".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!(["user-2"]), response_body);
+}
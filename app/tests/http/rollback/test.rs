@@ -0,0 +1,143 @@
+use api_gen::model::http_method::HttpMethod;
+use axum::Router;
+use http::StatusCode;
+use serde_json::{Value, json};
+
+use crate::http::{
+    register::registrar::Registrar, request_sender::RequestSender, util::app,
+};
+
+const PORT: &str = "3800";
+
+async fn register_orders(router: &mut Router) {
+    router
+        .register(
+            json!({
+                "port": PORT,
+                "method": "GET",
+                "path": "/orders",
+                "response": ["order"],
+            }),
+            |status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+}
+
+async fn list_registrations(router: &mut Router) -> Value {
+    let (_, response_body) = router
+        .send("/info".to_string(), HttpMethod::Get, None)
+        .await;
+
+    response_body
+}
+
+fn orders_only() -> Value {
+    json!([{
+        "port": PORT,
+        "registrations": [{
+            "method": "GET",
+            "path": "/orders",
+            "response": ["order"],
+        }],
+    }])
+}
+
+#[tokio::test]
+async fn should_keep_previous_server_when_registration_fails_to_bind() {
+    let (mut router, connection_establisher) = app();
+    register_orders(&mut router).await;
+    connection_establisher.fail_next_connection(PORT);
+
+    router
+        .register(
+            json!({
+                "port": PORT,
+                "method": "GET",
+                "path": "/invoices",
+                "response": ["invoice"],
+            }),
+            |status_code, response_body| {
+                assert_eq!(StatusCode::BAD_REQUEST, status_code);
+                assert_eq!(
+                    json!({
+                        "status": "FAILED",
+                        "failureType": "Connection",
+                        "failureMessage": "Failed to establish connection, port 3800 is unavailable, the previous server on port 3800 keeps serving",
+                    }),
+                    response_body
+                );
+            },
+        )
+        .await;
+
+    assert_eq!(orders_only(), list_registrations(&mut router).await);
+
+    let mut stub = connection_establisher.get_router(PORT);
+    let (status_code, response_body) = stub
+        .send("/orders".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!(["order"]), response_body);
+
+    let (status_code, _) = stub
+        .send("/invoices".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(StatusCode::NOT_FOUND, status_code);
+}
+
+#[tokio::test]
+async fn should_keep_previous_server_while_its_port_is_unavailable() {
+    let (mut router, connection_establisher) = app();
+    register_orders(&mut router).await;
+    connection_establisher.make_unavailable(PORT);
+
+    router
+        .register(
+            json!({
+                "port": PORT,
+                "method": "GET",
+                "path": "/invoices",
+                "response": ["invoice"],
+            }),
+            |status_code, response_body| {
+                assert_eq!(StatusCode::BAD_REQUEST, status_code);
+                assert_eq!(
+                    json!(
+                        "Failed to establish connection, port 3800 is unavailable, the previous server on port 3800 keeps serving"
+                    ),
+                    response_body["failureMessage"]
+                );
+            },
+        )
+        .await;
+
+    assert_eq!(orders_only(), list_registrations(&mut router).await);
+
+    let (status_code, _) = connection_establisher
+        .get_router(PORT)
+        .send("/orders".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+}
+
+#[tokio::test]
+async fn should_keep_previous_server_when_port_update_fails_to_bind() {
+    let (mut router, connection_establisher) = app();
+    register_orders(&mut router).await;
+    connection_establisher.fail_next_connection(PORT);
+
+    let (status_code, response_body) = router
+        .send(
+            "/register/cors".to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": PORT,
+                "allowedOrigins": ["https://app.example.com"],
+            })),
+        )
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    assert_eq!(json!("Connection"), response_body["failureType"]);
+    assert_eq!(orders_only(), list_registrations(&mut router).await);
+}
@@ -0,0 +1,196 @@
+use api_gen::{
+    business::server::path_matching::PathMatching,
+    model::http_method::HttpMethod,
+};
+use http::StatusCode;
+use serde_json::{Value, json};
+
+use crate::http::{
+    register::registrar::Registrar,
+    request_sender::RequestSender,
+    util::{app, app_with_path_matching},
+};
+
+const PORT: &str = "3700";
+
+fn registration(path: &str, response: Value) -> Value {
+    json!({
+        "port": PORT,
+        "method": "GET",
+        "path": path,
+        "response": response,
+    })
+}
+
+#[tokio::test]
+async fn should_treat_trailing_and_duplicate_slashes_as_same_route() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .register(registration("/hello", json!("first")), |_, _| {})
+        .await;
+    router
+        .register(
+            registration("//hello/", json!("second")),
+            |status_code, response_body| {
+                assert_eq!(StatusCode::OK, status_code);
+                assert_eq!(json!("/hello"), response_body["added"]["path"]);
+                assert_eq!(json!("/hello"), response_body["removed"]["path"]);
+            },
+        )
+        .await;
+
+    let mut stub = connection_establisher.get_router(PORT);
+    for path in ["/hello", "/hello/", "//hello", "/hello//?page=1"] {
+        let (status_code, response_body) =
+            stub.send(path.to_string(), HttpMethod::Get, None).await;
+
+        assert_eq!(StatusCode::OK, status_code, "`{path}` should be served");
+        assert_eq!(json!("second"), response_body);
+    }
+}
+
+#[tokio::test]
+async fn should_canonicalize_percent_encoding() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .register(
+            registration("/caf%c3%a9/%7Euser", json!("café")),
+            |status_code, response_body| {
+                assert_eq!(StatusCode::OK, status_code);
+                assert_eq!(
+                    json!("/caf%C3%A9/~user"),
+                    response_body["added"]["path"]
+                );
+            },
+        )
+        .await;
+
+    let mut stub = connection_establisher.get_router(PORT);
+    let (status_code, response_body) = stub
+        .send("/caf%C3%A9/%7euser".to_string(), HttpMethod::Get, None)
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!("café"), response_body);
+}
+
+#[tokio::test]
+async fn should_reject_invalid_percent_encoding() {
+    let (mut router, _) = app();
+
+    router
+        .register(
+            registration("/orders/%zz", json!([])),
+            |status_code, response_body| {
+                assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status_code);
+                assert_eq!(
+                    json!([{
+                        "field": "path",
+                        "message": "`/orders/%zz` contains an invalid percent-encoding",
+                    }]),
+                    response_body["fields"]
+                );
+            },
+        )
+        .await;
+}
+
+#[tokio::test]
+async fn should_reject_conflicting_route_and_keep_server() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .register(registration("/users/{id}", json!("user")), |_, _| {})
+        .await;
+    router
+        .register(
+            json!({
+                "port": PORT,
+                "method": "POST",
+                "path": "/users/{name}",
+                "response": "created",
+            }),
+            |status_code, response_body| {
+                assert_eq!(StatusCode::CONFLICT, status_code);
+                assert_eq!(
+                    json!({
+                        "status": "FAILED",
+                        "failureType": "Conflict",
+                        "failureMessage": "`/users/{name}` conflicts with the registered route `/users/{id}`",
+                    }),
+                    response_body
+                );
+            },
+        )
+        .await;
+
+    let mut stub = connection_establisher.get_router(PORT);
+    let (status_code, response_body) = stub
+        .send("/users/1".to_string(), HttpMethod::Get, None)
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!("user"), response_body);
+}
+
+#[tokio::test]
+async fn should_reject_conflicting_routes_within_batch() {
+    let (mut router, _) = app();
+
+    let (status_code, response_body) = router
+        .send(
+            "/register/batch".to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "registrations": [
+                    registration("/files/{*rest}", json!("file")),
+                    registration("/files/{*path}", json!("file")),
+                    registration("/health", json!("UP")),
+                ],
+            })),
+        )
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    assert_eq!(json!(1), response_body["failures"][0]["index"]);
+    assert_eq!(
+        json!(
+            "`/files/{*path}` conflicts with the registered route `/files/{*rest}`"
+        ),
+        response_body["failures"][0]["failureMessage"]
+    );
+    assert_eq!(1, response_body["failures"].as_array().unwrap().len());
+}
+
+#[tokio::test]
+async fn should_match_paths_case_insensitively() {
+    let (mut router, connection_establisher) =
+        app_with_path_matching(PathMatching::CaseInsensitive);
+
+    router
+        .register(registration("/Orders/{Id}", json!("order")), |_, _| {})
+        .await;
+    router
+        .register(
+            registration("/ORDERS/{id}", json!("replaced")),
+            |status_code, response_body| {
+                assert_eq!(StatusCode::OK, status_code);
+                assert_eq!(
+                    json!("/orders/{id}"),
+                    response_body["removed"]["path"]
+                );
+            },
+        )
+        .await;
+
+    let mut stub = connection_establisher.get_router(PORT);
+    for path in ["/orders/1", "/ORDERS/1", "/Orders/1/"] {
+        let (status_code, response_body) =
+            stub.send(path.to_string(), HttpMethod::Get, None).await;
+
+        assert_eq!(StatusCode::OK, status_code, "`{path}` should be served");
+        assert_eq!(json!("replaced"), response_body);
+    }
+}
@@ -0,0 +1,291 @@
+use api_gen::model::http_method::HttpMethod;
+use axum::Router;
+use http::StatusCode;
+use serde_json::{Value, json};
+
+use crate::http::{
+    register::registrar::Registrar, request_sender::RequestSender, util::app,
+};
+
+async fn register_stubs(router: &mut Router) {
+    router
+        .register_many(
+            json!([
+                {
+                    "port": "3001",
+                    "method": "POST",
+                    "path": "/users",
+                    "response": "created",
+                },
+                {
+                    "port": "3001",
+                    "method": "GET",
+                    "path": "/users/{id}",
+                    "response": "user",
+                },
+                {
+                    "port": "3001",
+                    "method": "GET",
+                    "path": "/users",
+                    "response": ["user"],
+                },
+                {
+                    "port": "3000",
+                    "method": "GET",
+                    "path": "/orders",
+                    "response": ["order"],
+                },
+                {
+                    "port": "3002",
+                    "method": "GET",
+                    "path": "/health",
+                    "response": "UP",
+                },
+            ]),
+            |_, status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+}
+
+async fn get(router: &mut Router, uri: &str) -> (StatusCode, Value) {
+    router.send(uri.to_string(), HttpMethod::Get, None).await
+}
+
+fn ports(page: &Value) -> Vec<&str> {
+    page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|server| server["port"].as_str().unwrap())
+        .collect()
+}
+
+fn routes(page: &Value) -> Vec<String> {
+    page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|route| format!("{} {}", route["method"], route["path"]))
+        .collect()
+}
+
+#[tokio::test]
+async fn should_list_servers_ordered_by_port() {
+    let (mut router, _) = app();
+    register_stubs(&mut router).await;
+
+    let (status_code, response_body) = get(&mut router, "/servers").await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(vec!["3000", "3001", "3002"], ports(&response_body));
+    assert_eq!(json!(3), response_body["total"]);
+    assert_eq!(
+        json!({
+            "port": "3001",
+            "routes": 3,
+            "grpc": 0,
+            "socket": false,
+            "oidc": false,
+            "cors": false,
+            "rateLimit": false,
+        }),
+        response_body["items"][1]
+    );
+}
+
+#[tokio::test]
+async fn should_paginate_servers() {
+    let (mut router, _) = app();
+    register_stubs(&mut router).await;
+
+    let (status_code, response_body) =
+        get(&mut router, "/servers?offset=1&limit=1").await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(vec!["3001"], ports(&response_body));
+    assert_eq!(json!(3), response_body["total"]);
+    assert_eq!(json!(1), response_body["offset"]);
+    assert_eq!(json!(1), response_body["limit"]);
+
+    let (_, response_body) =
+        get(&mut router, "/servers?offset=5&limit=1").await;
+    assert_eq!(Vec::<&str>::new(), ports(&response_body));
+}
+
+#[tokio::test]
+async fn should_reject_invalid_query_parameters() {
+    let (mut router, _) = app();
+    register_stubs(&mut router).await;
+
+    let (status_code, response_body) =
+        get(&mut router, "/servers?offset=-1&limit=501").await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status_code);
+    assert_eq!(
+        json!({
+            "status": "FAILED",
+            "failureType": "Validation",
+            "failureMessage": "Invalid fields: `offset`, `limit`",
+            "fields": [
+                {
+                    "field": "offset",
+                    "message": "`-1` is not a count, expected a non-negative number",
+                },
+                {
+                    "field": "limit",
+                    "message": "`501` exceeds the maximum of 500",
+                },
+            ],
+        }),
+        response_body
+    );
+
+    let (status_code, response_body) =
+        get(&mut router, "/servers/3001/routes?method=FETCH").await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status_code);
+    assert_eq!(json!("Validation"), response_body["failureType"]);
+    assert_eq!(json!("method"), response_body["fields"][0]["field"]);
+}
+
+#[tokio::test]
+async fn should_get_server_by_port() {
+    let (mut router, _) = app();
+    register_stubs(&mut router).await;
+
+    let (status_code, response_body) = get(&mut router, "/servers/3000").await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(
+        json!({
+            "port": "3000",
+            "registrations": [{
+                "method": "GET",
+                "path": "/orders",
+                "response": ["order"],
+            }],
+        }),
+        response_body
+    );
+}
+
+#[tokio::test]
+async fn should_remove_server_by_port() {
+    let (mut router, _) = app();
+    register_stubs(&mut router).await;
+
+    let (status_code, response_body) = router
+        .send("/servers/3000".to_string(), HttpMethod::Delete, None)
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!("3000"), response_body["port"]);
+
+    let (_, response_body) = get(&mut router, "/servers").await;
+    assert_eq!(vec!["3001", "3002"], ports(&response_body));
+
+    let (status_code, _) = router
+        .send("/servers/3000".to_string(), HttpMethod::Delete, None)
+        .await;
+    assert_eq!(StatusCode::NOT_FOUND, status_code);
+}
+
+#[tokio::test]
+async fn should_respond_not_found_for_unknown_port() {
+    let (mut router, _) = app();
+    register_stubs(&mut router).await;
+
+    for uri in ["/servers/4000", "/servers/4000/routes"] {
+        let (status_code, response_body) = get(&mut router, uri).await;
+
+        assert_eq!(StatusCode::NOT_FOUND, status_code);
+        assert_eq!(
+            json!({
+                "status": "FAILED",
+                "failureType": "NotFound",
+                "failureMessage": "No server is running on port 4000",
+            }),
+            response_body
+        );
+    }
+}
+
+#[tokio::test]
+async fn should_reject_invalid_port_in_path() {
+    let (mut router, _) = app();
+
+    for uri in ["/servers/abc", "/servers/abc/routes"] {
+        let (status_code, response_body) = get(&mut router, uri).await;
+
+        assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status_code);
+        assert_eq!(
+            json!({
+                "status": "FAILED",
+                "failureType": "Validation",
+                "failureMessage": "Invalid fields: `port`",
+                "fields": [{
+                    "field": "port",
+                    "message": "`abc` is not a port, expected a number between 1 and 65535",
+                }],
+            }),
+            response_body
+        );
+    }
+}
+
+#[tokio::test]
+async fn should_list_routes_ordered_by_path_and_method() {
+    let (mut router, _) = app();
+    register_stubs(&mut router).await;
+
+    let (status_code, response_body) =
+        get(&mut router, "/servers/3001/routes").await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(
+        vec![
+            r#""GET" "/users""#,
+            r#""POST" "/users""#,
+            r#""GET" "/users/{id}""#,
+        ],
+        routes(&response_body)
+    );
+    assert_eq!(json!(3), response_body["total"]);
+}
+
+#[tokio::test]
+async fn should_filter_routes_by_method_and_path() {
+    let (mut router, _) = app();
+    register_stubs(&mut router).await;
+
+    let (_, response_body) =
+        get(&mut router, "/servers/3001/routes?method=POST").await;
+    assert_eq!(vec![r#""POST" "/users""#], routes(&response_body));
+
+    let (_, response_body) =
+        get(&mut router, "/servers/3001/routes?path=/users").await;
+    assert_eq!(
+        vec![r#""GET" "/users""#, r#""POST" "/users""#],
+        routes(&response_body)
+    );
+
+    let (_, response_body) = get(
+        &mut router,
+        "/servers/3001/routes?method=GET&path=/users/42",
+    )
+    .await;
+    assert_eq!(vec![r#""GET" "/users/{id}""#], routes(&response_body));
+    assert_eq!(json!(1), response_body["total"]);
+}
+
+#[tokio::test]
+async fn should_paginate_routes() {
+    let (mut router, _) = app();
+    register_stubs(&mut router).await;
+
+    let (_, response_body) =
+        get(&mut router, "/servers/3001/routes?offset=2&limit=5").await;
+
+    assert_eq!(vec![r#""GET" "/users/{id}""#], routes(&response_body));
+    assert_eq!(json!(3), response_body["total"]);
+}
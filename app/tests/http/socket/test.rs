@@ -0,0 +1,252 @@
+use api_gen::{
+    business::server::socket::{MAX_CAPTURED_MESSAGES, SocketReply},
+    model::http_method::HttpMethod,
+};
+use http::StatusCode;
+use serde_json::{Value, json};
+
+use crate::http::{request_sender::RequestSender, util::app};
+
+const REGISTER_SOCKET_ENDPOINT: &str = "/register/socket";
+
+fn captured_endpoint(port: &str) -> String {
+    format!("/sockets/{port}/captured")
+}
+
+fn replies(replies: Vec<SocketReply>) -> Vec<String> {
+    replies
+        .into_iter()
+        .map(|reply| String::from_utf8(reply.data).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn should_reply_to_matching_lines() {
+    let (mut router, connection_establisher) = app();
+
+    let (status_code, response_body) = router
+        .send(
+            REGISTER_SOCKET_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "4000",
+                "protocol": "TCP",
+                "banner": "220 ready\r\n",
+                "rules": [
+                    { "pattern": "^PING$", "reply": "PONG\n" },
+                    { "pattern": "^HELLO (\\S+)$", "reply": "HI $1\n" },
+                    { "pattern": "^QUIT$", "reply": "BYE\n", "close": true },
+                ],
+            })),
+        )
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(Value::Null, response_body["removed"]);
+
+    let handler = connection_establisher.get_socket_handler("4000");
+
+    assert_eq!(Some("220 ready\r\n".as_bytes()), handler.banner());
+    assert_eq!(
+        vec!["PONG\n", "HI api-gen\n"],
+        replies(
+            handler
+                .respond("peer", b"PING\r\nHELLO api-gen\nUNKNOWN\n")
+                .await
+        )
+    );
+
+    let quit = handler.respond("peer", b"QUIT\n").await;
+    assert_eq!(1, quit.len());
+    assert!(quit[0].close);
+}
+
+#[tokio::test]
+async fn should_echo_unmatched_frames() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .send(
+            REGISTER_SOCKET_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "4000",
+                "protocol": "UDP",
+                "framing": "RAW",
+                "echo": true,
+            })),
+        )
+        .await;
+
+    let handler = connection_establisher.get_socket_handler("4000");
+
+    assert_eq!(
+        vec!["metric:1|c\nmetric:2|c"],
+        replies(handler.respond("peer", b"metric:1|c\nmetric:2|c").await)
+    );
+}
+
+#[tokio::test]
+async fn should_capture_received_frames() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .send(
+            REGISTER_SOCKET_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "8125",
+                "protocol": "UDP",
+                "capture": true,
+            })),
+        )
+        .await;
+
+    let handler = connection_establisher.get_socket_handler("8125");
+    let _ = handler
+        .respond("127.0.0.1:5000", b"metric:1|c\nmetric:2|c\n")
+        .await;
+
+    let (status_code, captured) = router
+        .send(captured_endpoint("8125"), HttpMethod::Get, None)
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(2, captured.as_array().unwrap().len());
+    assert_eq!(json!("127.0.0.1:5000"), captured[0]["peer"]);
+    assert_eq!(json!("metric:1|c"), captured[0]["data"]);
+    assert_eq!(json!("bWV0cmljOjF8Yw=="), captured[0]["base64"]);
+    assert_eq!(json!("metric:2|c"), captured[1]["data"]);
+
+    let (status_code, cleared) = router
+        .send(captured_endpoint("8125"), HttpMethod::Delete, None)
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(captured, cleared);
+
+    let (_, captured) = router
+        .send(captured_endpoint("8125"), HttpMethod::Get, None)
+        .await;
+
+    assert_eq!(json!([]), captured);
+}
+
+#[tokio::test]
+async fn should_keep_only_the_latest_captured_frames() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .send(
+            REGISTER_SOCKET_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "8125",
+                "protocol": "UDP",
+                "capture": true,
+            })),
+        )
+        .await;
+
+    let frames = (0..MAX_CAPTURED_MESSAGES + 2)
+        .map(|index| format!("metric:{index}|c\n"))
+        .collect::<String>();
+
+    let handler = connection_establisher.get_socket_handler("8125");
+    let _ = handler.respond("127.0.0.1:5000", frames.as_bytes()).await;
+
+    let (status_code, captured) = router
+        .send(captured_endpoint("8125"), HttpMethod::Get, None)
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(MAX_CAPTURED_MESSAGES, captured.as_array().unwrap().len());
+    assert_eq!(json!("metric:2|c"), captured[0]["data"]);
+}
+
+#[tokio::test]
+async fn should_fail_for_invalid_socket_stubs() {
+    let (mut router, _) = app();
+
+    let invalid_stubs = [
+        json!({
+            "port": "4000",
+            "protocol": "TCP",
+            "rules": [{ "pattern": "(unclosed", "reply": "" }],
+        }),
+        json!({
+            "port": "4000",
+            "protocol": "UDP",
+            "banner": "hello",
+        }),
+    ];
+
+    for invalid_stub in invalid_stubs {
+        let (status_code, response_body) = router
+            .send(
+                REGISTER_SOCKET_ENDPOINT.to_string(),
+                HttpMethod::Post,
+                Some(invalid_stub),
+            )
+            .await;
+
+        assert_eq!(StatusCode::BAD_REQUEST, status_code);
+        assert_eq!(json!("Socket"), response_body["failureType"]);
+    }
+}
+
+#[tokio::test]
+async fn should_fail_to_list_captured_frames_for_unknown_socket() {
+    let (mut router, _) = app();
+
+    let (status_code, response_body) = router
+        .send(captured_endpoint("4000"), HttpMethod::Get, None)
+        .await;
+
+    assert_eq!(StatusCode::NOT_FOUND, status_code);
+    assert_eq!(json!("NotFound"), response_body["failureType"]);
+}
+
+#[tokio::test]
+async fn should_replace_socket_stub_with_http_routes() {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .send(
+            REGISTER_SOCKET_ENDPOINT.to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "4000",
+                "protocol": "TCP",
+                "echo": true,
+            })),
+        )
+        .await;
+
+    router
+        .send(
+            "/register".to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "4000",
+                "method": "GET",
+                "path": "/hello",
+                "response": "Hello World!",
+            })),
+        )
+        .await;
+
+    let (status_code, response_body) = connection_establisher
+        .get_router("4000")
+        .send("/hello".to_string(), HttpMethod::Get, None)
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!("Hello World!"), response_body);
+
+    let (status_code, _) = router
+        .send(captured_endpoint("4000"), HttpMethod::Get, None)
+        .await;
+
+    assert_eq!(StatusCode::NOT_FOUND, status_code);
+}
@@ -0,0 +1,283 @@
+use std::{env, fs, path::PathBuf, process, sync::Arc};
+
+use api_gen::{
+    business::{
+        app_state::AppState,
+        batch_registration::{register_batch, restore_stored_registrations},
+        storage::{RegistrationStorage, sqlite_storage::SqliteStorage},
+        store::store_error::StoreError,
+    },
+    model::{
+        http_method::HttpMethod,
+        request::registration_request::RegistrationRequest,
+    },
+};
+use axum::Router;
+use http::StatusCode;
+use serde_json::json;
+
+use crate::{
+    http::{
+        register::registrar::Registrar, request_sender::RequestSender,
+        util::app_with_storage,
+    },
+    test_double::{
+        failing_registration_storage::FailingRegistrationStorage,
+        fake_connection_establisher::FakeConnectionEstablisher,
+    },
+};
+
+const ORDERS_PORT: &str = "4100";
+const USERS_PORT: &str = "4101";
+
+fn database(name: &str) -> PathBuf {
+    env::temp_dir()
+        .join(format!("api-gen-http-storage-{}-{name}.db", process::id()))
+}
+
+async fn register_stubs(router: &mut Router) {
+    router
+        .register_many(
+            json!([
+                {
+                    "port": ORDERS_PORT,
+                    "method": "GET",
+                    "path": "/orders",
+                    "response": ["order-1"],
+                },
+                {
+                    "port": USERS_PORT,
+                    "method": "GET",
+                    "path": "/users",
+                    "response": ["user-1"],
+                },
+            ]),
+            |_, status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+}
+
+async fn stored_paths(storage: &SqliteStorage) -> Vec<String> {
+    storage
+        .load()
+        .await
+        .unwrap()
+        .into_iter()
+        .flat_map(|stored_port| stored_port.registrations)
+        .map(|registration| {
+            format!("{}{}", registration.port, registration.path)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn should_mirror_registrations_into_storage() {
+    let file = database("mirror");
+    let storage = Arc::new(SqliteStorage::open(&file).unwrap());
+    let (mut router, _) = app_with_storage(storage.clone());
+
+    register_stubs(&mut router).await;
+    let registered = stored_paths(&storage).await;
+
+    let (status_code, _) = router
+        .send(format!("/servers/{USERS_PORT}"), HttpMethod::Delete, None)
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    let removed = stored_paths(&storage).await;
+
+    let (status_code, _) = router
+        .send("/reset".to_string(), HttpMethod::Post, None)
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    let reset = stored_paths(&storage).await;
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(vec!["4100/orders", "4101/users"], registered);
+    assert_eq!(vec!["4100/orders"], removed);
+    assert!(reset.is_empty());
+}
+
+#[tokio::test]
+async fn should_restore_registrations_from_storage() {
+    let file = database("restore");
+    let (mut router, _) =
+        app_with_storage(Arc::new(SqliteStorage::open(&file).unwrap()));
+    register_stubs(&mut router).await;
+    drop(router);
+
+    let connection_establisher = FakeConnectionEstablisher::new();
+    let app_state = AppState::new(connection_establisher.clone())
+        .with_storage(Arc::new(SqliteStorage::open(&file).unwrap()));
+    let restored = restore_stored_registrations(&app_state).await.ok();
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(Some(2), restored.map(|responses| responses.len()));
+    for (port, path, response) in [
+        (ORDERS_PORT, "/orders", json!(["order-1"])),
+        (USERS_PORT, "/users", json!(["user-1"])),
+    ] {
+        let (status_code, response_body) = connection_establisher
+            .get_router(port)
+            .send(path.to_string(), HttpMethod::Get, None)
+            .await;
+
+        assert_eq!(StatusCode::OK, status_code);
+        assert_eq!(response, response_body);
+    }
+}
+
+#[tokio::test]
+async fn should_keep_stored_registrations_across_shutdown() {
+    let file = database("shutdown");
+    let app_state = AppState::new(FakeConnectionEstablisher::new())
+        .with_storage(Arc::new(SqliteStorage::open(&file).unwrap()));
+    let registration: RegistrationRequest = serde_json::from_value(json!({
+        "port": ORDERS_PORT,
+        "method": "GET",
+        "path": "/orders",
+        "response": ["order-1"],
+    }))
+    .unwrap();
+    assert!(register_batch(&app_state, vec![registration]).await.is_ok());
+    assert!(app_state.shutdown_servers().await.is_ok());
+    drop(app_state);
+
+    let connection_establisher = FakeConnectionEstablisher::new();
+    let app_state = AppState::new(connection_establisher.clone())
+        .with_storage(Arc::new(SqliteStorage::open(&file).unwrap()));
+    let restored = restore_stored_registrations(&app_state).await.ok();
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(Some(1), restored.map(|responses| responses.len()));
+    let (status_code, response_body) = connection_establisher
+        .get_router(ORDERS_PORT)
+        .send("/orders".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!(["order-1"]), response_body);
+}
+
+#[tokio::test]
+async fn should_keep_previous_server_when_storage_fails() {
+    let storage = Arc::new(FailingRegistrationStorage::new(
+        StoreError::Backend("disk I/O error".to_string()),
+    ));
+    let (mut router, connection_establisher) =
+        app_with_storage(storage.clone());
+    register_stubs(&mut router).await;
+    storage.fail();
+
+    let invoices = json!({
+        "port": ORDERS_PORT,
+        "method": "GET",
+        "path": "/invoices",
+        "response": ["invoice-1"],
+    });
+    router
+        .register(invoices.clone(), |status_code, response_body| {
+            assert_eq!(StatusCode::SERVICE_UNAVAILABLE, status_code);
+            assert_eq!(
+                json!({
+                    "status": "FAILED",
+                    "failureType": "Store",
+                    "failureMessage": "disk I/O error, the previous server on port 4100 keeps serving",
+                }),
+                response_body
+            );
+        })
+        .await;
+    let (status_code, response_body) = router
+        .send(
+            "/register/batch".to_string(),
+            HttpMethod::Post,
+            Some(json!({ "registrations": [invoices] })),
+        )
+        .await;
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, status_code);
+    assert_eq!(json!("disk I/O error"), response_body["failureMessage"]);
+
+    let mut stub = connection_establisher.get_router(ORDERS_PORT);
+    let (status_code, response_body) = stub
+        .send("/orders".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!(["order-1"]), response_body);
+
+    let (status_code, _) = stub
+        .send("/invoices".to_string(), HttpMethod::Get, None)
+        .await;
+    assert_eq!(StatusCode::NOT_FOUND, status_code);
+}
+
+#[tokio::test]
+async fn should_restore_cors_policy_and_rate_limit_from_storage() {
+    let file = database("settings");
+    let (mut router, _) =
+        app_with_storage(Arc::new(SqliteStorage::open(&file).unwrap()));
+    register_stubs(&mut router).await;
+
+    let cors = json!({
+        "port": ORDERS_PORT,
+        "allowedOrigins": ["https://app.example.com"],
+    });
+    let rate_limit = json!({
+        "port": ORDERS_PORT,
+        "strategy": "fixedWindow",
+        "limit": 5,
+        "windowSeconds": 60,
+    });
+    for (uri, request) in [
+        ("/register/cors", cors),
+        ("/register/rate-limit", rate_limit),
+    ] {
+        let (status_code, _) = router
+            .send(uri.to_string(), HttpMethod::Post, Some(request))
+            .await;
+        assert_eq!(StatusCode::OK, status_code);
+    }
+    let (_, registered) = router
+        .send(format!("/servers/{ORDERS_PORT}"), HttpMethod::Get, None)
+        .await;
+
+    drop(router);
+
+    let app_state = Arc::new(
+        AppState::new(FakeConnectionEstablisher::new())
+            .with_storage(Arc::new(SqliteStorage::open(&file).unwrap())),
+    );
+    let restored = restore_stored_registrations(&app_state).await.ok();
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(Some(2), restored.map(|responses| responses.len()));
+    let (_, restored) = api_gen::app("8080", app_state)
+        .send(format!("/servers/{ORDERS_PORT}"), HttpMethod::Get, None)
+        .await;
+    assert_eq!(registered, restored);
+    assert!(restored["cors"].is_object());
+    assert!(restored["rateLimit"].is_object());
+}
+
+#[tokio::test]
+async fn should_accept_stubs_the_storage_cannot_keep() {
+    let file = database("unstorable");
+    let storage = Arc::new(SqliteStorage::open(&file).unwrap());
+    let (mut router, _) = app_with_storage(storage.clone());
+    register_stubs(&mut router).await;
+
+    let (status_code, _) = router
+        .send(
+            "/register/socket".to_string(),
+            HttpMethod::Post,
+            Some(json!({
+                "port": "4102",
+                "protocol": "TCP",
+                "echo": true,
+            })),
+        )
+        .await;
+    let stored = stored_paths(&storage).await;
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(vec!["4100/orders", "4101/users"], stored);
+}
@@ -0,0 +1,119 @@
+use std::{collections::HashMap, time::Duration};
+
+use api_gen::{
+    business::store::{
+        Store, memory_backend::MemoryBackend, store_error::StoreError,
+    },
+    model::http_method::HttpMethod,
+};
+use http::StatusCode;
+use serde_json::json;
+
+use crate::{
+    http::{
+        register::registrar::Registrar, request_sender::RequestSender,
+        util::app_with_server_store,
+    },
+    test_double::failing_store_backend::FailingStoreBackend,
+};
+
+const PORT: &str = "3900";
+
+#[tokio::test]
+async fn should_fail_registration_when_store_is_contended() {
+    let timeout = Duration::from_millis(250);
+    let (mut router, _) = app_with_server_store(Store::new(
+        FailingStoreBackend::new(StoreError::Contention(timeout)),
+    ));
+
+    router
+        .register(
+            json!({
+                "port": PORT,
+                "method": "GET",
+                "path": "/orders",
+                "response": [],
+            }),
+            |status_code, response_body| {
+                assert_eq!(StatusCode::SERVICE_UNAVAILABLE, status_code);
+                assert_eq!(
+                    json!({
+                        "status": "FAILED",
+                        "failureType": "Store",
+                        "failureMessage": "The state store is busy, it could not be locked within 250ms",
+                    }),
+                    response_body
+                );
+            },
+        )
+        .await;
+}
+
+#[tokio::test]
+async fn should_fail_queries_when_store_is_poisoned() {
+    let (mut router, _) = app_with_server_store(Store::new(
+        FailingStoreBackend::new(StoreError::Poisoned),
+    ));
+
+    for uri in [
+        "/info".to_string(),
+        "/servers".to_string(),
+        format!("/servers/{PORT}"),
+        format!("/servers/{PORT}/routes"),
+        format!("/rate-limits/{PORT}"),
+        format!("/sockets/{PORT}/captured"),
+    ] {
+        let (status_code, response_body) =
+            router.send(uri.clone(), HttpMethod::Get, None).await;
+
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, status_code, "{uri}");
+        assert_eq!(json!("Store"), response_body["failureType"], "{uri}");
+    }
+}
+
+#[tokio::test]
+async fn should_fail_reset_when_store_is_poisoned() {
+    let (mut router, _) = app_with_server_store(Store::new(
+        FailingStoreBackend::new(StoreError::Poisoned),
+    ));
+
+    let (status_code, response_body) = router
+        .send("/reset".to_string(), HttpMethod::Post, None)
+        .await;
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, status_code);
+    assert_eq!(
+        json!(
+            "The state store is poisoned, an earlier operation panicked while changing it"
+        ),
+        response_body["failureMessage"]
+    );
+}
+
+#[tokio::test]
+async fn should_serve_from_supplied_backend() {
+    let backend = MemoryBackend::new(HashMap::new())
+        .with_lock_timeout(Duration::from_secs(1));
+    let (mut router, connection_establisher) =
+        app_with_server_store(Store::new(backend));
+
+    router
+        .register(
+            json!({
+                "port": PORT,
+                "method": "GET",
+                "path": "/orders",
+                "response": ["order"],
+            }),
+            |status_code, _| assert_eq!(StatusCode::OK, status_code),
+        )
+        .await;
+
+    let (status_code, response_body) = connection_establisher
+        .get_router(PORT)
+        .send("/orders".to_string(), HttpMethod::Get, None)
+        .await;
+
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!(["order"]), response_body);
+}
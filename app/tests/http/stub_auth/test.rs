@@ -0,0 +1,347 @@
+use std::fs;
+
+use axum::{Router, body::Body, extract::Request};
+use base64::{Engine, prelude::BASE64_URL_SAFE_NO_PAD};
+use http::{StatusCode, header::WWW_AUTHENTICATE};
+use http_body_util::BodyExt;
+use jsonwebtoken::{EncodingKey, Header, encode};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use crate::http::{register::registrar::Registrar, util::app};
+
+const JWT_SECRET: &str = "jwt-secret";
+
+fn registration(auth: Value) -> Value {
+    json!({
+        "port": "3000",
+        "method": "GET",
+        "path": "/orders",
+        "auth": auth,
+        "response": ["order-1"],
+    })
+}
+
+async fn register_stub(auth: Value) -> Router {
+    let (mut router, connection_establisher) = app();
+
+    router
+        .register(registration(auth), |status_code, _| {
+            assert_eq!(StatusCode::OK, status_code);
+        })
+        .await;
+
+    connection_establisher.get_router("3000")
+}
+
+async fn send(
+    router: &Router,
+    headers: &[(&str, &str)],
+) -> (StatusCode, Option<String>, Value) {
+    let mut request = Request::builder().uri("/orders").method("GET");
+
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+
+    let response = router
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .expect("Couldn't make the request!");
+
+    let status_code = response.status();
+    let challenge = response
+        .headers()
+        .get(WWW_AUTHENTICATE)
+        .map(|challenge| challenge.to_str().unwrap().to_string());
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+
+    (
+        status_code,
+        challenge,
+        serde_json::from_slice(&body).unwrap(),
+    )
+}
+
+fn token(header: Header, claims: Value, secret: &str) -> String {
+    encode(
+        &header,
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn should_require_bearer_token() {
+    let router =
+        register_stub(json!({ "type": "bearer", "token": "stub-token" })).await;
+
+    let (status_code, challenge, response_body) = send(&router, &[]).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status_code);
+    assert_eq!(Some("Bearer".to_string()), challenge);
+    assert_eq!(
+        json!({
+            "status": "FAILED",
+            "failureType": "Unauthorized",
+            "failureMessage": "Missing or invalid bearer token",
+        }),
+        response_body
+    );
+
+    let (status_code, _, response_body) =
+        send(&router, &[("Authorization", "Bearer stub-token")]).await;
+    assert_eq!(StatusCode::OK, status_code);
+    assert_eq!(json!(["order-1"]), response_body);
+}
+
+#[tokio::test]
+async fn should_require_basic_credentials() {
+    let router = register_stub(json!({
+        "type": "basic",
+        "username": "admin",
+        "password": "secret",
+    }))
+    .await;
+
+    let (status_code, challenge, _) =
+        send(&router, &[("Authorization", "Basic YWRtaW46d3Jvbmc=")]).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status_code);
+    assert_eq!(Some("Basic realm=\"api-gen\"".to_string()), challenge);
+
+    let (status_code, _, _) =
+        send(&router, &[("Authorization", "Basic YWRtaW46c2VjcmV0")]).await;
+    assert_eq!(StatusCode::OK, status_code);
+}
+
+#[tokio::test]
+async fn should_require_api_key_header() {
+    let router = register_stub(json!({
+        "type": "apiKey",
+        "header": "X-Api-Key",
+        "value": "key-123",
+    }))
+    .await;
+
+    let (status_code, _, _) = send(&router, &[("X-Api-Key", "other")]).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status_code);
+
+    let (status_code, _, _) = send(&router, &[("X-Api-Key", "key-123")]).await;
+    assert_eq!(StatusCode::OK, status_code);
+}
+
+#[tokio::test]
+async fn should_validate_jwt_signature_and_claims() {
+    let router = register_stub(json!({
+        "type": "jwt",
+        "secret": JWT_SECRET,
+        "claims": { "scope": "orders:read", "role": "admin" },
+    }))
+    .await;
+
+    let valid = token(
+        Header::default(),
+        json!({ "scope": "profile orders:read", "role": ["user", "admin"] }),
+        JWT_SECRET,
+    );
+    let (status_code, _, _) =
+        send(&router, &[("Authorization", &format!("Bearer {valid}"))]).await;
+    assert_eq!(StatusCode::OK, status_code);
+
+    let missing_claim = token(
+        Header::default(),
+        json!({ "scope": "profile", "role": "admin" }),
+        JWT_SECRET,
+    );
+    let (status_code, _, response_body) = send(
+        &router,
+        &[("Authorization", &format!("Bearer {missing_claim}"))],
+    )
+    .await;
+    assert_eq!(StatusCode::FORBIDDEN, status_code);
+    assert_eq!(
+        json!({
+            "status": "FAILED",
+            "failureType": "Forbidden",
+            "failureMessage": "Token claim `scope` does not satisfy `\"orders:read\"`",
+        }),
+        response_body
+    );
+
+    let wrong_signature = token(
+        Header::default(),
+        json!({ "scope": "orders:read", "role": "admin" }),
+        "other-secret",
+    );
+    let (status_code, challenge, _) = send(
+        &router,
+        &[("Authorization", &format!("Bearer {wrong_signature}"))],
+    )
+    .await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status_code);
+    assert_eq!(Some("Bearer".to_string()), challenge);
+}
+
+#[tokio::test]
+async fn should_validate_jwt_against_jwks_file() {
+    let jwks_file = std::env::temp_dir()
+        .join(format!("api-gen-jwks-{}.json", std::process::id()));
+    fs::write(
+        &jwks_file,
+        json!({
+            "keys": [{
+                "kty": "oct",
+                "kid": "stub-key",
+                "alg": "HS256",
+                "k": BASE64_URL_SAFE_NO_PAD.encode(JWT_SECRET),
+            }],
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let router = register_stub(json!({
+        "type": "jwt",
+        "jwksFile": jwks_file.to_str().unwrap(),
+    }))
+    .await;
+
+    let header = Header {
+        kid: Some("stub-key".to_string()),
+        ..Default::default()
+    };
+    let valid = token(header, json!({ "sub": "user-1" }), JWT_SECRET);
+
+    let (status_code, _, _) =
+        send(&router, &[("Authorization", &format!("Bearer {valid}"))]).await;
+    assert_eq!(StatusCode::OK, status_code);
+
+    let header = Header {
+        kid: Some("unknown-key".to_string()),
+        ..Default::default()
+    };
+    let unknown_key = token(header, json!({ "sub": "user-1" }), JWT_SECRET);
+
+    let (status_code, _, _) = send(
+        &router,
+        &[("Authorization", &format!("Bearer {unknown_key}"))],
+    )
+    .await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status_code);
+
+    fs::remove_file(jwks_file).unwrap();
+}
+
+#[tokio::test]
+async fn should_keep_jwks_keys_loaded_at_registration() {
+    let jwks_file = std::env::temp_dir()
+        .join(format!("api-gen-jwks-loaded-{}.json", std::process::id()));
+    fs::write(
+        &jwks_file,
+        json!({
+            "keys": [{
+                "kty": "oct",
+                "alg": "HS256",
+                "k": BASE64_URL_SAFE_NO_PAD.encode(JWT_SECRET),
+            }],
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let router = register_stub(json!({
+        "type": "jwt",
+        "jwksFile": jwks_file.to_str().unwrap(),
+    }))
+    .await;
+    fs::remove_file(&jwks_file).unwrap();
+
+    let valid =
+        token(Header::default(), json!({ "sub": "user-1" }), JWT_SECRET);
+    let (status_code, _, _) =
+        send(&router, &[("Authorization", &format!("Bearer {valid}"))]).await;
+
+    assert_eq!(StatusCode::OK, status_code);
+}
+
+#[tokio::test]
+async fn should_reject_unusable_jwks_file() {
+    let jwks_file = std::env::temp_dir()
+        .join(format!("api-gen-jwks-empty-{}.json", std::process::id()));
+    fs::write(&jwks_file, json!({ "keys": [] }).to_string()).unwrap();
+    let (mut router, _) = app();
+
+    router
+        .register(
+            registration(json!({
+                "type": "jwt",
+                "jwksFile": jwks_file.to_str().unwrap(),
+            })),
+            |status_code, response_body| {
+                assert_eq!(StatusCode::BAD_REQUEST, status_code);
+                assert_eq!(
+                    json!(format!(
+                        "JWKS file `{}` does not contain any keys",
+                        jwks_file.to_str().unwrap()
+                    )),
+                    response_body["failureMessage"]
+                );
+            },
+        )
+        .await;
+    fs::remove_file(&jwks_file).unwrap();
+}
+
+#[tokio::test]
+async fn should_reject_invalid_auth_configuration() {
+    let (mut router, _) = app();
+
+    router
+        .register(
+            registration(json!({
+                "type": "jwt",
+                "secret": JWT_SECRET,
+                "jwksFile": "/tmp/jwks.json",
+            })),
+            |status_code, response_body| {
+                assert_eq!(StatusCode::BAD_REQUEST, status_code);
+                assert_eq!(
+                    json!({
+                        "status": "FAILED",
+                        "failureType": "Auth",
+                        "failureMessage": "Exactly one of `secret` and `jwksFile` must be configured for JWT auth",
+                    }),
+                    response_body
+                );
+            },
+        )
+        .await;
+}
+
+#[tokio::test]
+async fn should_echo_auth_in_registration_response() {
+    let (mut router, _) = app();
+
+    router
+        .register(
+            registration(json!({ "type": "bearer", "token": "stub-token" })),
+            |status_code, registration_response| {
+                assert_eq!(StatusCode::OK, status_code);
+                assert_eq!(
+                    json!({
+                        "port": "3000",
+                        "added": {
+                            "method": "GET",
+                            "path": "/orders",
+                            "auth": { "type": "bearer", "token": "stub-token" },
+                            "response": ["order-1"],
+                        },
+                        "removed": null,
+                    }),
+                    registration_response
+                );
+            },
+        )
+        .await;
+}
@@ -1,14 +1,61 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use api_gen::business::app_state::AppState;
+use api_gen::{
+    business::{
+        app_state::AppState,
+        peer_sync::PeerSync,
+        server::{path_matching::PathMatching, server::Server},
+        storage::RegistrationStorage,
+        store::Store,
+    },
+    model::{port::Port, request::registration_request::RegistrationRequest},
+    security::admin_auth::AdminAuth,
+};
 use axum::Router;
 
 use crate::test_double::fake_connection_establisher::FakeConnectionEstablisher;
 
-const DEFAULT_APPLICATION_PORT: &'static str = "8080";
+const DEFAULT_APPLICATION_PORT: &str = "8080";
 
 pub(super) fn app() -> (Router, FakeConnectionEstablisher) {
+    app_with_admin_auth(None)
+}
+
+pub(super) fn app_with_admin_auth(
+    admin_auth: Option<AdminAuth>,
+) -> (Router, FakeConnectionEstablisher) {
     let connection_establisher = FakeConnectionEstablisher::new();
+    let app_state = Arc::new(
+        AppState::new(connection_establisher.clone())
+            .with_admin_auth(admin_auth),
+    );
+
+    (
+        api_gen::app(DEFAULT_APPLICATION_PORT, app_state.clone()),
+        connection_establisher,
+    )
+}
+
+pub(super) fn app_with_pinned_registrations(
+    pinned_registrations: Vec<RegistrationRequest>,
+) -> (Router, FakeConnectionEstablisher) {
+    let connection_establisher = FakeConnectionEstablisher::new();
+    let app_state = Arc::new(
+        AppState::new(connection_establisher.clone())
+            .with_pinned_registrations(pinned_registrations),
+    );
+
+    (
+        api_gen::app(DEFAULT_APPLICATION_PORT, app_state.clone()),
+        connection_establisher,
+    )
+}
+
+pub(super) fn app_with_path_matching(
+    path_matching: PathMatching,
+) -> (Router, FakeConnectionEstablisher) {
+    let connection_establisher =
+        FakeConnectionEstablisher::new().with_path_matching(path_matching);
     let app_state = Arc::new(AppState::new(connection_establisher.clone()));
 
     (
@@ -16,3 +63,47 @@ pub(super) fn app() -> (Router, FakeConnectionEstablisher) {
         connection_establisher,
     )
 }
+
+pub(super) fn app_with_server_store(
+    servers: Store<HashMap<Port, Server>>,
+) -> (Router, FakeConnectionEstablisher) {
+    let connection_establisher = FakeConnectionEstablisher::new();
+    let app_state = Arc::new(
+        AppState::new(connection_establisher.clone())
+            .with_server_store(servers),
+    );
+
+    (
+        api_gen::app(DEFAULT_APPLICATION_PORT, app_state.clone()),
+        connection_establisher,
+    )
+}
+
+pub(super) fn app_with_peer_sync(
+    peer_sync: PeerSync,
+) -> (Router, FakeConnectionEstablisher) {
+    let connection_establisher = FakeConnectionEstablisher::new();
+    let app_state = Arc::new(
+        AppState::new(connection_establisher.clone())
+            .with_peer_sync(Some(peer_sync)),
+    );
+
+    (
+        api_gen::app(DEFAULT_APPLICATION_PORT, app_state.clone()),
+        connection_establisher,
+    )
+}
+
+pub(super) fn app_with_storage(
+    storage: Arc<dyn RegistrationStorage>,
+) -> (Router, FakeConnectionEstablisher) {
+    let connection_establisher = FakeConnectionEstablisher::new();
+    let app_state = Arc::new(
+        AppState::new(connection_establisher.clone()).with_storage(storage),
+    );
+
+    (
+        api_gen::app(DEFAULT_APPLICATION_PORT, app_state.clone()),
+        connection_establisher,
+    )
+}
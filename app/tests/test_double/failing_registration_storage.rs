@@ -0,0 +1,61 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use api_gen::{
+    business::{
+        storage::{
+            RegistrationStorage, StorageFuture, memory_storage::MemoryStorage,
+        },
+        store::store_error::StoreError,
+    },
+    model::{internal::stored_port::StoredPort, port::Port},
+};
+
+/// Keeps registrations in memory until [`Self::fail`] is called, then fails
+/// every change with `error`.
+pub struct FailingRegistrationStorage {
+    storage: MemoryStorage,
+    error: StoreError,
+    failing: AtomicBool,
+}
+
+impl FailingRegistrationStorage {
+    pub fn new(error: StoreError) -> Self {
+        Self {
+            storage: MemoryStorage::default(),
+            error,
+            failing: AtomicBool::new(false),
+        }
+    }
+
+    pub fn fail(&self) {
+        self.failing.store(true, Ordering::Release);
+    }
+
+    fn failure(&self) -> Option<StorageFuture<'_, ()>> {
+        self.failing.load(Ordering::Acquire).then(
+            || -> StorageFuture<'_, ()> {
+                Box::pin(async move { Err(self.error.clone()) })
+            },
+        )
+    }
+}
+
+impl RegistrationStorage for FailingRegistrationStorage {
+    fn save_port(&self, stored_port: StoredPort) -> StorageFuture<'_, ()> {
+        self.failure()
+            .unwrap_or_else(|| self.storage.save_port(stored_port))
+    }
+
+    fn remove_port(&self, port: Port) -> StorageFuture<'_, ()> {
+        self.failure()
+            .unwrap_or_else(|| self.storage.remove_port(port))
+    }
+
+    fn clear(&self) -> StorageFuture<'_, ()> {
+        self.failure().unwrap_or_else(|| self.storage.clear())
+    }
+
+    fn load(&self) -> StorageFuture<'_, Vec<StoredPort>> {
+        self.storage.load()
+    }
+}
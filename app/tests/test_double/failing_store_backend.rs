@@ -0,0 +1,28 @@
+use api_gen::business::store::{
+    ReadOperation, StoreBackend, StoreFuture, WriteOperation,
+    store_error::StoreError,
+};
+
+/// Fails every operation with `error` without running it.
+pub struct FailingStoreBackend {
+    error: StoreError,
+}
+
+impl FailingStoreBackend {
+    pub fn new(error: StoreError) -> Self {
+        Self { error }
+    }
+}
+
+impl<V> StoreBackend<V> for FailingStoreBackend {
+    fn read<'a>(&'a self, _operation: ReadOperation<'a, V>) -> StoreFuture<'a> {
+        Box::pin(async move { Err(self.error.clone()) })
+    }
+
+    fn write<'a>(
+        &'a self,
+        _operation: WriteOperation<'a, V>,
+    ) -> StoreFuture<'a> {
+        Box::pin(async move { Err(self.error.clone()) })
+    }
+}
@@ -1,24 +1,50 @@
 use std::{
-    collections::HashMap,
-    sync::{Arc, RwLock},
+    collections::{HashMap, HashSet},
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicU16, AtomicU64, Ordering},
+    },
 };
 
 use api_gen::{
-    business::server::connection_establisher::ConnectionEstablisher,
-    model::error::Error,
-    util::lock::{safe_read, safe_write},
+    business::server::{
+        connection_establisher::{
+            BoundSocket, Connection, ConnectionEstablisher, ConnectionHandler,
+        },
+        path_matching::PathMatching,
+        socket::SocketHandler,
+    },
+    logging::http_trace::HttpTracingMiddleware,
+    model::{error::Error, port::Port},
 };
 use axum::Router;
-use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+const FIRST_EPHEMERAL_PORT: u16 = 49152;
+
+/// The live connections on each port, the newest one answers requests.
+type Connections<T> = Arc<RwLock<HashMap<String, Vec<(u64, T)>>>>;
 
 pub struct FakeConnectionEstablisher {
-    routers: Arc<RwLock<HashMap<String, Router>>>,
+    routers: Connections<Router>,
+    sockets: Connections<SocketHandler>,
+    next_connection_id: Arc<AtomicU64>,
+    unavailable_ports: Arc<RwLock<HashSet<String>>>,
+    failing_ports: Arc<RwLock<HashSet<String>>>,
+    next_ephemeral_port: Arc<AtomicU16>,
+    path_matching: PathMatching,
 }
 
 impl Clone for FakeConnectionEstablisher {
     fn clone(&self) -> Self {
         Self {
             routers: self.routers.clone(),
+            sockets: self.sockets.clone(),
+            next_connection_id: self.next_connection_id.clone(),
+            unavailable_ports: self.unavailable_ports.clone(),
+            failing_ports: self.failing_ports.clone(),
+            next_ephemeral_port: self.next_ephemeral_port.clone(),
+            path_matching: self.path_matching,
         }
     }
 }
@@ -27,39 +53,123 @@ impl FakeConnectionEstablisher {
     pub fn new() -> Self {
         Self {
             routers: Arc::new(RwLock::new(HashMap::new())),
+            sockets: Arc::new(RwLock::new(HashMap::new())),
+            next_connection_id: Arc::new(AtomicU64::new(0)),
+            unavailable_ports: Arc::new(RwLock::new(HashSet::new())),
+            failing_ports: Arc::new(RwLock::new(HashSet::new())),
+            next_ephemeral_port: Arc::new(AtomicU16::new(FIRST_EPHEMERAL_PORT)),
+            path_matching: PathMatching::default(),
         }
     }
+
+    /// Fails only the next connection on `port`, later ones succeed.
+    pub fn fail_next_connection(&self, port: &str) {
+        self.failing_ports.write().unwrap().insert(port.to_string());
+    }
+
+    pub fn with_path_matching(mut self, path_matching: PathMatching) -> Self {
+        self.path_matching = path_matching;
+        self
+    }
+
+    pub fn make_unavailable(&self, port: &str) {
+        self.unavailable_ports
+            .write()
+            .unwrap()
+            .insert(port.to_string());
+    }
 }
 
 impl ConnectionEstablisher for FakeConnectionEstablisher {
     async fn connect(
         &self,
-        port: String,
-        router: Router,
-    ) -> Result<JoinHandle<()>, Error> {
-        safe_write(&self.routers, |mut guard| {
-            guard.insert(port, router);
+        port: Port,
+        handler: ConnectionHandler,
+        _inherited: Option<BoundSocket>,
+        shutdown: CancellationToken,
+    ) -> Result<Connection, Error> {
+        let port = if port.is_ephemeral() {
+            Port::new(self.next_ephemeral_port.fetch_add(1, Ordering::Relaxed))
+        } else {
+            port
+        };
+        let key = port.to_string();
+
+        let unavailable = self.unavailable_ports.read().unwrap().contains(&key);
+        let failing = self.failing_ports.write().unwrap().remove(&key);
+
+        if unavailable || failing {
+            return Err(Error::Connection(format!(
+                "Failed to establish connection, port {port} is unavailable"
+            )));
+        }
+
+        let id = self.next_connection_id.fetch_add(1, Ordering::Relaxed);
+        match handler {
+            ConnectionHandler::Http(router, metrics) => {
+                let router = router.with_http_tracing(key.clone(), metrics);
+                connect(&self.routers, &key, id, router);
+            }
+            ConnectionHandler::Socket(handler) => {
+                connect(&self.sockets, &key, id, handler);
+            }
+        }
+
+        // Like a real server, the connection answers until it's shut down.
+        let routers = self.routers.clone();
+        let sockets = self.sockets.clone();
+        let handle = tokio::spawn(async move {
+            shutdown.cancelled().await;
+            disconnect(&routers, &key, id);
+            disconnect(&sockets, &key, id);
         });
 
-        Ok(tokio::spawn(async move {}))
+        Ok(Connection {
+            handle,
+            port,
+            socket: None,
+        })
     }
+
+    fn path_matching(&self) -> PathMatching {
+        self.path_matching
+    }
+}
+
+fn connect<T>(connections: &Connections<T>, port: &str, id: u64, handler: T) {
+    connections
+        .write()
+        .unwrap()
+        .entry(port.to_string())
+        .or_default()
+        .push((id, handler));
+}
+
+fn disconnect<T>(connections: &Connections<T>, port: &str, id: u64) {
+    if let Some(handlers) = connections.write().unwrap().get_mut(port) {
+        handlers.retain(|(handler_id, _)| *handler_id != id);
+    }
+}
+
+fn newest<T: Clone>(connections: &Connections<T>, port: &str) -> Option<T> {
+    let connections = connections.read().unwrap();
+    let (_, handler) = connections.get(port)?.last()?;
+
+    Some(handler.clone())
 }
 
 impl FakeConnectionEstablisher {
     pub fn get_router(&self, port: &str) -> Router {
-        let router = safe_read(&self.routers, |guard| match guard.get(port) {
-            Some(router) => router.clone(),
-            None => {
-                panic!("No server listening on port {}!", port)
-            }
-        });
-
-        match router {
+        match newest(&self.routers, port) {
             Some(router) => router,
-            None => panic!(
-                "Something went wrong while retrieving server on port {}!",
-                port
-            ),
+            None => panic!("No server listening on port {}!", port),
+        }
+    }
+
+    pub fn get_socket_handler(&self, port: &str) -> SocketHandler {
+        match newest(&self.sockets, port) {
+            Some(handler) => handler,
+            None => panic!("No socket listening on port {}!", port),
         }
     }
 }
@@ -1 +1,3 @@
+pub(crate) mod failing_registration_storage;
+pub(crate) mod failing_store_backend;
 pub(crate) mod fake_connection_establisher;
@@ -8,7 +8,7 @@ use crate::{
 mod arguments;
 mod task;
 
-const HELP_MESSAGE: &'static str = "
+const HELP_MESSAGE: &str = "
 Available Tasks:
     code-coverage: Generates the test coverage report (Use --use-lcov flag if LCOV report is needed)
     install-code-coverage-utility: Installs the utilities required to generate code coverage
@@ -79,11 +79,13 @@ impl GenerateCodeCoverageTask {
         );
 
         create_dir_all(GenerateCodeCoverageTask::DEFAULT_TARGET_COVERAGE_DIR)
-            .expect(&format!(
-                "[TASK: {}]: Failed to create {} directory.",
-                GenerateCodeCoverageTask::TASK_NAME,
-                GenerateCodeCoverageTask::DEFAULT_TARGET_COVERAGE_DIR,
-            ));
+            .unwrap_or_else(|_| {
+                panic!(
+                    "[TASK: {}]: Failed to create {} directory.",
+                    GenerateCodeCoverageTask::TASK_NAME,
+                    GenerateCodeCoverageTask::DEFAULT_TARGET_COVERAGE_DIR
+                )
+            });
 
         println!(
             "[TASK: {}]: Successfully created directory {}.",
@@ -120,10 +122,7 @@ impl GenerateCodeCoverageTask {
                 "--exclude",
                 "tasks",
                 "--output-dir",
-                &format!(
-                    "{}",
-                    GenerateCodeCoverageTask::DEFAULT_TARGET_COVERAGE_DIR
-                ),
+                GenerateCodeCoverageTask::DEFAULT_TARGET_COVERAGE_DIR,
             ],
         );
     }
@@ -142,11 +141,9 @@ impl GenerateCodeCoverageTask {
             .current_dir(project_root)
             .args(cli_args)
             .status()
-            .expect(&format!(
-                "[TASK: {}]: Something went wrong while generating code coverage ({}).",
+            .unwrap_or_else(|_| panic!("[TASK: {}]: Something went wrong while generating code coverage ({}).",
                 GenerateCodeCoverageTask::TASK_NAME,
-                format,
-            ));
+                format));
 
         if !result.success() {
             panic!(
@@ -173,16 +170,14 @@ impl GenerateCodeCoverageTask {
         let project_root = get_project_root();
         let result = Command::new("genhtml")
             .current_dir(project_root)
-            .args(&[
+            .args([
                 &format!("{}/lcov.info", GenerateCodeCoverageTask::DEFAULT_TARGET_COVERAGE_DIR),
                 "-o",
                 GenerateCodeCoverageTask::DEFAULT_TARGET_COVERAGE_DIR,
             ])
             .status()
-            .expect(&format!(
-                "[TASK: {}]: Something went wrong while generating HTML report from LCOV report.",
-                GenerateCodeCoverageTask::DEFAULT_TARGET_COVERAGE_DIR,
-            ));
+            .unwrap_or_else(|_| panic!("[TASK: {}]: Something went wrong while generating HTML report from LCOV report.",
+                GenerateCodeCoverageTask::DEFAULT_TARGET_COVERAGE_DIR));
 
         if !result.success() {
             panic!(
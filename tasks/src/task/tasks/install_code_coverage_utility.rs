@@ -77,13 +77,15 @@ impl MacOs {
         let project_root = get_project_root();
         let result = Command::new("brew")
             .current_dir(project_root)
-            .args(&["install", package_name])
+            .args(["install", package_name])
             .status()
-            .expect(&format!(
-                "[TASK: {}]: Something went wrong while installing `{}`.",
-                InstallCodeCoverageUtilityTask::TASK_NAME,
-                package_name,
-            ));
+            .unwrap_or_else(|_| {
+                panic!(
+                    "[TASK: {}]: Something went wrong while installing `{}`.",
+                    InstallCodeCoverageUtilityTask::TASK_NAME,
+                    package_name
+                )
+            });
 
         if !result.success() {
             panic!(
@@ -105,7 +107,7 @@ impl MacOs {
         let project_root = get_project_root();
         let result = Command::new("brew")
             .current_dir(project_root)
-            .args(&["ls", "--versions", package_name])
+            .args(["ls", "--versions", package_name])
             .output();
 
         match result {